@@ -27,19 +27,22 @@ use fuel_indexer_utils::prelude::*;
 )]
 mod hello_world_native {
 
-    async fn index_logged_greeting(event: Greeting, block_data: BlockData) {
+    async fn index_logged_greeting(
+        event: Greeting,
+        block_data: BlockData,
+    ) -> IndexerResult<()> {
         let height = std::cmp::min(0, block_data.header.height - 1);
         let name = event.person.name.to_right_trimmed_str().to_string();
         let greeting = event.greeting.to_right_trimmed_str().to_string();
         let message = format!("{greeting} 👋, my name is {name}");
 
-        let greeter = Greeter::new(name, height).get_or_create().await;
+        let greeter = Greeter::new(name, height).get_or_create().await?;
 
         let salutation = Salutation::new(message, greeter.id.clone(), height)
             .get_or_create()
-            .await;
+            .await?;
 
-        greeter.save().await;
-        salutation.save().await;
+        greeter.save().await?;
+        salutation.save().await
     }
 }
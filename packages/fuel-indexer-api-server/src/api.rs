@@ -1,8 +1,9 @@
 use crate::{
     middleware::AuthenticationMiddleware,
     uses::{
-        get_nonce, graphql_playground, health_check, indexer_status, query_graph,
-        register_indexer_assets, remove_indexer, sql_query, verify_signature,
+        export_snapshot, get_nonce, get_schema, graphql_playground, health_check,
+        import_snapshot, indexer_status, query_graph, register_indexer_assets,
+        remove_indexer, sql_query, verify_signature,
     },
 };
 
@@ -11,8 +12,9 @@ use crate::middleware::MetricsMiddleware;
 
 use async_std::sync::{Arc, RwLock};
 use axum::{
+    body::Body,
     error_handling::HandleErrorLayer,
-    extract::{Extension, Json},
+    extract::{DefaultBodyLimit, Extension, Json},
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::{delete, get, post},
@@ -20,9 +22,11 @@ use axum::{
 };
 use fuel_indexer_database::{IndexerConnectionPool, IndexerDatabaseError};
 use fuel_indexer_graphql::graphql::GraphqlError;
-use fuel_indexer_lib::{config::IndexerConfig, defaults, utils::ServiceRequest};
+use fuel_indexer_lib::{
+    config::IndexerConfig, defaults, manifest::ManifestError, utils::ServiceRequest,
+};
 use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
-use hyper::Method;
+use hyper::{Method, Request};
 use serde_json::json;
 use std::{
     net::SocketAddr,
@@ -30,16 +34,19 @@ use std::{
     time::{Duration, Instant},
 };
 use thiserror::Error;
-use tokio::sync::mpsc::{error::SendError, Sender};
-use tower::ServiceBuilder;
+use tokio::sync::{
+    mpsc::{error::SendError, Sender},
+    watch,
+};
 use tower::{buffer::BufferLayer, limit::RateLimitLayer};
+use tower::{make::Shared, service_fn, ServiceBuilder, ServiceExt};
 use tower_http::{
     cors::{Any, CorsLayer},
     limit::RequestBodyLimitLayer,
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
     LatencyUnit,
 };
-use tracing::{error, Level};
+use tracing::{error, info, Level};
 
 /// Result type returned by web server operations.
 pub type ApiResult<T> = core::result::Result<T, ApiError>;
@@ -103,6 +110,8 @@ pub enum ApiError {
     SqlValidator(#[from] crate::sql::SqlValidatorError),
     #[error("ParseError: {0:?}")]
     ParseError(#[from] strum::ParseError),
+    #[error("Manifest error: {0:?}")]
+    Manifest(#[from] ManifestError),
     #[error("Other error: {0}")]
     OtherError(String),
 }
@@ -168,6 +177,9 @@ impl IntoResponse for ApiError {
                 // This is currently the only type of ParseError on the web server
                 (StatusCode::BAD_REQUEST, format!("Invalid asset type: {e}"))
             }
+            ApiError::Manifest(e) => {
+                (StatusCode::BAD_REQUEST, format!("Invalid manifest: {e}"))
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, generic_details),
         };
 
@@ -197,6 +209,7 @@ impl WebApi {
         let sm = SchemaManager::new(pool.clone());
         let schema_manager = Arc::new(RwLock::new(sm));
         let max_body_size = config.web_api.max_body_size;
+        let max_snapshot_size = config.web_api.max_snapshot_size;
         let start_time = Arc::new(Instant::now());
 
         // NOTE: We never expect to panic here, and if we do panic,
@@ -255,7 +268,7 @@ impl WebApi {
             .layer(Extension(config.clone()))
             .route("/:namespace/:identifier", delete(remove_indexer))
             .layer(AuthenticationMiddleware::from(&config))
-            .layer(Extension(tx))
+            .layer(Extension(tx.clone()))
             .layer(Extension(pool.clone()))
             .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
@@ -263,6 +276,23 @@ impl WebApi {
         #[cfg(feature = "metrics")]
         let indexer_routes = indexer_routes.layer(MetricsMiddleware::default());
 
+        let snapshot_routes = Router::new()
+            .route("/:namespace/:identifier", get(export_snapshot))
+            .layer(AuthenticationMiddleware::from(&config))
+            .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
+            .route("/:namespace/:identifier", post(import_snapshot))
+            .layer(AuthenticationMiddleware::from(&config))
+            .layer(Extension(tx))
+            .layer(Extension(schema_manager.clone()))
+            .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(max_snapshot_size));
+
+        #[cfg(feature = "metrics")]
+        let snapshot_routes = snapshot_routes.layer(MetricsMiddleware::default());
+
         let root_routes = Router::new()
             .route("/status", get(indexer_status))
             .layer(Extension(pool.clone()))
@@ -288,6 +318,15 @@ impl WebApi {
         #[cfg(feature = "metrics")]
         let auth_routes = auth_routes.layer(MetricsMiddleware::default());
 
+        // Like `/graph` and `/playground`, the schema of an indexer is intentionally
+        // public, since it can already be read through GraphQL introspection.
+        let schema_routes = Router::new()
+            .route("/:namespace/:identifier", get(get_schema))
+            .layer(Extension(schema_manager.clone()));
+
+        #[cfg(feature = "metrics")]
+        let schema_routes = schema_routes.layer(MetricsMiddleware::default());
+
         let playground_route = Router::new()
             .route("/:namespace/:identifier", get(graphql_playground))
             .layer(Extension(schema_manager))
@@ -301,7 +340,9 @@ impl WebApi {
             .nest("/", root_routes)
             .nest("/playground", playground_route)
             .nest("/index", indexer_routes)
+            .nest("/snapshot", snapshot_routes)
             .nest("/graph", graph_routes)
+            .nest("/schema", schema_routes)
             .nest("/sql", sql_routes)
             .nest("/auth", auth_routes);
 
@@ -360,4 +401,51 @@ impl WebApi {
 
         Ok(())
     }
+
+    /// Build a GraphQL API web server as an `axum` application and start the server,
+    /// rebuilding the application whenever the configuration is reloaded.
+    ///
+    /// Requests that are in flight when the configuration is reloaded are completed
+    /// by the previous application. The server's address is never changed by a reload.
+    pub async fn build_and_run_with_reloads(
+        mut config: watch::Receiver<IndexerConfig>,
+        pool: IndexerConnectionPool,
+        tx: Sender<ServiceRequest>,
+    ) -> ApiResult<()> {
+        let initial = config.borrow_and_update().clone();
+        let listen_on: SocketAddr = initial.web_api.clone().into();
+        let app = WebApi::build(initial, pool.clone(), tx.clone()).await?;
+
+        let current = std::sync::Arc::new(std::sync::Mutex::new(app));
+
+        let rebuild = tokio::spawn({
+            let current = current.clone();
+            async move {
+                while config.changed().await.is_ok() {
+                    let reloaded = config.borrow_and_update().clone();
+                    match WebApi::build(reloaded, pool.clone(), tx.clone()).await {
+                        Ok(app) => {
+                            *current.lock().expect("Poisoned app lock.") = app;
+                            info!("Web API reloaded with new configuration.");
+                        }
+                        Err(e) => error!("Failed to reload Web API: {e:?}"),
+                    }
+                }
+            }
+        });
+
+        let service = service_fn(move |req: Request<Body>| {
+            let app = current.lock().expect("Poisoned app lock.").clone();
+            app.oneshot(req)
+        });
+
+        let result = axum::Server::bind(&listen_on)
+            .serve(Shared::new(service))
+            .await;
+
+        rebuild.abort();
+        result?;
+
+        Ok(())
+    }
 }
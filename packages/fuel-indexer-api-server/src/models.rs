@@ -19,6 +19,16 @@ pub(crate) struct QueryResponse {
     pub data: Value,
 }
 
+/// GraphQL schema of an indexer.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SchemaResponse {
+    /// Schema in the GraphQL schema definition language.
+    pub sdl: String,
+
+    /// Result of an introspection query against the schema.
+    pub introspection: Value,
+}
+
 /// JWT claims.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Claims {
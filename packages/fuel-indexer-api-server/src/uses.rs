@@ -1,6 +1,6 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
-    models::{Claims, SqlQuery, VerifySignatureRequest},
+    models::{Claims, SchemaResponse, SqlQuery, VerifySignatureRequest},
     sql::SqlQueryValidator,
 };
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
@@ -15,14 +15,18 @@ use axum::{
 use fuel_crypto::{Message, Signature};
 use fuel_indexer_database::{
     queries,
-    types::{IndexerAsset, IndexerAssetType},
+    types::{
+        DeploymentStatus, IndexerAsset, IndexerAssetType, IndexerSnapshot, TableSnapshot,
+    },
     IndexerConnectionPool,
 };
-use fuel_indexer_graphql::dynamic::{build_dynamic_schema, execute_query};
+use fuel_indexer_graphql::dynamic::{build_dynamic_schema, execute_query, introspect};
 use fuel_indexer_lib::{
-    config::{auth::AuthenticationStrategy, IndexerConfig},
+    config::{auth::AuthenticationStrategy, FuelClientConfig, IndexerConfig},
     defaults,
     graphql::GraphQLSchema,
+    health::FUEL_NODE_HEALTH,
+    manifest::{ConfigSchema, Manifest},
     utils::{
         FuelClientHealthResponse, ReloadRequest, ServiceRequest, ServiceStatus,
         StopRequest,
@@ -30,6 +34,7 @@ use fuel_indexer_lib::{
     ExecutionSource,
 };
 use fuel_indexer_schema::db::manager::SchemaManager;
+use futures_util::future::join_all;
 use hyper::Client;
 use hyper_rustls::HttpsConnectorBuilder;
 use jsonwebtoken::{encode, EncodingKey, Header};
@@ -46,6 +51,8 @@ use http::Request;
 
 /// Given an indexer namespace and identifier, return the results for the given
 /// `GraphQLRequest`.
+///
+/// If the indexer has blue/green deployments, the query is served by the active one.
 pub(crate) async fn query_graph(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -73,8 +80,38 @@ pub(crate) async fn query_graph(
     }
 }
 
-/// Return the `ServiceStatus` for the Fuel client.
-pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
+/// Given an indexer namespace and identifier, return the GraphQL schema that the
+/// indexer's GraphQL API is served with, as SDL and as the result of an
+/// introspection query.
+///
+/// If the indexer has blue/green deployments, the schema of the active one is returned,
+/// since the `SchemaManager` loads the schema of the deployment serving the indexer.
+pub(crate) async fn get_schema(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+) -> ApiResult<axum::Json<SchemaResponse>> {
+    let schema = manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_e| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        })?;
+
+    let dynamic_schema = build_dynamic_schema(&schema)?;
+    let introspection = introspect(&dynamic_schema).await?;
+
+    Ok(Json(SchemaResponse {
+        sdl: dynamic_schema.sdl(),
+        introspection,
+    }))
+}
+
+/// Return the `ServiceStatus` for a Fuel node.
+pub(crate) async fn get_fuel_status(fuel_node: &FuelClientConfig) -> ServiceStatus {
     let https = HttpsConnectorBuilder::new()
         .with_native_roots()
         .https_or_http()
@@ -83,10 +120,7 @@ pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
         .build();
 
     let client = Client::builder().build::<_, hyper::Body>(https);
-    match client
-        .get(config.to_owned().fuel_node.health_check_uri())
-        .await
-    {
+    match client.get(fuel_node.to_owned().health_check_uri()).await {
         Ok(r) => {
             let body_bytes = hyper::body::to_bytes(r.into_body())
                 .await
@@ -98,7 +132,10 @@ pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
             ServiceStatus::from(clienth_health)
         }
         Err(e) => {
-            error!("Failed to fetch Fuel client health status: {e}.");
+            error!(
+                "Failed to fetch Fuel client health status for {}: {e}.",
+                fuel_node.to_string()
+            );
             ServiceStatus::NotOk
         }
     }
@@ -106,6 +143,9 @@ pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
 
 /// Return a JSON payload with the health status of various components, including
 /// the fuel client, the database, and the uptime of the service.
+///
+/// The health of a Fuel node is the health tracked by the indexers fetching blocks
+/// from it. Nodes that no indexer has used yet are probed instead.
 pub(crate) async fn health_check(
     Extension(config): Extension<IndexerConfig>,
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -113,10 +153,55 @@ pub(crate) async fn health_check(
 ) -> ApiResult<axum::Json<Value>> {
     let db_status = pool.is_connected().await.unwrap_or(ServiceStatus::NotOk);
     let uptime = start_time.elapsed().as_secs().to_string();
-    let client_status = get_fuel_status(&config).await;
+
+    let fuel_nodes =
+        join_all(config.fuel_nodes().into_iter().map(|fuel_node| async move {
+            let endpoint = fuel_node.to_string();
+            match FUEL_NODE_HEALTH.get(&endpoint) {
+                Some(health) => {
+                    let available = health.consecutive_errors == 0;
+                    let status = if available {
+                        ServiceStatus::OK
+                    } else {
+                        ServiceStatus::NotOk
+                    };
+                    let health = json!({
+                        "endpoint": endpoint,
+                        "status": status,
+                        "latency_ms": health.latency.map(|l| l.as_millis()),
+                        "consecutive_errors": health.consecutive_errors,
+                        "errors": health.errors,
+                        "head_height": health.head_height,
+                    });
+                    (available, health)
+                }
+                None => {
+                    let start = Instant::now();
+                    let status = get_fuel_status(&fuel_node).await;
+                    let available = matches!(status, ServiceStatus::OK);
+                    let health = json!({
+                        "endpoint": endpoint,
+                        "status": status,
+                        "latency_ms": start.elapsed().as_millis(),
+                    });
+                    (available, health)
+                }
+            }
+        }))
+        .await;
+
+    // The client is available as long as any of the Fuel nodes is available.
+    let client_status = if fuel_nodes.iter().any(|(available, _)| *available) {
+        ServiceStatus::OK
+    } else {
+        ServiceStatus::NotOk
+    };
+    let fuel_nodes: Vec<Value> =
+        fuel_nodes.into_iter().map(|(_, health)| health).collect();
 
     Ok(Json(json!({
         "client_status": client_status,
+        "fuel_nodes": fuel_nodes,
         "uptime": uptime,
         "database_status": db_status,
     })))
@@ -221,31 +306,46 @@ pub(crate) async fn register_indexer_assets(
 
     let multipart = multipart.ok_or_else(ApiError::default)?;
 
-    let (replace_indexer, asset_bytes) =
+    let (replace_indexer, blue_green, asset_bytes) =
         parse_register_indexer_multipart(multipart).await?;
 
     queries::start_transaction(&mut conn).await?;
 
-    let result = register_indexer_assets_transaction(
-        &mut conn,
-        schema_manager.clone(),
-        config,
-        &namespace,
-        &identifier,
-        claims.sub(),
-        replace_indexer,
-        asset_bytes,
-    )
-    .await;
+    let result = if blue_green {
+        register_indexer_deployment_transaction(
+            &mut conn,
+            schema_manager.clone(),
+            config,
+            &namespace,
+            &identifier,
+            claims.sub(),
+            asset_bytes,
+        )
+        .await
+        .map(|(deployment, assets)| (Some(deployment), assets))
+    } else {
+        register_indexer_assets_transaction(
+            &mut conn,
+            schema_manager.clone(),
+            config,
+            &namespace,
+            &identifier,
+            claims.sub(),
+            replace_indexer,
+            asset_bytes,
+        )
+        .await
+        .map(|assets| (None, assets))
+    };
 
     match result {
-        Ok(assets) => {
+        Ok((deployment, assets)) => {
             queries::commit_transaction(&mut conn).await?;
 
             if let Err(e) = tx
                 .send(ServiceRequest::Reload(ReloadRequest {
                     namespace,
-                    identifier,
+                    identifier: deployment.clone().unwrap_or(identifier),
                 }))
                 .await
             {
@@ -256,6 +356,7 @@ pub(crate) async fn register_indexer_assets(
             Ok(Json(json!({
                 "success": "true",
                 "assets": assets,
+                "deployment": deployment,
             })))
         }
         Err(e) => {
@@ -319,6 +420,18 @@ async fn register_indexer_assets_transaction(
         ))));
     }
 
+    // The config in the manifest must match the config schema declared by the
+    // indexer's module.
+    let asset = |typ| asset_bytes.iter().find(|(t, _)| *t == typ).map(|(_, d)| d);
+    if let (Some(manifest), Some(wasm)) = (
+        asset(IndexerAssetType::Manifest),
+        asset(IndexerAssetType::Wasm),
+    ) {
+        if let Some(config_schema) = ConfigSchema::from_wasm(wasm)? {
+            Manifest::try_from(manifest)?.validate_config(&config_schema)?;
+        }
+    }
+
     for (asset_type, data) in asset_bytes.iter() {
         match asset_type {
             IndexerAssetType::Wasm | IndexerAssetType::Manifest => {
@@ -368,14 +481,338 @@ async fn register_indexer_assets_transaction(
     Ok(assets)
 }
 
+// Register a new version of an existing indexer as a blue/green deployment.
+//
+// The new version is registered under a version-suffixed identifier (and thus
+// a version-suffixed schema), and backfills alongside the version currently
+// serving queries. The service cuts queries over to it once it reaches the
+// chain head.
+async fn register_indexer_deployment_transaction(
+    conn: &mut fuel_indexer_database::IndexerConnection,
+    schema_manager: Arc<RwLock<SchemaManager>>,
+    config: IndexerConfig,
+    namespace: &str,
+    identifier: &str,
+    pubkey: &str,
+    asset_bytes: Vec<(IndexerAssetType, Vec<u8>)>,
+) -> ApiResult<(String, Vec<IndexerAsset>)> {
+    if !config.replace_indexer {
+        error!("Failed to deploy Indexer({namespace}.{identifier}): replacing an indexer is not enabled.");
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Failed to deploy Indexer({namespace}.{identifier}): replacing an indexer is not enabled."
+        ))));
+    }
+
+    let mut deployments =
+        queries::indexer_deployments(conn, namespace, identifier).await?;
+
+    // The first blue/green deployment of an indexer makes the version that is
+    // currently registered the active deployment.
+    if deployments.is_empty() {
+        if queries::get_indexer_id(conn, namespace, identifier)
+            .await
+            .is_err()
+        {
+            return Err(ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) does not exist. Deploy it before deploying a new version of it."
+            ))));
+        }
+
+        let active = queries::register_deployment(
+            conn,
+            namespace,
+            identifier,
+            identifier,
+            DeploymentStatus::Active,
+        )
+        .await?;
+        deployments.push(active);
+    }
+
+    // A newer version supersedes any version that is still backfilling.
+    for d in deployments
+        .iter()
+        .filter(|d| d.status == DeploymentStatus::Pending)
+    {
+        queries::retire_deployment(conn, namespace, &d.deployment).await?;
+    }
+
+    let prefix = format!("{identifier}_v");
+    let version = deployments
+        .iter()
+        .filter_map(|d| d.deployment.strip_prefix(&prefix)?.parse::<u32>().ok())
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let deployment = format!("{prefix}{version}");
+
+    let asset_bytes = asset_bytes
+        .into_iter()
+        .map(|(asset_type, data)| {
+            if asset_type != IndexerAssetType::Manifest {
+                return Ok((asset_type, data));
+            }
+            let mut manifest = Manifest::try_from(&data)?;
+            manifest.set_identifier(deployment.clone());
+            Ok((asset_type, manifest.into()))
+        })
+        .collect::<ApiResult<Vec<_>>>()?;
+
+    let assets = register_indexer_assets_transaction(
+        conn,
+        schema_manager,
+        config,
+        namespace,
+        &deployment,
+        pubkey,
+        false,
+        asset_bytes,
+    )
+    .await?;
+
+    queries::register_deployment(
+        conn,
+        namespace,
+        identifier,
+        &deployment,
+        DeploymentStatus::Pending,
+    )
+    .await?;
+
+    Ok((deployment, assets))
+}
+
+/// Given an indexer namespace and identifier, return a snapshot of the indexer's
+/// registry metadata and indexed data, at the last block height it has indexed.
+pub(crate) async fn export_snapshot(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<IndexerSnapshot>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    // The snapshot is read in a single transaction, so that the data of every
+    // table is consistent with the block height of the snapshot.
+    queries::start_transaction(&mut conn).await?;
+    let result = export_snapshot_transaction(&mut conn, &namespace, &identifier).await;
+    queries::revert_transaction(&mut conn).await?;
+
+    Ok(Json(result?))
+}
+
+async fn export_snapshot_transaction(
+    conn: &mut fuel_indexer_database::IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> ApiResult<IndexerSnapshot> {
+    queries::execute_query(
+        conn,
+        "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ".to_string(),
+    )
+    .await?;
+
+    // If the indexer has blue/green deployments, the active one is exported.
+    let deployment = queries::active_deployment(conn, namespace, identifier)
+        .await?
+        .map(|d| d.deployment)
+        .unwrap_or_else(|| identifier.to_string());
+
+    let indexer_id = queries::get_indexer_id(conn, namespace, &deployment)
+        .await
+        .map_err(|_e| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) was not found."
+            )))
+        })?;
+
+    let assets = queries::indexer_assets(conn, &indexer_id).await?;
+    let block_height =
+        queries::last_block_height_for_indexer(conn, namespace, &deployment).await?;
+
+    let mut manifest = Manifest::try_from(&assets.manifest.bytes)
+        .map_err(|e| ApiError::OtherError(e.to_string()))?;
+    manifest.set_identifier(identifier.to_string());
+    let manifest: Vec<u8> = manifest.into();
+
+    let mut tables = Vec::new();
+    for name in queries::indexer_tables(conn, namespace, &deployment).await? {
+        let rows = queries::export_table(conn, namespace, &deployment, &name).await?;
+        tables.push(TableSnapshot { name, rows });
+    }
+
+    let contract_ids = queries::contract_ids(conn, namespace, &deployment).await?;
+    let state = queries::indexer_state(conn, namespace, &deployment)
+        .await?
+        .into_iter()
+        .map(|(key, value)| (key, hex::encode(value)))
+        .collect();
+
+    Ok(IndexerSnapshot {
+        namespace: namespace.to_string(),
+        identifier: identifier.to_string(),
+        block_height,
+        manifest: String::from_utf8(manifest)
+            .map_err(|e| ApiError::OtherError(e.to_string()))?,
+        schema: String::from_utf8(assets.schema.bytes)
+            .map_err(|e| ApiError::OtherError(e.to_string()))?,
+        wasm: hex::encode(assets.wasm.bytes),
+        tables,
+        contract_ids,
+        state,
+    })
+}
+
+/// Given an indexer namespace and identifier, register the indexer and the data
+/// of an `IndexerSnapshot` in the database, and send a `ServiceRequest::Reload`
+/// to the service for this indexer, so that it resumes from the snapshot's block height.
+pub(crate) async fn import_snapshot(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(config): Extension<IndexerConfig>,
+    Json(snapshot): Json<IndexerSnapshot>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    // The type IDs compiled into the module are derived from the namespace and
+    // identifier of the indexer, so a snapshot can't be imported under another UID.
+    if snapshot.namespace != namespace || snapshot.identifier != identifier {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Snapshot of Indexer({}) can not be imported as Indexer({namespace}.{identifier}).",
+            snapshot.uid()
+        ))));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    queries::start_transaction(&mut conn).await?;
+
+    let result = import_snapshot_transaction(
+        &mut conn,
+        schema_manager,
+        config,
+        claims.sub(),
+        &snapshot,
+    )
+    .await;
+
+    match result {
+        Ok(()) => {
+            queries::commit_transaction(&mut conn).await?;
+
+            if let Err(e) = tx
+                .send(ServiceRequest::Reload(ReloadRequest {
+                    namespace,
+                    identifier,
+                }))
+                .await
+            {
+                error!("Failed to send ServiceRequest::Reload: {e:?}");
+                return Err(e.into());
+            }
+
+            Ok(Json(json!({
+                "success": "true",
+                "block_height": snapshot.block_height,
+            })))
+        }
+        Err(e) => {
+            queries::revert_transaction(&mut conn).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn import_snapshot_transaction(
+    conn: &mut fuel_indexer_database::IndexerConnection,
+    schema_manager: Arc<RwLock<SchemaManager>>,
+    config: IndexerConfig,
+    pubkey: &str,
+    snapshot: &IndexerSnapshot,
+) -> ApiResult<()> {
+    let IndexerSnapshot {
+        namespace,
+        identifier,
+        ..
+    } = snapshot;
+
+    let asset_bytes = vec![
+        (
+            IndexerAssetType::Manifest,
+            snapshot.manifest.as_bytes().to_vec(),
+        ),
+        (
+            IndexerAssetType::Schema,
+            snapshot.schema.as_bytes().to_vec(),
+        ),
+        (IndexerAssetType::Wasm, hex::decode(&snapshot.wasm)?),
+    ];
+
+    // Importing a snapshot never replaces an existing indexer.
+    register_indexer_assets_transaction(
+        conn,
+        schema_manager,
+        config,
+        namespace,
+        identifier,
+        pubkey,
+        false,
+        asset_bytes,
+    )
+    .await?;
+
+    let tables = queries::indexer_tables(conn, namespace, identifier).await?;
+    for table in snapshot.tables.iter() {
+        if !tables.contains(&table.name) {
+            return Err(ApiError::Http(HttpError::Conflict(format!(
+                "Table '{}' of the snapshot is not part of Indexer({}).",
+                table.name,
+                snapshot.uid()
+            ))));
+        }
+
+        queries::import_table(conn, namespace, identifier, &table.name, &table.rows)
+            .await?;
+    }
+
+    for contract_id in snapshot.contract_ids.iter() {
+        queries::register_contract_id(conn, namespace, identifier, contract_id, None)
+            .await?;
+    }
+
+    for (key, value) in snapshot.state.iter() {
+        queries::put_state(conn, namespace, identifier, key, &hex::decode(value)?)
+            .await?;
+    }
+
+    Ok(())
+}
+
 // This function parses the `Multipart` struct set to the deploy indexer
-// endpoint. It extracts the `bool` value indicating whether to replace an
-// indexer if it already exists, and extracts the indexer assets: manifest,
-// schema, and the WASM module.
+// endpoint. It extracts the `bool` values indicating whether to replace an
+// indexer if it already exists and whether to deploy it as a blue/green
+// deployment, and extracts the indexer assets: manifest, schema, and the WASM
+// module.
 async fn parse_register_indexer_multipart(
     mut multipart: Multipart,
-) -> ApiResult<(bool, Vec<(IndexerAssetType, Vec<u8>)>)> {
+) -> ApiResult<(bool, bool, Vec<(IndexerAssetType, Vec<u8>)>)> {
     let mut replace_indexer: bool = false;
+    let mut blue_green: bool = false;
     let mut assets: Vec<(IndexerAssetType, Vec<u8>)> = vec![];
 
     while let Ok(Some(field)) = multipart.next_field().await {
@@ -388,6 +825,12 @@ async fn parse_register_indexer_multipart(
                     .parse::<bool>()
                     .map_err(|e| ApiError::OtherError(e.to_string()))?;
             }
+            "blue_green" => {
+                blue_green = std::str::from_utf8(&data.to_owned())
+                    .map_err(|e| ApiError::OtherError(e.to_string()))?
+                    .parse::<bool>()
+                    .map_err(|e| ApiError::OtherError(e.to_string()))?;
+            }
             name => {
                 let asset_type = IndexerAssetType::from_str(name)?;
                 assets.push((asset_type, data.to_vec()));
@@ -395,7 +838,7 @@ async fn parse_register_indexer_multipart(
         };
     }
 
-    Ok((replace_indexer, assets))
+    Ok((replace_indexer, blue_green, assets))
 }
 
 /// Return a `Nonce` to be used for authentication.
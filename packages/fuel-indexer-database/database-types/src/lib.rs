@@ -7,25 +7,28 @@ use async_graphql_parser::{
     types::{FieldDefinition, ObjectType, TypeDefinition, TypeKind},
     Pos, Positioned,
 };
-use async_graphql_value::Name;
+use async_graphql_value::{ConstValue, Name};
 use chrono::{
-    serde::ts_microseconds,
+    serde::{ts_microseconds, ts_microseconds_option},
     {DateTime, Utc},
 };
 use fuel_indexer_lib::{
     graphql::{
-        extract_foreign_key_info, field_id, is_list_type,
+        extract_foreign_key_info, field_check_expr, field_default_value, field_id,
+        is_list_type, object_indices, provenance_fields,
         types::{IdCol, ObjectCol},
         JoinTableMeta, ParsedGraphQLSchema,
     },
+    utils::sha256_digest,
     MAX_ARRAY_LENGTH,
 };
 use fuel_indexer_types::type_id;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     fmt,
     fmt::Write,
+    str::FromStr,
     string::ToString,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -42,6 +45,38 @@ pub enum IndexMethod {
     /// SQL Hash index.
     #[strum(serialize = "hash")]
     Hash,
+
+    /// SQL GIN index over the text search vector of a column.
+    #[strum(serialize = "gin")]
+    Gin,
+}
+
+/// Text search configuration used to build and query full-text search indices.
+pub const TEXT_SEARCH_CONFIG: &str = "english";
+
+/// Maximum length, in bytes, of a Postgres identifier. Longer identifiers are
+/// silently truncated by Postgres.
+pub const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+/// Number of characters of the hash appended to shortened identifiers.
+const SHORTENED_IDENTIFIER_HASH_LENGTH: usize = 8;
+
+/// Shorten an identifier to at most `MAX_IDENTIFIER_LENGTH` bytes.
+///
+/// The end of a long identifier is replaced with a hash of the whole identifier, so
+/// that identifiers sharing a long prefix remain distinct once shortened.
+pub fn shorten_identifier(name: String) -> String {
+    if name.len() <= MAX_IDENTIFIER_LENGTH {
+        return name;
+    }
+
+    let hash = &sha256_digest(&name)[..SHORTENED_IDENTIFIER_HASH_LENGTH];
+    let mut end = MAX_IDENTIFIER_LENGTH - SHORTENED_IDENTIFIER_HASH_LENGTH - 1;
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}_{hash}", &name[..end])
 }
 
 /// SQL database types used by indexers.
@@ -312,6 +347,9 @@ pub struct Column {
     /// Whether this column is nullable.
     pub nullable: bool,
 
+    /// SQL literal of the column's default value, if any.
+    pub default: Option<String>,
+
     /// SQL type of the array's contents
     ///
     /// Only if this is a `ColumnType::Array`
@@ -362,6 +400,7 @@ impl Column {
                     position,
                     unique,
                     nullable: f.ty.node.nullable,
+                    default: field_default_value(f).map(|v| sql_literal(&v)),
                     persistence,
                     ..Self::default()
                 }
@@ -456,16 +495,34 @@ impl Column {
     }
 }
 
+/// Return the SQL literal for a given GraphQL value.
+///
+/// Only used for the values of `@default` directives, which have already been validated
+/// to be numbers, strings, booleans, or enum values.
+fn sql_literal(value: &ConstValue) -> String {
+    match value {
+        ConstValue::String(s) => format!("'{}'", s.replace('\'', "''")),
+        ConstValue::Enum(name) => format!("'{name}'"),
+        other => other.to_string(),
+    }
+}
+
 impl SqlFragment for Column {
     /// Return the SQL create statement for a `Column`.
     fn create(&self) -> String {
         let null_frag = if self.nullable { "" } else { "not null" };
         let unique_frag = if self.unique { "unique" } else { "" };
+        let default_frag = self
+            .default
+            .as_ref()
+            .map(|default| format!(" default {default}"))
+            .unwrap_or_default();
         format!(
-            "{} {} {} {}",
+            "{} {}{} {} {}",
             self.name,
             // Will only panic if given an array type
             self.sql_type(),
+            default_frag,
             null_frag,
             unique_frag
         )
@@ -632,6 +689,114 @@ impl RegisteredIndexer {
     }
 }
 
+/// Status of a blue/green deployment of an indexer.
+#[derive(
+    Debug, Eq, PartialEq, Clone, Copy, EnumString, AsRefStr, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentStatus {
+    /// The deployment is backfilling, while queries are served by the active deployment.
+    #[strum(serialize = "pending")]
+    Pending,
+
+    /// The deployment serves the queries of the indexer.
+    #[strum(serialize = "active")]
+    Active,
+
+    /// The deployment has been replaced, and its data will be removed once the
+    /// grace period has elapsed.
+    #[strum(serialize = "retired")]
+    Retired,
+}
+
+/// A version of an indexer deployed side by side with the other versions of that indexer.
+///
+/// Each deployment is registered as a separate indexer, whose identifier is suffixed
+/// with the version of the deployment, so that its tables reside in their own schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerDeployment {
+    /// Database ID of the deployment.
+    pub id: i64,
+
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer, under which queries are served.
+    pub identifier: String,
+
+    /// Identifier under which this deployment is registered.
+    pub deployment: String,
+
+    /// Status of the deployment.
+    pub status: DeploymentStatus,
+
+    /// Time at which the deployment was created.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+
+    /// Time at which the deployment was retired, if it has been.
+    #[serde(with = "ts_microseconds_option")]
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+impl IndexerDeployment {
+    /// Return the unique identifier (UID) of the indexer registered for this deployment.
+    pub fn uid(&self) -> String {
+        format!("{}.{}", self.namespace, self.deployment)
+    }
+}
+
+/// A portable archive of an indexer's registry metadata and indexed data, taken at
+/// a consistent block height.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerSnapshot {
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Last block height indexed by the indexer when the snapshot was taken.
+    pub block_height: u32,
+
+    /// Manifest of the indexer.
+    pub manifest: String,
+
+    /// GraphQL schema of the indexer.
+    pub schema: String,
+
+    /// Hex-encoded WASM module of the indexer.
+    pub wasm: String,
+
+    /// Data of each of the indexer's tables.
+    pub tables: Vec<TableSnapshot>,
+
+    /// Hex-encoded contract IDs registered by the indexer's handlers.
+    #[serde(default)]
+    pub contract_ids: Vec<String>,
+
+    /// Hex-encoded values of the indexer's state, by key.
+    #[serde(default)]
+    pub state: BTreeMap<String, String>,
+}
+
+impl IndexerSnapshot {
+    /// Return the unique identifier (UID) of the indexer in this snapshot.
+    pub fn uid(&self) -> String {
+        format!("{}.{}", self.namespace, self.identifier)
+    }
+}
+
+/// The rows of a table in an `IndexerSnapshot`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSnapshot {
+    /// Name of the table.
+    pub name: String,
+
+    /// Rows of the table, as a JSON array.
+    pub rows: String,
+}
+
 /// SQL database types used by indexers.
 #[derive(Eq, PartialEq, Debug, Clone, Default)]
 pub enum DbType {
@@ -691,6 +856,40 @@ impl SqlFragment for PrimaryKey {
     }
 }
 
+/// Merge indices over the same columns that would otherwise be created twice under
+/// the same name. This happens when a field is `@indexed` (or `@unique`) and also
+/// indexed by an `@index` directive on its object.
+///
+/// A unique index serves the same lookups as a non-unique one, so the merged index
+/// is unique if any of the merged indices is. Likewise, a btree index serves the
+/// same lookups as a hash one, so a btree index and a hash index over the same
+/// columns are merged into a btree index.
+fn merge_duplicate_indices(constraints: Vec<Constraint>) -> Vec<Constraint> {
+    let mut merged: Vec<Constraint> = Vec::with_capacity(constraints.len());
+    for constraint in constraints {
+        if let Constraint::Index(index) = &constraint {
+            let duplicate = merged.iter_mut().find_map(|c| match c {
+                Constraint::Index(existing)
+                    if existing.column_names == index.column_names
+                        && existing.sql_name() == index.sql_name() =>
+                {
+                    Some(existing)
+                }
+                _ => None,
+            });
+            if let Some(existing) = duplicate {
+                existing.unique |= index.unique;
+                if index.method == IndexMethod::BTree {
+                    existing.method = IndexMethod::BTree;
+                }
+                continue;
+            }
+        }
+        merged.push(constraint);
+    }
+    merged
+}
+
 /// SQL index constraint for a given column.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct SqlIndex {
@@ -709,14 +908,19 @@ pub struct SqlIndex {
     /// Whether this index is unique.
     pub unique: bool,
 
-    /// Name of column to which index is applied.
-    pub column_name: String,
+    /// Name of columns to which index is applied.
+    pub column_names: Vec<String>,
 }
 
 impl SqlNamed for SqlIndex {
     /// Return the SQL name of the index.
     fn sql_name(&self) -> String {
-        format!("{}_{}_idx", &self.table_name, &self.column_name)
+        let cols = self.column_names.join("_");
+        let name = match self.method {
+            IndexMethod::Gin => format!("{}_{}_search_idx", &self.table_name, cols),
+            _ => format!("{}_{}_idx", &self.table_name, cols),
+        };
+        shorten_identifier(name)
     }
 }
 
@@ -730,14 +934,24 @@ impl SqlFragment for SqlIndex {
 
         match self.db_type {
             DbType::Postgres => {
+                let cols = self
+                    .column_names
+                    .iter()
+                    .map(|col| match self.method {
+                        IndexMethod::Gin => {
+                            format!("to_tsvector('{TEXT_SEARCH_CONFIG}', {col})")
+                        }
+                        _ => col.clone(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
                 let _ = write!(
                     frag,
-                    "INDEX {} ON {}.{} USING {} ({});",
+                    "INDEX {} ON {}.{} USING {} ({cols});",
                     self.sql_name(),
                     self.namespace,
                     self.table_name,
                     self.method.as_ref(),
-                    self.column_name
                 );
             }
         }
@@ -782,6 +996,9 @@ pub enum Constraint {
 
     /// SQL primary key constraint.
     Pk(PrimaryKey),
+
+    /// SQL check constraint.
+    Check(CheckConstraint),
 }
 
 impl SqlFragment for Constraint {
@@ -791,6 +1008,48 @@ impl SqlFragment for Constraint {
             Constraint::Index(idx) => idx.create(),
             Constraint::Fk(fk) => fk.create(),
             Constraint::Pk(pk) => pk.create(),
+            Constraint::Check(ck) => ck.create(),
+        }
+    }
+}
+
+/// SQL check constraint for a given column.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct CheckConstraint {
+    /// The type of database.
+    pub db_type: DbType,
+
+    /// Name of table on which constraint is applied.
+    pub table_name: String,
+
+    /// Fully qualified namespace of the indexer.
+    pub namespace: String,
+
+    /// Name of column on which the constraint is declared.
+    pub column_name: String,
+
+    /// Boolean expression that every row must satisfy.
+    pub expr: String,
+}
+
+impl SqlNamed for CheckConstraint {
+    /// Return the SQL name of the check constraint.
+    fn sql_name(&self) -> String {
+        format!("{}_{}_check", self.table_name, self.column_name)
+    }
+}
+
+impl SqlFragment for CheckConstraint {
+    /// Return the SQL create statement for a `CheckConstraint`.
+    fn create(&self) -> String {
+        match self.db_type {
+            DbType::Postgres => format!(
+                "ALTER TABLE {}.{} ADD CONSTRAINT {} CHECK ({});",
+                self.namespace,
+                self.table_name,
+                self.sql_name(),
+                self.expr
+            ),
         }
     }
 }
@@ -990,12 +1249,39 @@ impl Table {
                                 table_name: typ.name.to_string().to_lowercase(),
                                 namespace: parsed.fully_qualified_namespace(),
                                 unique: has_unique,
-                                column_name: f.node.name.to_string(),
+                                column_names: vec![f.node.name.to_string()],
                                 ..SqlIndex::default()
                             }));
                         }
 
 
+                        let has_search = f
+                            .node
+                            .directives
+                            .iter()
+                            .any(|d| d.node.name.to_string() == "search");
+
+                        if has_search {
+                            constraints.push(Constraint::Index(SqlIndex {
+                                db_type: DbType::Postgres,
+                                table_name: typ.name.to_string().to_lowercase(),
+                                namespace: parsed.fully_qualified_namespace(),
+                                method: IndexMethod::Gin,
+                                column_names: vec![f.node.name.to_string()],
+                                ..SqlIndex::default()
+                            }));
+                        }
+
+                        if let Some(expr) = field_check_expr(&f.node) {
+                            constraints.push(Constraint::Check(CheckConstraint {
+                                db_type: DbType::Postgres,
+                                table_name: typ.name.to_string().to_lowercase(),
+                                namespace: parsed.fully_qualified_namespace(),
+                                column_name: f.node.name.to_string(),
+                                expr,
+                            }));
+                        }
+
                         let field_typ = f.node.ty.node.to_string().replace(['[', ']', '!'], "");
                         if parsed.is_possible_foreign_key(&field_typ) {
                             let (ref_coltype, ref_colname, ref_tablename) =
@@ -1017,6 +1303,36 @@ impl Table {
                         }
                 });
 
+                // Indices over one or more columns can also be declared on the object itself.
+                if persistence == Persistence::Scalar {
+                    constraints.extend(object_indices(typ).into_iter().map(|index| {
+                        Constraint::Index(SqlIndex {
+                            db_type: DbType::Postgres,
+                            table_name: typ.name.to_string().to_lowercase(),
+                            namespace: parsed.fully_qualified_namespace(),
+                            method: IndexMethod::from_str(&index.index_type.to_lowercase())
+                                .unwrap_or_default(),
+                            unique: index.unique,
+                            column_names: index.fields,
+                        })
+                    }));
+                }
+
+                // Provenance columns are filled in by the indexer service whenever an
+                // object is saved, so they're stored alongside the object's own fields.
+                if parsed.is_provenance_type(&typ.name.to_string()) {
+                    for f in provenance_fields() {
+                        let position = columns.len() as i32;
+                        columns.push(Column::from_field_def(
+                            &f,
+                            parsed,
+                            ty_id,
+                            position,
+                            persistence,
+                        ));
+                    }
+                }
+
                 // `Object` columns contain the `FtColumn` bytes for each
                 // column in the object. This column shouldn't really be public
                 columns.push(Column {
@@ -1037,7 +1353,7 @@ impl Table {
                     namespace: parsed.namespace().to_string(),
                     identifier: parsed.identifier().to_string(),
                     columns,
-                    constraints,
+                    constraints: merge_duplicate_indices(constraints),
                     persistence,
                     table_type: TableType::Regular
                 }
@@ -1198,11 +1514,11 @@ impl Table {
                 table_name: item.table_name(),
                 namespace: parsed.fully_qualified_namespace(),
                 unique: false,
-                column_name: format!(
+                column_names: vec![format!(
                     "{}_{}",
                     item.parent_table_name(),
                     item.parent_column_name()
-                ),
+                )],
                 ..SqlIndex::default()
             }),
             Constraint::Index(SqlIndex {
@@ -1210,11 +1526,11 @@ impl Table {
                 table_name: item.table_name(),
                 namespace: parsed.fully_qualified_namespace(),
                 unique: false,
-                column_name: format!(
+                column_names: vec![format!(
                     "{}_{}",
                     item.child_table_name(),
                     item.child_column_name()
-                ),
+                )],
                 ..SqlIndex::default()
             }),
         ];
@@ -1401,6 +1717,23 @@ type Person @entity {
         assert!(!column.nullable);
     }
 
+    #[test]
+    fn test_can_create_well_formed_search_index() {
+        let index = SqlIndex {
+            db_type: DbType::Postgres,
+            table_name: "person".to_string(),
+            namespace: "test_test".to_string(),
+            method: IndexMethod::Gin,
+            column_names: vec!["name".to_string()],
+            ..SqlIndex::default()
+        };
+
+        assert_eq!(
+            index.create(),
+            "CREATE INDEX person_name_search_idx ON test_test.person USING gin (to_tsvector('english', name));"
+        );
+    }
+
     #[test]
     fn test_can_create_well_formed_join_table_from_m2m_relationship() {
         let schema = r#"
@@ -1466,4 +1799,211 @@ type Wallet @entity {
             })
         );
     }
+
+    #[test]
+    fn test_can_create_well_formed_composite_indices_from_index_directives() {
+        let schema = r#"
+type Pool @entity {
+    id: ID!
+}
+
+type Position @entity @index(fields: ["pool", "owner"], unique: true) @index(fields: ["owner"], type: Hash) {
+    id: ID!
+    pool: Pool!
+    owner: Address!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Position").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+        let indices = table
+            .constraints()
+            .iter()
+            .filter_map(|c| match c {
+                Constraint::Index(idx) => Some(idx.create()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            indices,
+            vec![
+                "CREATE UNIQUE INDEX position_pool_owner_idx ON test_test.position USING btree (pool, owner);".to_string(),
+                "CREATE INDEX position_owner_idx ON test_test.position USING hash (owner);".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_indices_are_merged() {
+        let schema = r#"
+type Position @entity @index(fields: ["owner"]) @index(fields: ["pool"], unique: true) @index(fields: ["amount"], type: Hash) {
+    id: ID!
+    pool: UInt8! @indexed
+    owner: Address! @indexed
+    amount: UInt8! @indexed
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Position").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+        let indices = table
+            .constraints()
+            .iter()
+            .filter_map(|c| match c {
+                Constraint::Index(idx) => Some(idx.create()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            indices,
+            vec![
+                "CREATE UNIQUE INDEX position_pool_idx ON test_test.position USING btree (pool);".to_string(),
+                "CREATE INDEX position_owner_idx ON test_test.position USING btree (owner);".to_string(),
+                "CREATE INDEX position_amount_idx ON test_test.position USING btree (amount);".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_index_names_are_shortened() {
+        let index = |column: &str| SqlIndex {
+            db_type: DbType::Postgres,
+            table_name: "liquidity_position_snapshot".to_string(),
+            namespace: "test_test".to_string(),
+            column_names: vec![
+                "cumulative_fees_collected".to_string(),
+                column.to_string(),
+            ],
+            ..SqlIndex::default()
+        };
+
+        let a = index("last_updated_block_height").sql_name();
+        let b = index("last_updated_block_time").sql_name();
+
+        assert_eq!(a.len(), MAX_IDENTIFIER_LENGTH);
+        assert_eq!(b.len(), MAX_IDENTIFIER_LENGTH);
+        assert!(a.starts_with("liquidity_position_snapshot_cumulative_fees_collected_"));
+        assert_ne!(a, b);
+
+        assert_eq!(
+            shorten_identifier("person_name_idx".to_string()),
+            "person_name_idx"
+        );
+    }
+
+    #[test]
+    fn test_can_create_columns_with_default_and_check_directives() {
+        let schema = r#"
+enum Status {
+    Active
+    Paused
+}
+
+type Token @entity {
+    id: ID!
+    decimals: UInt1! @default(value: 9) @check(expr: "decimals <= 18")
+    name: Charfield! @default(value: "Unknown")
+    status: Status! @default(value: Active)
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Token").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+        let columns = table
+            .columns()
+            .iter()
+            .map(|c| c.create())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            columns[1..4],
+            [
+                "decimals integer default 9 not null".to_string(),
+                "name varchar(255) default 'Unknown' not null".to_string(),
+                "status varchar(255) default 'Active' not null".to_string(),
+            ]
+        );
+
+        let checks = table
+            .constraints()
+            .iter()
+            .filter_map(|c| match c {
+                Constraint::Check(chk) => Some(chk.create()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            checks,
+            vec![
+                "ALTER TABLE test_test.token ADD CONSTRAINT token_decimals_check CHECK (decimals <= 18);".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_can_create_provenance_columns_from_provenance_directive() {
+        let schema = r#"
+type Pool @entity @provenance {
+    id: ID!
+    reserve: UInt8!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Pool").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+        let columns = table
+            .columns()
+            .iter()
+            .map(|c| c.create())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            columns,
+            vec![
+                "id varchar(64) primary key not null".to_string(),
+                "reserve numeric(20, 0) not null".to_string(),
+                "_created_at_block integer".to_string(),
+                "_updated_at_block integer".to_string(),
+                "_updated_tx_id varchar(64)".to_string(),
+                "_created_at bigint".to_string(),
+                "_updated_at bigint".to_string(),
+                "object bytea not null".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS index_deployments (
+    id bigserial primary key,
+    namespace varchar(255) not null,
+    identifier varchar(255) not null,
+    deployment varchar(255) not null,
+    status varchar(16) not null,
+    created_at timestamptz not null default now(),
+    retired_at timestamptz,
+    UNIQUE(namespace, deployment)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS index_contract_ids (
+    id bigserial primary key,
+    namespace varchar(255) not null,
+    identifier varchar(255) not null,
+    contract_id varchar(64) not null,
+    block_height integer,
+    created_at timestamp not null default now(),
+    UNIQUE(namespace, identifier, contract_id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS index_state (
+    id bigserial primary key,
+    namespace varchar(255) not null,
+    identifier varchar(255) not null,
+    key varchar(255) not null,
+    value bytea not null,
+    UNIQUE(namespace, identifier, key)
+);
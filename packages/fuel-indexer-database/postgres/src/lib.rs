@@ -48,6 +48,22 @@ pub async fn get_object(
     Ok(row.get(0))
 }
 
+/// Fetch all of the blobs of serialized `FtColumns` returned by a query.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_objects(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+) -> sqlx::Result<Vec<Vec<u8>>> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    Ok(query
+        .fetch_all(conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect())
+}
+
 /// Run database migrations.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn run_migration(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<()> {
@@ -364,6 +380,7 @@ pub async fn list_column_by_id(
                     persistence: Persistence::from_str(persistence.as_str())
                         .expect("Bad persistence."),
                     array_coltype: array_coltype.map(|t| ColumnType::from(t.as_str())),
+                    default: None,
                 }
             })
             .collect::<Vec<Column>>(),
@@ -451,6 +468,46 @@ pub async fn get_indexer(
     }
 }
 
+/// Return the indexer serving the queries of the given indexer: its active
+/// blue/green deployment if it has one, or else the indexer itself.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_serving_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<RegisteredIndexer> {
+    let row = sqlx::query(
+        "SELECT * FROM index_registry
+        WHERE namespace = $1
+        AND identifier = COALESCE(
+            (
+                SELECT deployment FROM index_deployments
+                WHERE namespace = $1
+                AND identifier = $2
+                AND status = 'active'
+            ),
+            $2
+        )",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let created_at: DateTime<Utc> = {
+        let created_at: NaiveDateTime = row.get(4);
+        DateTime::<Utc>::from_naive_utc_and_offset(created_at, Utc)
+    };
+
+    Ok(RegisteredIndexer {
+        id: row.get(0),
+        namespace: row.get(1),
+        identifier: row.get(2),
+        pubkey: row.get(3),
+        created_at,
+    })
+}
+
 /// Register the given indexer's metadata.
 ///
 /// Note that this only reigsters the indexer's metadata. Indexer assets are registered separately.
@@ -653,6 +710,73 @@ pub async fn last_block_height_for_indexer(
         .unwrap_or(0))
 }
 
+/// Return the names of the tables in which the given indexer stores its data.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_tables(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    Ok(sqlx::query(
+        "SELECT table_name::TEXT FROM information_schema.tables
+        WHERE table_schema = $1 AND table_type = 'BASE TABLE'
+        ORDER BY table_name",
+    )
+    .bind(format!("{namespace}_{identifier}"))
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.get(0))
+    .collect())
+}
+
+/// Return every row of one of the given indexer's tables, as a JSON array.
+///
+/// The rows are returned as text, so that values such as `numeric`s are preserved exactly.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn export_table(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<String> {
+    let query = format!(
+        "SELECT COALESCE(json_agg(t), '[]'::json)::TEXT FROM {namespace}_{identifier}.{table} t"
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    Ok(row.get(0))
+}
+
+/// Insert rows, given as a JSON array, into one of the given indexer's tables.
+///
+/// User triggers are disabled while the rows are inserted, since the rows of a
+/// snapshot are not necessarily ordered by block height.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn import_table(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+    rows: &str,
+) -> sqlx::Result<usize> {
+    let table = format!("{namespace}_{identifier}.{table}");
+
+    execute_query(conn, format!("ALTER TABLE {table} DISABLE TRIGGER USER")).await?;
+
+    let result = sqlx::query(&format!(
+        "INSERT INTO {table} SELECT * FROM json_populate_recordset(NULL::{table}, $1::json)"
+    ))
+    .bind(rows)
+    .execute(&mut *conn)
+    .await?;
+
+    execute_query(conn, format!("ALTER TABLE {table} ENABLE TRIGGER USER")).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 // TODO: https://github.com/FuelLabs/fuel-indexer/issues/251
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn asset_already_exists(
@@ -822,11 +946,344 @@ pub async fn remove_indexer(
             format!("DROP SCHEMA IF EXISTS {namespace}_{identifier} CASCADE"),
         )
         .await?;
+
+        execute_query(
+            conn,
+            format!(
+                "DELETE FROM index_contract_ids WHERE namespace = '{namespace}' AND identifier = '{identifier}'"
+            ),
+        )
+        .await?;
+
+        execute_query(
+            conn,
+            format!(
+                "DELETE FROM index_state WHERE namespace = '{namespace}' AND identifier = '{identifier}'"
+            ),
+        )
+        .await?;
     }
 
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_deployments WHERE namespace = '{namespace}' AND deployment = '{identifier}'"
+        ),
+    )
+    .await?;
+
     Ok(())
 }
 
+fn deployment_from_row(row: PgRow) -> IndexerDeployment {
+    let status: String = row.get(4);
+
+    IndexerDeployment {
+        id: row.get(0),
+        namespace: row.get(1),
+        identifier: row.get(2),
+        deployment: row.get(3),
+        status: DeploymentStatus::from_str(&status).expect("Bad deployment status."),
+        created_at: row.get(5),
+        retired_at: row.get(6),
+    }
+}
+
+/// Register a deployment of an indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn register_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    deployment: &str,
+    status: DeploymentStatus,
+) -> sqlx::Result<IndexerDeployment> {
+    let row = sqlx::query(
+        "INSERT INTO index_deployments (namespace, identifier, deployment, status)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(deployment)
+    .bind(status.as_ref())
+    .fetch_one(conn)
+    .await?;
+
+    Ok(deployment_from_row(row))
+}
+
+/// Return all deployments of the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_deployments(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    Ok(sqlx::query(
+        "SELECT * FROM index_deployments
+        WHERE namespace = $1
+        AND identifier = $2
+        ORDER BY id",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(deployment_from_row)
+    .collect())
+}
+
+/// Return the deployment registered under the given identifier, if any.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<Option<IndexerDeployment>> {
+    Ok(sqlx::query(
+        "SELECT * FROM index_deployments
+        WHERE namespace = $1
+        AND deployment = $2",
+    )
+    .bind(namespace)
+    .bind(deployment)
+    .fetch_optional(conn)
+    .await?
+    .map(deployment_from_row))
+}
+
+/// Return the deployment serving the queries of the given indexer, if any.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn active_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerDeployment>> {
+    Ok(sqlx::query(
+        "SELECT * FROM index_deployments
+        WHERE namespace = $1
+        AND identifier = $2
+        AND status = 'active'",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?
+    .map(deployment_from_row))
+}
+
+/// Make the given pending deployment the one serving the queries of its indexer,
+/// and retire the previously active deployment.
+///
+/// Both changes are made in a single statement, so queries are never routed to a
+/// missing deployment. Returns `false` if the deployment is not pending.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn activate_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE index_deployments
+        SET status = CASE WHEN deployment = $2 THEN 'active' ELSE 'retired' END,
+            retired_at = CASE WHEN deployment = $2 THEN NULL ELSE now() END
+        WHERE namespace = $1
+        AND identifier = (
+            SELECT identifier FROM index_deployments
+            WHERE namespace = $1
+            AND deployment = $2
+            AND status = 'pending'
+        )
+        AND (status = 'active' OR deployment = $2)",
+    )
+    .bind(namespace)
+    .bind(deployment)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retire the given deployment.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn retire_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "UPDATE index_deployments
+        SET status = 'retired', retired_at = now()
+        WHERE namespace = $1
+        AND deployment = $2",
+    )
+    .bind(namespace)
+    .bind(deployment)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Return all retired deployments.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn retired_deployments(
+    conn: &mut PoolConnection<Postgres>,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    Ok(
+        sqlx::query("SELECT * FROM index_deployments WHERE status = 'retired'")
+            .fetch_all(conn)
+            .await?
+            .into_iter()
+            .map(deployment_from_row)
+            .collect(),
+    )
+}
+
+/// Track the given contract ID for an indexer, so that its receipts are
+/// dispatched to the indexer's handlers. Returns `false` if the contract ID was
+/// already being tracked.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn register_contract_id(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    contract_id: &str,
+    block_height: Option<u32>,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO index_contract_ids (namespace, identifier, contract_id, block_height)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(contract_id)
+    .bind(block_height.map(|h| h as i32))
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Return the contract IDs tracked by the given indexer, in the order in which
+/// they were registered.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn contract_ids(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    Ok(sqlx::query(
+        "SELECT contract_id FROM index_contract_ids
+        WHERE namespace = $1
+        AND identifier = $2
+        ORDER BY id",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.get(0))
+    .collect())
+}
+
+/// Return the value stored under the given key of an indexer's state, if any.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_state(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+) -> sqlx::Result<Option<Vec<u8>>> {
+    Ok(sqlx::query(
+        "SELECT value FROM index_state
+        WHERE namespace = $1
+        AND identifier = $2
+        AND key = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(key)
+    .fetch_optional(conn)
+    .await?
+    .map(|row| row.get(0)))
+}
+
+/// Store the given value under the given key of an indexer's state, replacing
+/// any value already stored under that key.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_state(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+    value: &[u8],
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_state (namespace, identifier, key, value)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (namespace, identifier, key) DO UPDATE SET value = EXCLUDED.value",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(key)
+    .bind(value)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove the given key from an indexer's state. Returns `false` if no value
+/// was stored under the key.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn delete_state(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM index_state
+        WHERE namespace = $1
+        AND identifier = $2
+        AND key = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(key)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Return all keys and values of an indexer's state, ordered by key.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_state(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<(String, Vec<u8>)>> {
+    Ok(sqlx::query(
+        "SELECT key, value FROM index_state
+        WHERE namespace = $1
+        AND identifier = $2
+        ORDER BY key",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.get(0), row.get(1)))
+    .collect())
+}
+
 /// Create a new nonce for a requesting user's authentication.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn create_nonce(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<Nonce> {
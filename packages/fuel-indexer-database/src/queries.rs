@@ -142,6 +142,16 @@ pub async fn get_object(
     }
 }
 
+/// Fetch all of the blobs of serialized `FtColumns` returned by a query.
+pub async fn get_objects(
+    conn: &mut IndexerConnection,
+    query: String,
+) -> sqlx::Result<Vec<Vec<u8>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::get_objects(c, query).await,
+    }
+}
+
 /// Run an arbitrary query and fetch all results.
 ///
 /// Note that if the results of the query can't be converted to `JsonValue`, this function
@@ -189,6 +199,20 @@ pub async fn new_root_columns(
     }
 }
 
+/// Return the indexer serving the queries of the given indexer: its active
+/// blue/green deployment if it has one, or else the indexer itself.
+pub async fn get_serving_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<RegisteredIndexer> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_serving_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
 /// Return the given indexer if it's already been registered.
 pub async fn get_indexer(
     conn: &mut IndexerConnection,
@@ -287,6 +311,48 @@ pub async fn last_block_height_for_indexer(
     }
 }
 
+/// Return the names of the tables in which the given indexer stores its data.
+pub async fn indexer_tables(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_tables(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return every row of one of the given indexer's tables, as a JSON array.
+pub async fn export_table(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<String> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::export_table(c, namespace, identifier, table).await
+        }
+    }
+}
+
+/// Insert rows, given as a JSON array, into one of the given indexer's tables.
+pub async fn import_table(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+    rows: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::import_table(c, namespace, identifier, table, rows).await
+        }
+    }
+}
+
 pub async fn asset_already_exists(
     conn: &mut IndexerConnection,
     asset_type: &IndexerAssetType,
@@ -357,6 +423,190 @@ pub async fn remove_indexer(
     }
 }
 
+/// Register a deployment of an indexer.
+pub async fn register_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    deployment: &str,
+    status: DeploymentStatus,
+) -> sqlx::Result<IndexerDeployment> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::register_deployment(c, namespace, identifier, deployment, status)
+                .await
+        }
+    }
+}
+
+/// Return all deployments of the given indexer.
+pub async fn indexer_deployments(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_deployments(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the deployment registered under the given identifier, if any.
+pub async fn get_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<Option<IndexerDeployment>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_deployment(c, namespace, deployment).await
+        }
+    }
+}
+
+/// Return the deployment serving the queries of the given indexer, if any.
+pub async fn active_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerDeployment>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::active_deployment(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Make the given pending deployment the one serving the queries of its indexer,
+/// and retire the previously active deployment.
+pub async fn activate_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<bool> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::activate_deployment(c, namespace, deployment).await
+        }
+    }
+}
+
+/// Retire the given deployment.
+pub async fn retire_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    deployment: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::retire_deployment(c, namespace, deployment).await
+        }
+    }
+}
+
+/// Return all retired deployments.
+pub async fn retired_deployments(
+    conn: &mut IndexerConnection,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::retired_deployments(c).await,
+    }
+}
+
+/// Track the given contract ID for an indexer. Returns `false` if the contract
+/// ID was already being tracked.
+pub async fn register_contract_id(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    contract_id: &str,
+    block_height: Option<u32>,
+) -> sqlx::Result<bool> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::register_contract_id(
+                c,
+                namespace,
+                identifier,
+                contract_id,
+                block_height,
+            )
+            .await
+        }
+    }
+}
+
+/// Return the contract IDs tracked by the given indexer.
+pub async fn contract_ids(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::contract_ids(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the value stored under the given key of an indexer's state, if any.
+pub async fn get_state(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+) -> sqlx::Result<Option<Vec<u8>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_state(c, namespace, identifier, key).await
+        }
+    }
+}
+
+/// Store the given value under the given key of an indexer's state.
+pub async fn put_state(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+    value: &[u8],
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_state(c, namespace, identifier, key, value).await
+        }
+    }
+}
+
+/// Remove the given key from an indexer's state. Returns `false` if no value
+/// was stored under the key.
+pub async fn delete_state(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &str,
+) -> sqlx::Result<bool> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::delete_state(c, namespace, identifier, key).await
+        }
+    }
+}
+
+/// Return all keys and values of an indexer's state.
+pub async fn indexer_state(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<(String, Vec<u8>)>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_state(c, namespace, identifier).await
+        }
+    }
+}
+
 /// Create a new nonce for a requesting user's authentication.
 pub async fn create_nonce(conn: &mut IndexerConnection) -> sqlx::Result<Nonce> {
     match conn {
@@ -1,5 +1,5 @@
-use super::graphql::GraphqlError;
-use fuel_indexer_database::DbType;
+use super::{graphql::GraphqlError, queries::JoinCondition};
+use fuel_indexer_database::{types::TEXT_SEARCH_CONFIG, DbType};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
@@ -27,19 +27,44 @@ impl QueryParams {
                     fully_qualified_table_name: fully_qualified_table_name.clone(),
                     filter_type: f,
                 }),
-                ParamType::Sort(field, order) => self.sorts.push(Sort {
-                    fully_qualified_table_name: format!(
-                        "{}.{}",
-                        fully_qualified_table_name, field
-                    ),
-                    order,
-                }),
+                ParamType::Sort(joins, field, order) => {
+                    // Fields of related entities are found on the last table joined.
+                    let mut table = fully_qualified_table_name.clone();
+                    let mut clauses = Vec::new();
+                    for join in joins.iter() {
+                        let (clause, alias) = related_join(&table, join);
+                        clauses.push(clause);
+                        table = alias;
+                    }
+                    self.sorts.push(Sort {
+                        fully_qualified_table_name: format!("{}.{}", table, field),
+                        order,
+                        joins: clauses,
+                    })
+                }
                 ParamType::Offset(n) => self.offset = Some(n),
                 ParamType::Limit(n) => self.limit = Some(n),
             }
         }
     }
 
+    /// Return the joins required by the query's filters and sorts, if any.
+    pub(crate) fn get_joins(&self) -> Vec<String> {
+        let mut joins = Vec::new();
+        for join in self
+            .filters
+            .iter()
+            .flat_map(|f| f.filter_type.joins(&f.fully_qualified_table_name))
+            .chain(self.sorts.iter().flat_map(|s| s.joins.clone()))
+        {
+            // A related entity that is both filtered and sorted on is only joined once.
+            if !joins.contains(&join) {
+                joins.push(join);
+            }
+        }
+        joins
+    }
+
     /// Return a string comprised of the query's filtering clauses, if any.
     pub(crate) fn get_filtering_expression(&self, db_type: &DbType) -> String {
         let mut query_clause = "".to_string();
@@ -99,7 +124,7 @@ impl Filter {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParamType {
     Filter(FilterType),
-    Sort(String, SortOrder),
+    Sort(Vec<JoinCondition>, String, SortOrder),
     Offset(u64),
     Limit(u64),
 }
@@ -108,6 +133,8 @@ pub enum ParamType {
 pub struct Sort {
     pub fully_qualified_table_name: String,
     pub order: SortOrder,
+    /// Joins to the tables of the related entities through which the sorted field is reached.
+    pub joins: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -166,6 +193,9 @@ pub enum FilterType {
     Membership(Membership),
     NullValueCheck(NullValueCheck),
     LogicOp(LogicOp),
+    Pattern(Pattern),
+    FullTextSearch(Vec<String>, String),
+    Related(JoinCondition, Box<FilterType>),
 }
 
 /// Represents an operation in which a record is compared against a particular value.
@@ -194,6 +224,18 @@ pub enum NullValueCheck {
     OnlyNulls(Vec<String>),
 }
 
+/// Represents an operation in which a record's string column value is matched against a pattern.
+///
+/// `StartsWith`, `EndsWith` and `Contains` match their value literally and are case-sensitive,
+/// whereas `Like` is a case-insensitive match in which `%` and `_` are wildcards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    StartsWith(String, String),
+    EndsWith(String, String),
+    Contains(String, String),
+    Like(String, String),
+}
+
 /// Represents an operation in which filters are associated with one another and evaluated together.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LogicOp {
@@ -244,10 +286,12 @@ impl FilterType {
                         r1.to_sql(fully_qualified_table.clone(), db_type),
                         r2.to_sql(fully_qualified_table, db_type)
                     ),
-                    // The NOT logical operator does not get turned into a string as
-                    // it will have already been used to transform a filter into its
-                    // inverse equivalent.
-                    _ => "".to_string(),
+                    // Most filters are transformed into their inverse equivalent when
+                    // negated; the NOT logical operator only remains for those filters
+                    // that have no such equivalent.
+                    LogicOp::Not(r) => {
+                        format!("NOT ({})", r.to_sql(fully_qualified_table, db_type))
+                    }
                 },
                 Self::Membership(m) => match m {
                     Membership::In(field, member_set) => {
@@ -289,6 +333,51 @@ impl FilterType {
                             .join(" AND ");
                     }
                 },
+                Self::Pattern(p) => match p {
+                    Pattern::StartsWith(field, val) => format!(
+                        "{fully_qualified_table}.{field} LIKE {}",
+                        quote_literal(&format!("{}%", escape_like_wildcards(val)))
+                    ),
+                    Pattern::EndsWith(field, val) => format!(
+                        "{fully_qualified_table}.{field} LIKE {}",
+                        quote_literal(&format!("%{}", escape_like_wildcards(val)))
+                    ),
+                    Pattern::Contains(field, val) => format!(
+                        "{fully_qualified_table}.{field} LIKE {}",
+                        quote_literal(&format!("%{}%", escape_like_wildcards(val)))
+                    ),
+                    Pattern::Like(field, val) => format!(
+                        "{fully_qualified_table}.{field} ILIKE {}",
+                        quote_literal(val)
+                    ),
+                },
+                // Filters on a related entity apply to the table joined through the
+                // foreign key, rather than the table of the entity being filtered.
+                Self::Related(join, f) => f.to_sql(
+                    related_table_alias(
+                        &fully_qualified_table,
+                        &join.referencing_key_col,
+                    ),
+                    db_type,
+                ),
+                // The expression on each column must match the expression of its
+                // search index in order for the index to be used.
+                Self::FullTextSearch(column_list, text) => {
+                    let query = format!(
+                        "plainto_tsquery('{TEXT_SEARCH_CONFIG}', {})",
+                        quote_literal(text)
+                    );
+                    format!(
+                        "({})",
+                        column_list
+                            .iter()
+                            .map(|col| format!(
+                                "to_tsvector('{TEXT_SEARCH_CONFIG}', {fully_qualified_table}.{col}) @@ {query}"
+                            ))
+                            .collect::<Vec<String>>()
+                            .join(" OR ")
+                    )
+                }
             },
         }
     }
@@ -364,10 +453,64 @@ impl FilterType {
                 ))),
                 LogicOp::Not(f) => Ok(*f.clone()),
             },
+            FilterType::Pattern(_) | FilterType::FullTextSearch(_, _) => {
+                Ok(FilterType::LogicOp(LogicOp::Not(Box::new(self.clone()))))
+            }
+            FilterType::Related(join, f) => {
+                Ok(FilterType::Related(join.clone(), Box::new(f.invert()?)))
+            }
+        }
+    }
+
+    /// Return the joins required to evaluate the filter on the table referenced as `table`.
+    fn joins(&self, table: &str) -> Vec<String> {
+        match self {
+            FilterType::Related(join, f) => {
+                let (clause, alias) = related_join(table, join);
+                let mut joins = vec![clause];
+                joins.extend(f.joins(&alias));
+                joins
+            }
+            FilterType::LogicOp(lo) => match lo {
+                LogicOp::And(r1, r2) | LogicOp::Or(r1, r2) => {
+                    let mut joins = r1.joins(table);
+                    joins.extend(r2.joins(table));
+                    joins
+                }
+                LogicOp::Not(f) => f.joins(table),
+            },
+            _ => vec![],
         }
     }
 }
 
+/// Quote a string as a SQL string literal.
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Escape the wildcard characters of a `LIKE` pattern so that a string is matched literally.
+fn escape_like_wildcards(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Operators that can be applied to a single field in a filter.
+pub(crate) const FIELD_OPERATORS: [&str; 11] = [
+    "between",
+    "equals",
+    "gt",
+    "gte",
+    "lt",
+    "lte",
+    "in",
+    "startsWith",
+    "endsWith",
+    "contains",
+    "like",
+];
+
 /// Parse an argument key-value pair into a `Filter`.
 ///
 /// `parse_arguments` is the entry point for parsing all API query arguments.
@@ -396,42 +539,41 @@ pub fn parse_argument_into_param(
         "id" => Ok(ParamType::Filter(FilterType::IdSelection(parse_value(
             &value,
         )?))),
-        "order" => {
-            if let Value::Object(obj) = value {
-                if let Some((field, sort_order)) = obj.into_iter().next() {
-                    if schema
+        "search" => {
+            let search_fields = entity_type
+                .map(|entity| {
+                    schema
                         .parsed()
-                        .graphql_type(entity_type, field.as_str())
-                        .is_some()
-                    {
-                        if let Value::Enum(sort_order) = sort_order {
-                            match sort_order.as_str() {
-                                "asc" => {
-                                    return Ok(ParamType::Sort(
-                                        field.to_string(),
-                                        SortOrder::Asc,
-                                    ))
-                                }
-                                "desc" => {
-                                    return Ok(ParamType::Sort(
-                                        field.to_string(),
-                                        SortOrder::Desc,
-                                    ))
-                                }
-                                other => {
-                                    return Err(GraphqlError::UnableToParseValue(
-                                        other.to_string(),
-                                    ))
-                                }
-                            }
-                        }
-                    } else {
-                        return Err(GraphqlError::UnsupportedValueType(
-                            sort_order.to_string(),
-                        ));
+                        .search_fields(&entity.replace(['[', ']', '!'], ""))
+                })
+                .unwrap_or_default();
+
+            if search_fields.is_empty() {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    entity_type
+                        .map(|entity| entity.to_string())
+                        .unwrap_or("root level object".to_string()),
+                    arg.to_string(),
+                ));
+            }
+
+            if let Value::Object(obj) = value {
+                match obj.get("text") {
+                    Some(Value::String(text)) => Ok(ParamType::Filter(
+                        FilterType::FullTextSearch(search_fields, text.clone()),
+                    )),
+                    Some(other) => {
+                        Err(GraphqlError::UnsupportedValueType(other.to_string()))
                     }
+                    None => Err(GraphqlError::NoPredicatesInFilter),
                 }
-                Err(GraphqlError::NoPredicatesInFilter)
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
+        "order" => {
+            if let Value::Object(obj) = value {
+                parse_sort_object(obj, entity_type, schema, Vec::new())
             } else {
                 Err(GraphqlError::UnsupportedValueType(value.to_string()))
             }
@@ -475,6 +617,144 @@ pub fn parse_argument_into_param(
     }
 }
 
+/// Parse an object from the "order" argument into a sort parameter.
+///
+/// A foreign key field can be given an object in place of a sort order, in
+/// which case records are sorted by a field of the related entity; `joins`
+/// holds the joins through which the related entity has been reached so far.
+fn parse_sort_object(
+    obj: IndexMap<Name, Value>,
+    entity_type: Option<&String>,
+    schema: &IndexerSchema,
+    mut joins: Vec<JoinCondition>,
+) -> Result<ParamType, GraphqlError> {
+    if let Some((field, sort_order)) = obj.into_iter().next() {
+        if schema
+            .parsed()
+            .graphql_type(entity_type, field.as_str())
+            .is_some()
+            && !is_derived_field(entity_type, field.as_str(), schema)
+        {
+            match sort_order {
+                Value::Enum(sort_order) => match sort_order.as_str() {
+                    "asc" => {
+                        return Ok(ParamType::Sort(
+                            joins,
+                            field.to_string(),
+                            SortOrder::Asc,
+                        ))
+                    }
+                    "desc" => {
+                        return Ok(ParamType::Sort(
+                            joins,
+                            field.to_string(),
+                            SortOrder::Desc,
+                        ))
+                    }
+                    other => {
+                        return Err(GraphqlError::UnableToParseValue(other.to_string()))
+                    }
+                },
+                Value::Object(inner_obj) => {
+                    if let Some((join, related_entity)) =
+                        related_entity_join(entity_type, field.as_str(), schema)
+                    {
+                        joins.push(join);
+                        return parse_sort_object(
+                            inner_obj,
+                            Some(&related_entity),
+                            schema,
+                            joins,
+                        );
+                    }
+                    return Err(GraphqlError::UnsupportedValueType(
+                        Value::Object(inner_obj).to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        } else {
+            return Err(GraphqlError::UnsupportedValueType(sort_order.to_string()));
+        }
+    }
+    Err(GraphqlError::NoPredicatesInFilter)
+}
+
+/// Return the join from an entity to the entity referenced by one of its foreign key
+/// fields, along with the name of the referenced entity.
+///
+/// Only single foreign key fields can be joined through; list fields cannot.
+fn related_entity_join(
+    entity_type: Option<&String>,
+    field: &str,
+    schema: &IndexerSchema,
+) -> Option<(JoinCondition, String)> {
+    let entity = entity_type?.replace(['[', ']', '!'], "");
+    let field_type = schema.parsed().graphql_type(Some(&entity), field)?;
+    if field_type.contains('[') {
+        return None;
+    }
+
+    let related_entity = field_type.replace('!', "");
+    if !schema.parsed().is_possible_foreign_key(&related_entity) {
+        return None;
+    }
+
+    let (ref_table, ref_col) = schema
+        .parsed()
+        .foreign_key_mappings()
+        .get(&entity.to_lowercase())?
+        .get(field)?;
+
+    let namespace = schema.parsed().fully_qualified_namespace();
+    let join = JoinCondition {
+        referencing_key_table: format!("{namespace}.{}", entity.to_lowercase()),
+        referencing_key_col: field.to_string(),
+        primary_key_table: format!("{namespace}.{ref_table}"),
+        primary_key_col: ref_col.clone(),
+    };
+
+    Some((join, related_entity))
+}
+
+/// Return the alias of the table joined through the foreign key `field` of the table
+/// referenced as `table`.
+///
+/// Related tables are aliased by the path of foreign key fields through which they are
+/// reached, so that a table can be joined more than once, e.g., through two foreign keys
+/// to the same entity, or through a foreign key of an entity to itself.
+fn related_table_alias(table: &str, field: &str) -> String {
+    let table = table.rsplit('.').next().unwrap_or(table);
+    format!("{table}__{field}")
+}
+
+/// Return the clause that joins the table referenced by `join` through the table
+/// referenced as `table`, along with the alias of the joined table.
+///
+/// Tables are left joined, so that entities whose foreign key is nullable and unset
+/// aren't dropped from the results.
+fn related_join(table: &str, join: &JoinCondition) -> (String, String) {
+    let alias = related_table_alias(table, &join.referencing_key_col);
+    let clause = format!(
+        "LEFT JOIN {} AS {alias} ON {table}.{} = {alias}.{}",
+        join.primary_key_table, join.referencing_key_col, join.primary_key_col
+    );
+    (clause, alias)
+}
+
+/// Whether a field of an entity is derived from a foreign key on another entity.
+fn is_derived_field(
+    entity_type: Option<&String>,
+    field: &str,
+    schema: &IndexerSchema,
+) -> bool {
+    entity_type.is_some_and(|entity| {
+        schema
+            .parsed()
+            .is_derived_field(&entity.replace(['[', ']', '!'], ""), field)
+    })
+}
+
 /// Parse an object from a parsed GraphQL document into a `Filter`.
 ///
 /// This serves as a helper function for starting the parsing operation for values under the "filter" key.
@@ -570,8 +850,36 @@ fn parse_arg_pred_pair(
             }
         }
         other => {
-            if schema.parsed().graphql_type(entity_type, other).is_some() {
+            // Derived fields have no column of their own, and so cannot be filtered on.
+            if is_derived_field(entity_type, other, schema) {
+                return Err(GraphqlError::UnrecognizedField(
+                    entity_type.cloned().unwrap_or_default(),
+                    other.to_string(),
+                ));
+            }
+
+            if let Some(field_type) = schema.parsed().graphql_type(entity_type, other) {
                 if let Value::Object(inner_obj) = predicate {
+                    // An object of anything but operators on a foreign key field is
+                    // a filter on the fields of the related entity.
+                    let is_related_filter = inner_obj
+                        .keys()
+                        .next()
+                        .is_some_and(|key| !FIELD_OPERATORS.contains(&key.as_str()));
+                    if is_related_filter {
+                        if let Some((join, related_entity)) =
+                            related_entity_join(entity_type, other, schema)
+                        {
+                            let filter = parse_filter_object(
+                                inner_obj,
+                                Some(&related_entity),
+                                schema,
+                                &mut None,
+                            )?;
+                            return Ok(FilterType::Related(join, Box::new(filter)));
+                        }
+                    }
+
                     for (key, predicate) in inner_obj.iter() {
                         match key.as_str() {
                             "between" => {
@@ -641,6 +949,9 @@ fn parse_arg_pred_pair(
                                     }
                                 }
                             }
+                            "startsWith" | "endsWith" | "contains" | "like" => {
+                                return parse_pattern(key, predicate, other, field_type)
+                            }
                             _ => {
                                 return Err(GraphqlError::UnsupportedFilterOperation(
                                     key.to_string(),
@@ -659,6 +970,40 @@ fn parse_arg_pred_pair(
     }
 }
 
+/// Parse a string pattern operator on a given field into a `Pattern` filter.
+///
+/// Pattern operators are only supported on `Charfield` fields.
+fn parse_pattern(
+    key: &str,
+    predicate: &Value,
+    field: &str,
+    field_type: &str,
+) -> Result<FilterType, GraphqlError> {
+    if field_type.replace('!', "") != "Charfield" {
+        return Err(GraphqlError::UnsupportedPatternFilter(
+            key.to_string(),
+            field.to_string(),
+        ));
+    }
+
+    let val = if let Value::String(s) = predicate {
+        s.clone()
+    } else {
+        return Err(GraphqlError::UnsupportedValueType(predicate.to_string()));
+    };
+
+    let field = field.to_string();
+    let pattern = match key {
+        "startsWith" => Pattern::StartsWith(field, val),
+        "endsWith" => Pattern::EndsWith(field, val),
+        "contains" => Pattern::Contains(field, val),
+        "like" => Pattern::Like(field, val),
+        _ => return Err(GraphqlError::UnsupportedFilterOperation(key.to_string())),
+    };
+
+    Ok(FilterType::Pattern(pattern))
+}
+
 /// Parse logical operators that operate on two components.
 ///
 /// `parse_binary_logical_operator` is a special parsing operation that
@@ -760,3 +1105,122 @@ fn parse_value(value: &Value) -> Result<ParsedValue, GraphqlError> {
         _ => Err(GraphqlError::UnsupportedValueType(value.to_string())),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn schema() -> IndexerSchema {
+        let schema = r#"
+type Book @entity {
+    id: ID!
+    title: Charfield! @search
+    summary: Charfield @search
+    author: Charfield!
+    pages: UInt8!
+}
+"#;
+
+        IndexerSchema::new(
+            "test",
+            "test",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap()
+    }
+
+    fn parse_filter(filter: &str) -> Result<ParamType, GraphqlError> {
+        let value = async_graphql_value::ConstValue::from_json(
+            serde_json::from_str(filter).unwrap(),
+        )
+        .unwrap()
+        .into_value();
+        parse_argument_into_param(Some(&"Book".to_string()), "filter", value, &schema())
+    }
+
+    #[test]
+    fn test_pattern_filters_escape_wildcards_and_quotes() {
+        let param = parse_filter(r#"{"author": {"startsWith": "O'Brien_1%"}}"#).unwrap();
+        let ParamType::Filter(filter) = param else {
+            panic!("Expected a filter.");
+        };
+
+        assert_eq!(
+            filter,
+            FilterType::Pattern(Pattern::StartsWith(
+                "author".to_string(),
+                "O'Brien_1%".to_string()
+            ))
+        );
+        assert_eq!(
+            filter.to_sql("test_test.book".to_string(), &DbType::Postgres),
+            r"test_test.book.author LIKE 'O''Brien\_1\%%'"
+        );
+
+        let ParamType::Filter(filter) =
+            parse_filter(r#"{"author": {"like": "%tolkien%"}}"#).unwrap()
+        else {
+            panic!("Expected a filter.");
+        };
+        assert_eq!(
+            filter.to_sql("test_test.book".to_string(), &DbType::Postgres),
+            "test_test.book.author ILIKE '%tolkien%'"
+        );
+    }
+
+    #[test]
+    fn test_negated_pattern_filter() {
+        let ParamType::Filter(filter) =
+            parse_filter(r#"{"not": {"title": {"contains": "ring"}}}"#).unwrap()
+        else {
+            panic!("Expected a filter.");
+        };
+
+        assert_eq!(
+            filter.to_sql("test_test.book".to_string(), &DbType::Postgres),
+            "NOT (test_test.book.title LIKE '%ring%')"
+        );
+    }
+
+    #[test]
+    fn test_pattern_filters_are_only_supported_on_charfields() {
+        assert!(matches!(
+            parse_filter(r#"{"pages": {"endsWith": "0"}}"#),
+            Err(GraphqlError::UnsupportedPatternFilter(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_pattern_operator_is_an_error() {
+        assert!(matches!(
+            parse_pattern("matches", &Value::String("ring".to_string()), "title", "Charfield!"),
+            Err(GraphqlError::UnsupportedFilterOperation(op)) if op == "matches"
+        ));
+    }
+
+    #[test]
+    fn test_search_argument_matches_all_search_fields() {
+        let value = async_graphql_value::ConstValue::from_json(
+            serde_json::json!({"text": "lord of the rings"}),
+        )
+        .unwrap()
+        .into_value();
+        let ParamType::Filter(filter) = parse_argument_into_param(
+            Some(&"Book".to_string()),
+            "search",
+            value,
+            &schema(),
+        )
+        .unwrap() else {
+            panic!("Expected a filter.");
+        };
+
+        assert_eq!(
+            filter.to_sql("test_test.book".to_string(), &DbType::Postgres),
+            "(to_tsvector('english', test_test.book.title) @@ plainto_tsquery('english', 'lord of the rings') OR to_tsvector('english', test_test.book.summary) @@ plainto_tsquery('english', 'lord of the rings'))"
+        );
+    }
+}
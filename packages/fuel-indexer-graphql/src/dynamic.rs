@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use async_graphql::{
     dynamic::{
@@ -15,7 +15,10 @@ use fuel_indexer_schema::db::tables::IndexerSchema;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
-use crate::graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult};
+use crate::{
+    arguments::FIELD_OPERATORS,
+    graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult},
+};
 
 lazy_static! {
     /// Scalar types supported by the Fuel indexer. These should always stay up-to-date
@@ -135,6 +138,71 @@ lazy_static! {
         HashSet::from(["object"]);
 }
 
+/// Name of the input object of the `search` argument.
+const SEARCH_OBJECT_NAME: &str = "SearchText";
+
+/// Name of the operation of `INTROSPECTION_QUERY`.
+const INTROSPECTION_OPERATION_NAME: &str = "IntrospectionQuery";
+
+/// The introspection query sent by GraphQL tooling, the result of which fully
+/// describes a schema.
+const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives { name description locations args { ...InputValue } }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType { kind name ofType { kind name ofType { kind name } } }
+        }
+      }
+    }
+  }
+}"#;
+
 /// Execute user query and return results.
 pub async fn execute_query(
     dynamic_request: Request,
@@ -174,6 +242,19 @@ pub async fn execute_query(
     }
 }
 
+/// Return the result of `INTROSPECTION_QUERY` against a dynamic schema.
+pub async fn introspect(dynamic_schema: &DynamicSchema) -> GraphqlResult<Value> {
+    let request =
+        Request::new(INTROSPECTION_QUERY).operation_name(INTROSPECTION_OPERATION_NAME);
+    let response = dynamic_schema.execute(request).await;
+
+    if let Some(e) = response.errors.first() {
+        return Err(GraphqlError::QueryError(e.message.clone()));
+    }
+
+    Ok(response.data.into_json()?)
+}
+
 /// Build a dynamic schema. This allows for introspection, which allows for extensive
 /// auto-documentation and code suggestions.
 pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSchema> {
@@ -200,6 +281,18 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
     let mut sort_object_list = Vec::new();
     let mut sorter_tracker = HashMap::new();
 
+    // Entities with at least one field marked with a `@search` directive
+    // accept a `search` argument.
+    let search_object = InputObject::new(SEARCH_OBJECT_NAME)
+        .field(InputValue::new("text", TypeRef::named_nn(TypeRef::STRING)));
+    let searchable_entities = schema
+        .parsed()
+        .object_field_mappings()
+        .keys()
+        .filter(|entity_type| !schema.parsed().search_fields(entity_type).is_empty())
+        .cloned()
+        .collect::<HashSet<String>>();
+
     // async-graphql requires a root query object so that the base entity
     // fields can be queried against. This QueryRoot does not appear anywhere
     // in the generated documentation nor is it required for the user to create.
@@ -222,7 +315,11 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
         let mut object_field_enum = Enum::new(format!("{entity_type}Fields"));
 
         for (field_name, field_type) in field_map.clone() {
-            if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str()) {
+            // Derived fields have no column of their own, and so cannot be filtered
+            // or sorted on; they are instead filtered and sorted by their own arguments.
+            if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str())
+                || schema.parsed().is_derived_field(entity_type, &field_name)
+            {
                 continue;
             }
 
@@ -232,6 +329,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
                     field_type,
                     entity_type.clone(),
                     &sort_enum,
+                    schema,
                 )?;
 
             filter_input_vals.push(field_filter_input_val);
@@ -270,16 +368,43 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             sorter_tracker.insert(entity_type.to_string(), sort_object_list.len() - 1);
         }
 
-        // Additionally, because we cannot refer to the object fields directly and
-        // associate the field arguments to them, we iterate through the fields a
-        // second time and construct the fields for the dynamic schema and add the
-        // field arguments as well.
+        schema_builder = schema_builder.register(object_field_enum);
+    }
+
+    // Additionally, because we cannot refer to the object fields directly and
+    // associate the field arguments to them, we iterate through the fields a
+    // second time and construct the fields for the dynamic schema and add the
+    // field arguments as well. This is done once the filter and sort objects for
+    // all entities exist, so that fields referring to other entities receive
+    // the arguments of those entities.
+    for (entity_type, field_map) in schema.parsed().object_field_mappings() {
+        if IGNORED_ENTITY_TYPES.contains(&entity_type.as_str()) {
+            continue;
+        }
+
         let mut fields = Vec::new();
         for (field_name, field_type) in field_map {
             if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str()) {
                 continue;
             }
 
+            // Derived fields resolve to a list of the entities that reference this entity.
+            if schema.parsed().is_derived_field(entity_type, field_name) {
+                let field = create_field_with_assoc_args(
+                    field_name.to_string(),
+                    TypeRef::named_nn_list_nn(field_type),
+                    &BaseType::Named(Name::new(field_type)),
+                    &filter_tracker,
+                    &filter_object_list,
+                    &sorter_tracker,
+                    &sort_object_list,
+                    &searchable_entities,
+                );
+
+                fields.push(field);
+                continue;
+            }
+
             if let Some(field_def) = Type::new(field_type) {
                 let base_field_type = &field_def.base;
                 let nullable = field_def.nullable;
@@ -330,6 +455,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
                     &filter_object_list,
                     &sorter_tracker,
                     &sort_object_list,
+                    &searchable_entities,
                 );
 
                 fields.push(field);
@@ -352,12 +478,13 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             &filter_object_list,
             &sorter_tracker,
             &sort_object_list,
+            &searchable_entities,
         );
         if !SCALAR_TYPES.contains(&obj.type_name()) {
             query_root = query_root.field(field);
         }
 
-        schema_builder = schema_builder.register(obj).register(object_field_enum);
+        schema_builder = schema_builder.register(obj);
     }
 
     // In order for the schema to successfully use the input objects
@@ -376,6 +503,9 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
     }
 
     schema_builder = schema_builder.register(sort_enum);
+    if !searchable_entities.is_empty() {
+        schema_builder = schema_builder.register(search_object);
+    }
     schema_builder = schema_builder.register(query_root);
 
     Ok(schema_builder.finish()?)
@@ -387,6 +517,7 @@ fn create_input_values_and_objects_for_field(
     field_type: String,
     entity_type: String,
     sort_enum: &Enum,
+    schema: &IndexerSchema,
 ) -> GraphqlResult<(InputValue, Vec<InputObject>, Option<InputValue>)> {
     let field_type =
         Type::new(&field_type).ok_or(GraphqlError::DynamicSchemaBuildError(
@@ -400,8 +531,28 @@ fn create_input_values_and_objects_for_field(
                     &field_name,
                     field_type.as_str(),
                     entity_type.as_str(),
+                    schema,
                 );
 
+            // Foreign key fields can be sorted by the fields of the related entity.
+            let has_sortable_related_fields = related_entity_fields(schema, &field_type)
+                .is_some_and(|fields| {
+                    fields.values().any(|t| {
+                        SORTABLE_SCALAR_TYPES.contains(t.replace('!', "").as_str())
+                    })
+                });
+            if has_sortable_related_fields {
+                let sort_input_val = InputValue::new(
+                    field_name,
+                    TypeRef::named(format!("{field_type}Sort")),
+                );
+                return Ok((
+                    field_filter_input_val,
+                    field_input_objects,
+                    Some(sort_input_val),
+                ));
+            }
+
             if SORTABLE_SCALAR_TYPES.contains(field_type.as_str()) {
                 let sort_input_val =
                     InputValue::new(field_name, TypeRef::named(sort_enum.type_name()));
@@ -419,6 +570,7 @@ fn create_input_values_and_objects_for_field(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create_field_with_assoc_args(
     field_name: String,
     field_type_ref: TypeRef,
@@ -427,6 +579,7 @@ fn create_field_with_assoc_args(
     filter_object_list: &[InputObject],
     sorter_tracker: &HashMap<String, usize>,
     sort_object_list: &[InputObject],
+    searchable_entities: &HashSet<String>,
 ) -> Field {
     // Because the dynamic schema is set to only resolve introspection
     // queries, we set the resolvers to return a dummy value.
@@ -454,6 +607,12 @@ fn create_field_with_assoc_args(
                     field = field.argument(object_sort_arg);
                 }
 
+                if searchable_entities.contains(field_type.as_str()) {
+                    let search_arg =
+                        InputValue::new("search", TypeRef::named(SEARCH_OBJECT_NAME));
+                    field = field.argument(search_arg);
+                }
+
                 let offset_arg = InputValue::new("offset", TypeRef::named(TypeRef::INT));
 
                 let limit_arg = InputValue::new("first", TypeRef::named(TypeRef::INT));
@@ -480,6 +639,7 @@ fn create_filter_val_and_objects_for_field<'a>(
     field_name: &'a str,
     field_type: &'a str,
     obj_name: &'a str,
+    schema: &IndexerSchema,
 ) -> (InputValue, Vec<InputObject>) {
     let mut input_objs: Vec<InputObject> = Vec::new();
 
@@ -496,7 +656,7 @@ fn create_filter_val_and_objects_for_field<'a>(
             .field(InputValue::new("min", TypeRef::named_nn(filter_arg_type)))
             .field(InputValue::new("max", TypeRef::named_nn(filter_arg_type)));
 
-    let complete_comparison_obj =
+    let mut complete_comparison_obj =
         InputObject::new(format!("{obj_name}{field_name}FilterObject"))
             .field(InputValue::new(
                 "between",
@@ -512,6 +672,30 @@ fn create_filter_val_and_objects_for_field<'a>(
                 TypeRef::named_nn_list(filter_arg_type),
             ));
 
+    // Foreign key fields can be filtered by the fields of the related entity.
+    if let Some(related_fields) = related_entity_fields(schema, field_type) {
+        for related_field in related_fields.keys() {
+            if IGNORED_ENTITY_FIELD_TYPES.contains(&related_field.as_str())
+                || FIELD_OPERATORS.contains(&related_field.as_str())
+                || schema.parsed().is_derived_field(field_type, related_field)
+            {
+                continue;
+            }
+            complete_comparison_obj = complete_comparison_obj.field(InputValue::new(
+                related_field,
+                TypeRef::named(format!("{field_type}{related_field}FilterObject")),
+            ));
+        }
+    }
+
+    if field_type == "Charfield" {
+        complete_comparison_obj = ["startsWith", "endsWith", "contains", "like"]
+            .into_iter()
+            .fold(complete_comparison_obj, |obj, op| {
+                obj.field(InputValue::new(op, TypeRef::named(TypeRef::STRING)))
+            });
+    }
+
     let input_val_for_field = InputValue::new(
         field_name,
         TypeRef::named(complete_comparison_obj.type_name()),
@@ -520,3 +704,14 @@ fn create_filter_val_and_objects_for_field<'a>(
     input_objs.append(&mut vec![complex_comparison_obj, complete_comparison_obj]);
     (input_val_for_field, input_objs)
 }
+
+/// Return the fields of the entity referenced by a foreign key field type, if any.
+fn related_entity_fields<'a>(
+    schema: &'a IndexerSchema,
+    field_type: &str,
+) -> Option<&'a BTreeMap<String, String>> {
+    if !schema.parsed().is_possible_foreign_key(field_type) {
+        return None;
+    }
+    schema.parsed().object_field_mappings().get(field_type)
+}
@@ -1,6 +1,6 @@
 use super::{
     arguments::{parse_argument_into_param, ParamType, QueryParams},
-    queries::{JoinCondition, QueryElement, QueryJoinNode, UserQuery},
+    queries::{insert_join, JoinCondition, QueryElement, QueryJoinNode, UserQuery},
 };
 use async_graphql_parser::{
     parse_query,
@@ -46,6 +46,8 @@ pub enum GraphqlError {
     NoPredicatesInFilter,
     #[error("Unsupported filter operation type: {0:?}")]
     UnsupportedFilterOperation(String),
+    #[error("Filter operation {0:?} is only supported on Charfield fields, not {1:?}")]
+    UnsupportedPatternFilter(String, String),
     #[error("Unable to parse value into string, bool, or i64: {0:?}")]
     UnableToParseValue(String),
     #[error("No available predicates to associate with logical operator")]
@@ -385,6 +387,50 @@ impl Operation {
                                     format!("{namespace}_{identifier}.{entity_name}"),
                                 );
                             }
+                        } else if let Some((
+                            ref_entity_name,
+                            ref_field_name,
+                            primary_key_col,
+                        )) =
+                            derived_field_reference(schema, &entity_name, &field_name)
+                        {
+                            // Derived fields are resolved from the table of the referencing
+                            // entity, using the foreign key from which the field is derived.
+                            let join = JoinCondition {
+                                referencing_key_table: format!(
+                                    "{namespace}_{identifier}.{ref_entity_name}"
+                                ),
+                                referencing_key_col: ref_field_name,
+                                primary_key_table: format!(
+                                    "{namespace}_{identifier}.{entity_name}"
+                                ),
+                                primary_key_col,
+                            };
+
+                            let derived_selection = Selection::Field {
+                                name: ref_entity_name,
+                                params: filters,
+                                sub_selections: subselections,
+                                alias: None,
+                            };
+                            let query = Operation::new(
+                                namespace.clone(),
+                                identifier.clone(),
+                                Selections {
+                                    has_fragments: false,
+                                    selections: vec![derived_selection],
+                                },
+                            )
+                            .parse(schema)
+                            .pop();
+
+                            if let Some(query) = query {
+                                elements.push(QueryElement::DerivedField {
+                                    key: alias.unwrap_or(field_name.clone()),
+                                    join,
+                                    query: Box::new(query),
+                                });
+                            }
                         } else {
                             let mut new_entity = field_name.clone();
                             // If the current entity has a foreign key on the current
@@ -409,65 +455,12 @@ impl Operation {
                                         primary_key_col: foreign_key_col.clone(),
                                     };
 
-                                    // Joins are modelled like a directed graph in
-                                    // order to ensure that tables can be joined in
-                                    // a dependent order, if necessary.
-                                    match joins
-                                        .get_mut(&join_condition.referencing_key_table)
-                                    {
-                                        Some(join_node) => {
-                                            join_node.dependencies.insert(
-                                                join_condition.primary_key_table.clone(),
-                                                join_condition.clone(),
-                                            );
-                                        }
-                                        None => {
-                                            joins.insert(
-                                                join_condition
-                                                    .referencing_key_table
-                                                    .clone(),
-                                                QueryJoinNode {
-                                                    dependencies: HashMap::from([(
-                                                        join_condition
-                                                            .primary_key_table
-                                                            .clone(),
-                                                        join_condition.clone(),
-                                                    )]),
-                                                    dependents: HashMap::new(),
-                                                },
-                                            );
-                                        }
-                                    };
+                                    insert_join(&mut joins, join_condition);
 
                                     if *foreign_key_table != field_name {
                                         new_entity = foreign_key_table.to_string();
                                     }
 
-                                    match joins.get_mut(&join_condition.primary_key_table)
-                                    {
-                                        Some(join_node) => {
-                                            join_node.dependents.insert(
-                                                join_condition
-                                                    .referencing_key_table
-                                                    .clone(),
-                                                join_condition.clone(),
-                                            );
-                                        }
-                                        None => {
-                                            joins.insert(
-                                                join_condition.primary_key_table.clone(),
-                                                QueryJoinNode {
-                                                    dependencies: HashMap::new(),
-                                                    dependents: HashMap::from([(
-                                                        join_condition
-                                                            .referencing_key_table
-                                                            .clone(),
-                                                        join_condition.clone(),
-                                                    )]),
-                                                },
-                                            );
-                                        }
-                                    };
                                     if !filters.is_empty() {
                                         query_params.add_params(
                                     filters,
@@ -522,6 +515,28 @@ impl Operation {
     }
 }
 
+/// Return the name of the referencing entity, the foreign key field on the referencing
+/// entity, and the referenced column for a given derived field, if the field is derived.
+fn derived_field_reference(
+    schema: &IndexerSchema,
+    entity_name: &str,
+    field_name: &str,
+) -> Option<(String, String, String)> {
+    let (ref_entity_type, ref_field_name) = schema
+        .parsed()
+        .derived_from_mappings()
+        .get(&entity_name.to_lowercase())?
+        .get(field_name)?;
+    let ref_entity_name = ref_entity_type.to_lowercase();
+    let (_, ref_col) = schema
+        .parsed()
+        .foreign_key_mappings()
+        .get(&ref_entity_name)?
+        .get(ref_field_name)?;
+
+    Some((ref_entity_name, ref_field_name.clone(), ref_col.clone()))
+}
+
 #[derive(Debug)]
 pub struct GraphqlQuery {
     operations: Vec<Operation>,
@@ -843,4 +858,207 @@ type Tx @entity {
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
+
+    fn related_entities_schema() -> IndexerSchema {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: Charfield!
+}
+
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+    issuer: Account!
+    payments: [Payment!]! @derivedFrom(field: "token")
+}
+
+type Payment @entity {
+    id: ID!
+    amount: UInt8!
+    token: Token!
+}
+"#;
+
+        IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_filters_and_sorts_on_related_entities_are_compiled_into_joins() {
+        let schema = related_entities_schema();
+        let query = r#"
+query {
+    payment(
+        filter: { token: { issuer: { name: { equals: "fuel" } } } },
+        order: { token: { symbol: asc } }
+    ) {
+        id
+        amount
+    }
+}
+"#;
+
+        let sql = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        assert_eq!(
+            sql[0],
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.payment.id, 'amount', fuel_indexer_test_test_index.payment.amount) FROM fuel_indexer_test_test_index.payment LEFT JOIN fuel_indexer_test_test_index.token AS payment__token ON fuel_indexer_test_test_index.payment.token = payment__token.id LEFT JOIN fuel_indexer_test_test_index.account AS payment__token__issuer ON payment__token.issuer = payment__token__issuer.id WHERE  payment__token__issuer.name = 'fuel'  ORDER BY payment__token.symbol ASC"
+        );
+    }
+
+    #[test]
+    fn test_nullable_foreign_keys_are_left_joined() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: Charfield!
+}
+
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+    issuer: Account
+}
+"#;
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+        let query = r#"
+query {
+    token(order: { issuer: { name: asc } }) {
+        id
+        symbol
+    }
+}
+"#;
+
+        let sql = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        // Tokens without an issuer must still be returned.
+        assert!(sql[0].contains(
+            "FROM fuel_indexer_test_test_index.token LEFT JOIN fuel_indexer_test_test_index.account AS token__issuer ON fuel_indexer_test_test_index.token.issuer = token__issuer.id"
+        ));
+        assert!(!sql[0].contains("INNER JOIN"));
+    }
+
+    #[test]
+    fn test_tables_joined_more_than_once_are_aliased_by_field_path() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: Charfield!
+    referrer: Account
+}
+
+type Payment @entity {
+    id: ID!
+    amount: UInt8!
+    sender: Account!
+    recipient: Account!
+}
+"#;
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+        let query = r#"
+query {
+    payment(
+        filter: { recipient: { referrer: { name: { equals: "bob" } } } },
+        order: { sender: { name: asc } }
+    ) {
+        id
+    }
+}
+"#;
+
+        let sql = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        assert_eq!(
+            sql[0],
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.payment.id) FROM fuel_indexer_test_test_index.payment LEFT JOIN fuel_indexer_test_test_index.account AS payment__recipient ON fuel_indexer_test_test_index.payment.recipient = payment__recipient.id LEFT JOIN fuel_indexer_test_test_index.account AS payment__recipient__referrer ON payment__recipient.referrer = payment__recipient__referrer.id LEFT JOIN fuel_indexer_test_test_index.account AS payment__sender ON fuel_indexer_test_test_index.payment.sender = payment__sender.id WHERE  payment__recipient__referrer.name = 'bob'  ORDER BY payment__sender.name ASC"
+        );
+    }
+
+    #[test]
+    fn test_dynamic_schema_exposes_related_entity_fields() {
+        let schema = crate::dynamic::build_dynamic_schema(&related_entities_schema())
+            .unwrap()
+            .sdl();
+
+        assert!(schema.contains("input PaymenttokenFilterObject"));
+        assert!(schema.contains("issuer: TokenissuerFilterObject"));
+        assert!(schema.contains("input TokenissuerFilterObject"));
+        assert!(schema.contains("name: AccountnameFilterObject"));
+        assert!(schema.contains("symbol: TokensymbolFilterObject"));
+        assert!(schema.contains("token: TokenSort"));
+    }
+
+    #[test]
+    fn test_derived_fields_are_compiled_into_correlated_subqueries() {
+        let schema = related_entities_schema();
+        let query = r#"
+query {
+    token {
+        symbol
+        payments(filter: { amount: { gt: 10 } }, order: { amount: desc }, first: 5) {
+            amount
+        }
+    }
+}
+"#;
+
+        let sql = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        assert_eq!(
+            sql[0],
+            "SELECT json_build_object('symbol', fuel_indexer_test_test_index.token.symbol, 'payments', (SELECT coalesce(json_agg(item), '[]'::json) FROM (SELECT json_build_object('amount', fuel_indexer_test_test_index.payment.amount) AS item FROM fuel_indexer_test_test_index.payment  WHERE fuel_indexer_test_test_index.payment.token = fuel_indexer_test_test_index.token.id AND fuel_indexer_test_test_index.payment.amount > 10 ORDER BY fuel_indexer_test_test_index.payment.amount DESC LIMIT 5) AS payments_items)) FROM fuel_indexer_test_test_index.token   "
+        );
+    }
+
+    #[test]
+    fn test_dynamic_schema_exposes_derived_fields() {
+        let schema = crate::dynamic::build_dynamic_schema(&related_entities_schema())
+            .unwrap()
+            .sdl();
+
+        assert!(schema.contains("payments(filter: PaymentFilter, order: PaymentSort, offset: Int, first: Int, id: String): [Payment!]!"));
+        assert!(!schema.contains("input TokenpaymentsFilterObject"));
+    }
 }
@@ -6,12 +6,24 @@ use std::{collections::HashMap, fmt::Display};
 /// Represents a part of a user query. Each part can be a key-value pair
 /// describing an entity field and its corresponding database table, or a
 /// boundary for a nested object; opening boundaries contain a string to
-/// be used as a JSON key in the final database query.
+/// be used as a JSON key in the final database query. Derived fields contain
+/// a query for the referencing entities, along with the join condition used to
+/// correlate those entities with the containing entity.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum QueryElement {
-    Field { key: String, value: String },
-    ObjectOpeningBoundary { key: String },
+    Field {
+        key: String,
+        value: String,
+    },
+    ObjectOpeningBoundary {
+        key: String,
+    },
     ObjectClosingBoundary,
+    DerivedField {
+        key: String,
+        join: JoinCondition,
+        query: Box<UserQuery>,
+    },
 }
 
 /// Represents the tables and columns used in a particular database join.
@@ -44,6 +56,36 @@ pub struct QueryJoinNode {
     pub dependents: HashMap<String, JoinCondition>,
 }
 
+/// Add a join to a graph of table joins.
+///
+/// Joins are modelled like a directed graph in order to ensure that tables
+/// can be joined in a dependent order, if necessary.
+pub(crate) fn insert_join(
+    joins: &mut HashMap<String, QueryJoinNode>,
+    join_condition: JoinCondition,
+) {
+    joins
+        .entry(join_condition.referencing_key_table.clone())
+        .or_insert_with(|| QueryJoinNode {
+            dependencies: HashMap::new(),
+            dependents: HashMap::new(),
+        })
+        .dependencies
+        .insert(
+            join_condition.primary_key_table.clone(),
+            join_condition.clone(),
+        );
+
+    joins
+        .entry(join_condition.primary_key_table.clone())
+        .or_insert_with(|| QueryJoinNode {
+            dependencies: HashMap::new(),
+            dependents: HashMap::new(),
+        })
+        .dependents
+        .insert(join_condition.referencing_key_table.clone(), join_condition);
+}
+
 /// Represents the full amount of requested information from a user query.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UserQuery {
@@ -77,36 +119,7 @@ impl UserQuery {
 
                 let selections_str = selections.join("");
 
-                let sorted_joins = self.get_topologically_sorted_joins();
-
-                let mut last_seen_primary_key_table = "".to_string();
-                let mut joins: Vec<String> = Vec::new();
-
-                // For each clause in the list of topologically-sorted joins,
-                // check if the clause's primary key table matches the last primary key
-                // key table that was seen in this loop. If so, add the join condition to
-                // the last join condition; if not, push this clause into the list of joins.
-                // This is required because Postgres does not allow for joined primary key tables
-                // to be mentioned multiple times.
-                for sj in sorted_joins {
-                    if sj.primary_key_table == last_seen_primary_key_table {
-                        if let Some(elem) = joins.last_mut() {
-                            *elem = format!("{elem} AND {sj}")
-                        }
-                    } else {
-                        joins.push(format!(
-                            "INNER JOIN {} ON {}",
-                            sj.primary_key_table, sj
-                        ));
-                        last_seen_primary_key_table = sj.primary_key_table;
-                    }
-                }
-
-                let joins_str = if !joins.is_empty() {
-                    joins.join(" ")
-                } else {
-                    "".to_string()
-                };
+                let joins_str = self.get_joins_str();
 
                 // If there's a limit applied to the query, then we need to create a query
                 // with pagination info. Otherwise, we can return the entire result set.
@@ -139,6 +152,86 @@ impl UserQuery {
         }
     }
 
+    /// Returns the table joins required by the query as a single string.
+    fn get_joins_str(&mut self) -> String {
+        let sorted_joins = self.get_topologically_sorted_joins();
+
+        let mut last_seen_primary_key_table = "".to_string();
+        let mut joins: Vec<String> = Vec::new();
+
+        // For each clause in the list of topologically-sorted joins,
+        // check if the clause's primary key table matches the last primary key
+        // key table that was seen in this loop. If so, add the join condition to
+        // the last join condition; if not, push this clause into the list of joins.
+        // This is required because Postgres does not allow for joined primary key tables
+        // to be mentioned multiple times.
+        for sj in sorted_joins {
+            if sj.primary_key_table == last_seen_primary_key_table {
+                if let Some(elem) = joins.last_mut() {
+                    *elem = format!("{elem} AND {sj}")
+                }
+            } else {
+                joins.push(format!("INNER JOIN {} ON {}", sj.primary_key_table, sj));
+                last_seen_primary_key_table = sj.primary_key_table;
+            }
+        }
+
+        // Filters and sorts on the fields of related entities require
+        // their tables to be joined as well.
+        joins.extend(self.query_params.get_joins());
+
+        joins.join(" ")
+    }
+
+    /// Returns a correlated subquery that aggregates the referencing entities
+    /// of a derived field into a JSON array.
+    fn create_derived_field_subquery(
+        db_type: &DbType,
+        key: &str,
+        join: &JoinCondition,
+        query: &UserQuery,
+    ) -> String {
+        match db_type {
+            DbType::Postgres => {
+                let mut query = query.clone();
+                let selections_str =
+                    query.parse_query_elements_into_selections(db_type).join("");
+                let joins_str = query.get_joins_str();
+
+                // The join condition correlates the referencing entities
+                // with the entity that is currently being selected.
+                let where_expressions = std::iter::once(join.to_string())
+                    .chain(query.query_params.filters.iter().map(|f| f.to_sql(db_type)))
+                    .collect::<Vec<String>>()
+                    .join(" AND ");
+
+                let limit_str = query
+                    .query_params
+                    .limit
+                    .map(|limit| format!(" LIMIT {limit}"))
+                    .unwrap_or_default();
+                let offset_str = query
+                    .query_params
+                    .offset
+                    .map(|offset| format!(" OFFSET {offset}"))
+                    .unwrap_or_default();
+
+                format!(
+                    "(SELECT coalesce(json_agg(item), '[]'::json) FROM (SELECT json_build_object({}) AS item FROM {}.{} {} WHERE {}{}{}{}) AS {}_items)",
+                    selections_str,
+                    query.namespace_identifier,
+                    query.entity_name,
+                    joins_str,
+                    where_expressions,
+                    query.query_params.get_ordering_modififer(db_type),
+                    limit_str,
+                    offset_str,
+                    key,
+                )
+            }
+        }
+    }
+
     /// Returns a SQL query that contains the requested results and a PageInfo object.
     fn create_query_with_pageinfo(
         &self,
@@ -229,7 +322,8 @@ impl UserQuery {
                             if let Some(next_element) = peekable_elements.peek() {
                                 match next_element {
                                     QueryElement::Field { .. }
-                                    | QueryElement::ObjectOpeningBoundary { .. } => {
+                                    | QueryElement::ObjectOpeningBoundary { .. }
+                                    | QueryElement::DerivedField { .. } => {
                                         selections.push(", ".to_string());
                                     }
                                     _ => {}
@@ -243,13 +337,36 @@ impl UserQuery {
                             selections.push(format!("'{key}', json_build_object("))
                         }
 
+                        // Derived fields are selected by way of a subquery on the table
+                        // of the referencing entity.
+                        QueryElement::DerivedField { key, join, query } => {
+                            selections.push(format!(
+                                "'{key}', {}",
+                                Self::create_derived_field_subquery(
+                                    db_type, key, join, query
+                                )
+                            ));
+
+                            if let Some(next_element) = peekable_elements.peek() {
+                                match next_element {
+                                    QueryElement::Field { .. }
+                                    | QueryElement::ObjectOpeningBoundary { .. }
+                                    | QueryElement::DerivedField { .. } => {
+                                        selections.push(", ".to_string());
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+
                         QueryElement::ObjectClosingBoundary => {
                             selections.push(")".to_string());
 
                             if let Some(next_element) = peekable_elements.peek() {
                                 match next_element {
                                     QueryElement::Field { .. }
-                                    | QueryElement::ObjectOpeningBoundary { .. } => {
+                                    | QueryElement::ObjectOpeningBoundary { .. }
+                                    | QueryElement::DerivedField { .. } => {
                                         selections.push(", ".to_string());
                                     }
                                     _ => {}
@@ -276,7 +393,8 @@ impl UserQuery {
 
                 while let Some(element) = peekable_elements.next() {
                     match element {
-                        QueryElement::Field { key, .. } => {
+                        QueryElement::Field { key, .. }
+                        | QueryElement::DerivedField { key, .. } => {
                             selections.push(format!(
                                 "{}->'{}' AS {}",
                                 self.entity_name, key, key
@@ -15,7 +15,7 @@ const JWT_SECRET_KEY: &str = "JWT_SECRET";
 const JWT_ISSUER_KEY: &str = "JWT_ISSUER";
 
 /// Indexer service authentication configuration.
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 pub struct AuthenticationConfig {
     /// Require users to authenticate for some operations.
     pub enabled: bool,
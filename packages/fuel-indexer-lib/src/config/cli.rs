@@ -51,6 +51,14 @@ pub struct IndexerArgs {
     )]
     pub fuel_node_port: String,
 
+    /// Additional Fuel nodes to fail over to if the primary Fuel node is unavailable.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        help = "Comma-separated list of additional Fuel nodes (HOST:PORT) to fail over to."
+    )]
+    pub fuel_node_fallbacks: Vec<FuelClientConfig>,
+
     /// Web API host.
     #[clap(long, help = "Web API host.", default_value = defaults::WEB_API_HOST)]
     pub web_api_host: String,
@@ -67,6 +75,10 @@ pub struct IndexerArgs {
     #[clap(long, help = "Max body size for web server requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max body size for snapshot import requests.
+    #[clap(long, help = "Max body size for snapshot import requests.", default_value_t = defaults::MAX_SNAPSHOT_SIZE )]
+    pub max_snapshot_size: usize,
+
     /// Postgres username.
     #[clap(long, help = "Postgres username.")]
     pub postgres_user: Option<String>,
@@ -170,6 +182,22 @@ pub struct IndexerArgs {
     )]
     pub metering_points: u64,
 
+    /// The maximum number of 64KiB pages of linear memory that an indexer's WASM module can use.
+    #[clap(
+        long,
+        help = "The maximum number of 64KiB pages of linear memory that an indexer's WASM module can use.",
+        default_value_t = defaults::MAX_MEMORY_PAGES
+    )]
+    pub max_memory_pages: u32,
+
+    /// The number of seconds after which a single call to an indexer's event handler will be stopped.
+    #[clap(
+        long,
+        help = "The number of seconds after which a single call to an indexer's event handler will be stopped.",
+        default_value_t = defaults::HANDLER_TIMEOUT
+    )]
+    pub handler_timeout: u64,
+
     /// Whether to allow replacing an existing indexer. If not specified, an attempt to deploy over an existing indexer results in an error.
     #[clap(
         long,
@@ -191,6 +219,33 @@ pub struct IndexerArgs {
     /// Amount of blocks to return in a request to a Fuel node.
     #[clap(long, help = "Amount of blocks to return in a request to a Fuel node.", default_value_t = defaults::NODE_BLOCK_PAGE_SIZE)]
     pub block_page_size: usize,
+
+    /// The number of seconds for which the data of a retired blue/green deployment is kept.
+    #[clap(long, help = "The number of seconds for which the data of a retired blue/green deployment is kept.", default_value_t = defaults::DEPLOYMENT_GRACE_PERIOD)]
+    pub deployment_grace_period: u64,
+
+    /// The maximum number of entities cached by an indexer while it processes a batch of blocks.
+    #[clap(long, help = "The maximum number of entities cached by an indexer while it processes a batch of blocks. Set to 0 to disable caching.", default_value_t = defaults::ENTITY_CACHE_SIZE)]
+    pub entity_cache_size: usize,
+
+    /// Export tracing spans to an OTLP collector.
+    #[clap(long, help = "Export tracing spans to an OTLP collector.")]
+    pub telemetry: bool,
+
+    /// OTLP/HTTP endpoint of the collector to which spans are exported.
+    #[clap(
+        long,
+        help = "OTLP/HTTP endpoint of the collector to which spans are exported."
+    )]
+    pub otlp_endpoint: Option<String>,
+
+    /// Service name attached to all exported spans.
+    #[clap(long, help = "Service name attached to all exported spans.")]
+    pub telemetry_service_name: Option<String>,
+
+    /// Ratio of traces to sample, between 0.0 and 1.0.
+    #[clap(long, help = "Ratio of traces to sample, between 0.0 and 1.0.")]
+    pub telemetry_sample_ratio: Option<f64>,
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -224,6 +279,14 @@ pub struct ApiServerArgs {
     )]
     pub fuel_node_port: String,
 
+    /// Additional Fuel nodes to fail over to if the primary Fuel node is unavailable.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        help = "Comma-separated list of additional Fuel nodes (HOST:PORT) to fail over to."
+    )]
+    pub fuel_node_fallbacks: Vec<FuelClientConfig>,
+
     /// Web API host.
     #[clap(long, help = "Web API host.", default_value = defaults::WEB_API_HOST)]
     pub web_api_host: String,
@@ -240,6 +303,10 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Max body size for web requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max body size for snapshot import requests.
+    #[clap(long, help = "Max body size for snapshot import requests.", default_value_t = defaults::MAX_SNAPSHOT_SIZE )]
+    pub max_snapshot_size: usize,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -317,3 +384,50 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Allow the web server to accept raw SQL queries.")]
     pub accept_sql_queries: bool,
 }
+
+/// Output format of the entities written during a dry run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum DryRunOutputFormat {
+    /// A single JSON array.
+    #[default]
+    Json,
+
+    /// One JSON object per line.
+    Ndjson,
+}
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+    name = "Indexer Dry Run",
+    about = "Execute an indexer over a block range without committing its changes",
+    version
+)]
+pub struct DryRunArgs {
+    #[clap(flatten)]
+    pub indexer: IndexerArgs,
+
+    /// First block of the range to execute the indexer over.
+    #[clap(long, help = "First block of the range to execute the indexer over.")]
+    pub start_block: u32,
+
+    /// Last block of the range to execute the indexer over.
+    #[clap(long, help = "Last block of the range to execute the indexer over.")]
+    pub end_block: u32,
+
+    /// Output format of the entities that would have been written.
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = DryRunOutputFormat::Json,
+        help = "Output format of the entities that would have been written."
+    )]
+    pub output_format: DryRunOutputFormat,
+
+    /// File to write the output to. Defaults to stdout.
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "File to write the output to. Defaults to stdout."
+    )]
+    pub output: Option<PathBuf>,
+}
@@ -1,14 +1,14 @@
 use crate::{
-    config::{utils::derive_http_url, Env, IndexerConfigResult},
+    config::{utils::derive_http_url, Env, IndexerConfigError, IndexerConfigResult},
     defaults,
 };
 pub use clap::Parser;
 use http::Uri;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, str::FromStr};
 
 /// Fuel GraphQL API configuration.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq)]
 pub struct FuelClientConfig {
     /// Host of the running Fuel node.
     #[serde(default)]
@@ -66,3 +66,22 @@ impl std::string::ToString for FuelClientConfig {
         format!("{}:{}", self.host, self.port)
     }
 }
+
+impl FromStr for FuelClientConfig {
+    type Err = IndexerConfigError;
+
+    /// Parse a Fuel node config from a `HOST:PORT` string.
+    fn from_str(s: &str) -> IndexerConfigResult<Self> {
+        match s.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {
+                Ok(FuelClientConfig {
+                    host: host.to_string(),
+                    port: port.to_string(),
+                })
+            }
+            _ => Err(IndexerConfigError::InvalidConfigError(format!(
+                "Invalid Fuel node '{s}', expected HOST:PORT."
+            ))),
+        }
+    }
+}
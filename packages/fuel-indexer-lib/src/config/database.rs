@@ -14,7 +14,7 @@ use std::{
 use url::{ParseError, Url};
 
 /// Indexer database configuration.
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum DatabaseConfig {
     Postgres {
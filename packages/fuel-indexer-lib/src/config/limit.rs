@@ -6,7 +6,7 @@ pub use clap::Parser;
 use serde::{Deserialize, Serialize};
 
 /// Indexer service rate limit configuration.
-#[derive(Clone, Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct RateLimitConfig {
     #[serde(default)]
     /// Enable rate limiting.
@@ -3,16 +3,18 @@ pub mod cli;
 pub mod client;
 pub mod database;
 pub mod limit;
+pub mod telemetry;
 pub mod utils;
 pub mod web;
 
 pub use crate::{
     config::{
         auth::{AuthenticationConfig, AuthenticationStrategy},
-        cli::{ApiServerArgs, IndexerArgs},
+        cli::{ApiServerArgs, DryRunArgs, DryRunOutputFormat, IndexerArgs},
         client::FuelClientConfig,
         database::DatabaseConfig,
         limit::RateLimitConfig,
+        telemetry::TelemetryConfig,
         web::WebApiConfig,
     },
     defaults,
@@ -63,6 +65,8 @@ pub enum IndexerConfigError {
     ParseError(#[from] url::ParseError),
     #[error("File IO error: {0:?}")]
     FileIoError(#[from] std::io::Error),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfigError(String),
 }
 
 /// Used to inject environment variables into configuration.
@@ -74,15 +78,19 @@ impl Default for IndexerArgs {
     fn default() -> Self {
         Self {
             metering_points: defaults::METERING_POINTS,
+            max_memory_pages: defaults::MAX_MEMORY_PAGES,
+            handler_timeout: defaults::HANDLER_TIMEOUT,
             log_level: defaults::LOG_LEVEL.to_string(),
             config: None,
             manifest: None,
             fuel_node_host: defaults::FUEL_NODE_HOST.to_string(),
             fuel_node_port: defaults::FUEL_NODE_PORT.to_string(),
+            fuel_node_fallbacks: vec![],
             web_api_host: defaults::WEB_API_HOST.to_string(),
             web_api_port: defaults::WEB_API_PORT.to_string(),
             database: defaults::DATABASE.to_string(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_snapshot_size: defaults::MAX_SNAPSHOT_SIZE,
             postgres_user: Some(defaults::POSTGRES_USER.to_string()),
             postgres_database: Some(defaults::POSTGRES_DATABASE.to_string()),
             postgres_password: None,
@@ -107,14 +115,22 @@ impl Default for IndexerArgs {
             remove_data: defaults::REMOVE_DATA,
             accept_sql_queries: defaults::ACCEPT_SQL,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            deployment_grace_period: defaults::DEPLOYMENT_GRACE_PERIOD,
+            entity_cache_size: defaults::ENTITY_CACHE_SIZE,
+            telemetry: defaults::TELEMETRY_ENABLED,
+            otlp_endpoint: None,
+            telemetry_service_name: None,
+            telemetry_sample_ratio: None,
         }
     }
 }
 
 /// Fuel indexer service configuration.
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq)]
 pub struct IndexerConfig {
     pub metering_points: Option<u64>,
+    pub max_memory_pages: Option<u32>,
+    pub handler_timeout: Option<u64>,
     pub log_level: String,
     #[serde(default)]
     pub verbose: bool,
@@ -125,6 +141,8 @@ pub struct IndexerConfig {
     #[serde(default)]
     pub fuel_node: FuelClientConfig,
     #[serde(default)]
+    pub fuel_node_fallbacks: Vec<FuelClientConfig>,
+    #[serde(default)]
     pub web_api: WebApiConfig,
     #[serde(default)]
     pub database: DatabaseConfig,
@@ -136,17 +154,24 @@ pub struct IndexerConfig {
     pub replace_indexer: bool,
     pub accept_sql_queries: bool,
     pub block_page_size: usize,
+    pub deployment_grace_period: u64,
+    pub entity_cache_size: usize,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
 }
 
 impl Default for IndexerConfig {
     fn default() -> Self {
         Self {
             metering_points: Some(defaults::METERING_POINTS),
+            max_memory_pages: Some(defaults::MAX_MEMORY_PAGES),
+            handler_timeout: Some(defaults::HANDLER_TIMEOUT),
             log_level: defaults::LOG_LEVEL.to_string(),
             verbose: defaults::VERBOSE_LOGGING,
             local_fuel_node: defaults::LOCAL_FUEL_NODE,
             indexer_net_config: defaults::INDEXER_NET_CONFIG,
             fuel_node: FuelClientConfig::default(),
+            fuel_node_fallbacks: vec![],
             web_api: WebApiConfig::default(),
             database: DatabaseConfig::default(),
             metrics: defaults::USE_METRICS,
@@ -157,6 +182,9 @@ impl Default for IndexerConfig {
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: defaults::ACCEPT_SQL,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            deployment_grace_period: defaults::DEPLOYMENT_GRACE_PERIOD,
+            entity_cache_size: defaults::ENTITY_CACHE_SIZE,
+            telemetry: TelemetryConfig::default(),
         }
     }
 }
@@ -204,6 +232,8 @@ impl From<IndexerArgs> for IndexerConfig {
 
         let mut config = IndexerConfig {
             metering_points: Some(args.metering_points),
+            max_memory_pages: Some(args.max_memory_pages),
+            handler_timeout: Some(args.handler_timeout),
             log_level: args.log_level,
             verbose: args.verbose,
             local_fuel_node: args.local_fuel_node,
@@ -213,10 +243,12 @@ impl From<IndexerArgs> for IndexerConfig {
                 host: args.fuel_node_host,
                 port: args.fuel_node_port,
             },
+            fuel_node_fallbacks: args.fuel_node_fallbacks,
             web_api: WebApiConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                max_snapshot_size: args.max_snapshot_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
@@ -238,6 +270,20 @@ impl From<IndexerArgs> for IndexerConfig {
             replace_indexer: args.replace_indexer,
             accept_sql_queries: args.accept_sql_queries,
             block_page_size: args.block_page_size,
+            deployment_grace_period: args.deployment_grace_period,
+            entity_cache_size: args.entity_cache_size,
+            telemetry: TelemetryConfig {
+                enabled: args.telemetry,
+                otlp_endpoint: args
+                    .otlp_endpoint
+                    .unwrap_or(defaults::OTLP_ENDPOINT.to_string()),
+                service_name: args
+                    .telemetry_service_name
+                    .unwrap_or(defaults::TELEMETRY_SERVICE_NAME.to_string()),
+                sample_ratio: args
+                    .telemetry_sample_ratio
+                    .unwrap_or(defaults::TELEMETRY_SAMPLE_RATIO),
+            },
         };
 
         config
@@ -291,6 +337,8 @@ impl From<ApiServerArgs> for IndexerConfig {
 
         let mut config = IndexerConfig {
             metering_points: Some(defaults::METERING_POINTS),
+            max_memory_pages: Some(defaults::MAX_MEMORY_PAGES),
+            handler_timeout: Some(defaults::HANDLER_TIMEOUT),
             log_level: args.log_level,
             verbose: args.verbose,
             local_fuel_node: defaults::LOCAL_FUEL_NODE,
@@ -300,10 +348,12 @@ impl From<ApiServerArgs> for IndexerConfig {
                 host: args.fuel_node_host,
                 port: args.fuel_node_port,
             },
+            fuel_node_fallbacks: args.fuel_node_fallbacks,
             web_api: WebApiConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                max_snapshot_size: args.max_snapshot_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
@@ -325,6 +375,9 @@ impl From<ApiServerArgs> for IndexerConfig {
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: args.accept_sql_queries,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            deployment_grace_period: defaults::DEPLOYMENT_GRACE_PERIOD,
+            entity_cache_size: defaults::ENTITY_CACHE_SIZE,
+            telemetry: TelemetryConfig::default(),
         };
 
         config
@@ -349,6 +402,8 @@ impl IndexerConfig {
         let log_level_key = serde_yaml::Value::String("log_level".into());
         let replace_indexer_key = serde_yaml::Value::String("replace_indexer".into());
         let metering_points_key = serde_yaml::Value::String("metering_points".into());
+        let max_memory_pages_key = serde_yaml::Value::String("max_memory_pages".into());
+        let handler_timeout_key = serde_yaml::Value::String("handler_timeout".into());
         let metrics_key = serde_yaml::Value::String("metrics".into());
         let stop_idle_indexers_key =
             serde_yaml::Value::String("stop_idle_indexers".into());
@@ -363,6 +418,11 @@ impl IndexerConfig {
 
         let block_page_size_key = serde_yaml::Value::String("block_page_size".into());
 
+        let deployment_grace_period_key =
+            serde_yaml::Value::String("deployment_grace_period".into());
+
+        let entity_cache_size_key = serde_yaml::Value::String("entity_cache_size".into());
+
         if let Some(accept_sql_queries) = content.get(accept_sql_config_key) {
             config.accept_sql_queries = accept_sql_queries.as_bool().unwrap();
         }
@@ -375,6 +435,14 @@ impl IndexerConfig {
             config.metering_points = Some(metering_points.as_u64().unwrap());
         }
 
+        if let Some(max_memory_pages) = content.get(max_memory_pages_key) {
+            config.max_memory_pages = Some(max_memory_pages.as_u64().unwrap() as u32);
+        }
+
+        if let Some(handler_timeout) = content.get(handler_timeout_key) {
+            config.handler_timeout = Some(handler_timeout.as_u64().unwrap());
+        }
+
         if let Some(log_level) = content.get(log_level_key) {
             config.log_level = log_level.as_str().unwrap().to_string();
         }
@@ -407,22 +475,48 @@ impl IndexerConfig {
             config.block_page_size = block_page_size.as_u64().unwrap() as usize;
         }
 
+        if let Some(deployment_grace_period) = content.get(deployment_grace_period_key) {
+            config.deployment_grace_period = deployment_grace_period.as_u64().unwrap();
+        }
+
+        if let Some(entity_cache_size) = content.get(entity_cache_size_key) {
+            config.entity_cache_size = entity_cache_size.as_u64().unwrap() as usize;
+        }
+
         let fuel_config_key = serde_yaml::Value::String("fuel_node".into());
         let web_config_key = serde_yaml::Value::String("web_api".into());
         let database_config_key = serde_yaml::Value::String("database".into());
         let auth_config_key = serde_yaml::Value::String("authentication".into());
         let rate_limit_config_key = serde_yaml::Value::String("rate_limit".into());
+        let telemetry_config_key = serde_yaml::Value::String("telemetry".into());
 
+        // The Fuel node section is either a single node, or a list of nodes, in which
+        // case the first node is the primary node and the rest are fallbacks.
         if let Some(section) = content.get(fuel_config_key) {
-            let fuel_node_host = section.get(&serde_yaml::Value::String("host".into()));
+            let parse_fuel_node = |section: &serde_yaml::Value| {
+                let mut fuel_node = FuelClientConfig::default();
+                let fuel_node_host =
+                    section.get(&serde_yaml::Value::String("host".into()));
 
-            if let Some(fuel_node_host) = fuel_node_host {
-                config.fuel_node.host = fuel_node_host.as_str().unwrap().to_string();
-            }
-            let fuel_node_port = section.get(&serde_yaml::Value::String("port".into()));
+                if let Some(fuel_node_host) = fuel_node_host {
+                    fuel_node.host = fuel_node_host.as_str().unwrap().to_string();
+                }
+                let fuel_node_port =
+                    section.get(&serde_yaml::Value::String("port".into()));
 
-            if let Some(fuel_node_port) = fuel_node_port {
-                config.fuel_node.port = fuel_node_port.as_u64().unwrap().to_string();
+                if let Some(fuel_node_port) = fuel_node_port {
+                    fuel_node.port = fuel_node_port.as_u64().unwrap().to_string();
+                }
+                fuel_node
+            };
+
+            match section.as_sequence() {
+                Some(nodes) => {
+                    let mut nodes = nodes.iter().map(parse_fuel_node);
+                    config.fuel_node = nodes.next().unwrap_or_default();
+                    config.fuel_node_fallbacks = nodes.collect();
+                }
+                None => config.fuel_node = parse_fuel_node(section),
             }
         }
 
@@ -444,6 +538,14 @@ impl IndexerConfig {
                 let size = max_body_size.as_str().unwrap();
                 config.web_api.max_body_size = usize::from_str(size).unwrap();
             }
+
+            let max_snapshot_size =
+                section.get(&serde_yaml::Value::String("max_snapshot_size".into()));
+
+            if let Some(max_snapshot_size) = max_snapshot_size {
+                let size = max_snapshot_size.as_str().unwrap();
+                config.web_api.max_snapshot_size = usize::from_str(size).unwrap();
+            }
         }
 
         if let Some(section) = content.get(database_config_key) {
@@ -543,6 +645,34 @@ impl IndexerConfig {
             }
         }
 
+        if let Some(section) = content.get(telemetry_config_key) {
+            let telemetry_enabled =
+                section.get(&serde_yaml::Value::String("enabled".into()));
+            if let Some(telemetry_enabled) = telemetry_enabled {
+                config.telemetry.enabled = telemetry_enabled.as_bool().unwrap();
+            }
+
+            let otlp_endpoint =
+                section.get(&serde_yaml::Value::String("otlp_endpoint".into()));
+            if let Some(otlp_endpoint) = otlp_endpoint {
+                config.telemetry.otlp_endpoint =
+                    otlp_endpoint.as_str().unwrap().to_string();
+            }
+
+            let service_name =
+                section.get(&serde_yaml::Value::String("service_name".into()));
+            if let Some(service_name) = service_name {
+                config.telemetry.service_name =
+                    service_name.as_str().unwrap().to_string();
+            }
+
+            let sample_ratio =
+                section.get(&serde_yaml::Value::String("sample_ratio".into()));
+            if let Some(sample_ratio) = sample_ratio {
+                config.telemetry.sample_ratio = sample_ratio.as_f64().unwrap();
+            }
+        }
+
         config.inject_opt_env_vars()?;
 
         Ok(config)
@@ -553,9 +683,118 @@ impl IndexerConfig {
         self.fuel_node.inject_opt_env_vars()?;
         self.database.inject_opt_env_vars()?;
         self.web_api.inject_opt_env_vars()?;
+        self.telemetry.inject_opt_env_vars()?;
 
         Ok(())
     }
+
+    /// All configured Fuel nodes, in order of preference, starting with the primary node.
+    pub fn fuel_nodes(&self) -> Vec<FuelClientConfig> {
+        std::iter::once(self.fuel_node.clone())
+            .chain(self.fuel_node_fallbacks.iter().cloned())
+            .collect()
+    }
+
+    /// Ensure that the settings of this configuration can be used by the indexer service.
+    pub fn validate(&self) -> IndexerConfigResult<()> {
+        if tracing::Level::from_str(&self.log_level).is_err() {
+            return Err(IndexerConfigError::InvalidConfigError(format!(
+                "Invalid log level '{}'.",
+                self.log_level
+            )));
+        }
+
+        if self.block_page_size == 0 {
+            return Err(IndexerConfigError::InvalidConfigError(
+                "block_page_size must be greater than zero.".to_string(),
+            ));
+        }
+
+        if self.rate_limit.enabled
+            && (self.rate_limit.request_count == Some(0)
+                || self.rate_limit.window_size == Some(0))
+        {
+            return Err(IndexerConfigError::InvalidConfigError(
+                "rate_limit request_count and window_size must be greater than zero."
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Apply the settings of a reloaded configuration that can safely change while
+    /// the indexer service is running.
+    ///
+    /// Returns the names of any other settings that changed, which only take effect
+    /// once the indexer service is restarted.
+    pub fn reload(&mut self, config: IndexerConfig) -> Vec<&'static str> {
+        let IndexerConfig {
+            metering_points,
+            max_memory_pages,
+            handler_timeout,
+            log_level,
+            verbose,
+            local_fuel_node,
+            indexer_net_config,
+            fuel_node,
+            fuel_node_fallbacks,
+            web_api,
+            database,
+            metrics,
+            stop_idle_indexers,
+            run_migrations,
+            authentication,
+            rate_limit,
+            replace_indexer,
+            accept_sql_queries,
+            block_page_size,
+            deployment_grace_period,
+            entity_cache_size,
+            telemetry,
+        } = config;
+
+        self.log_level = log_level;
+        self.stop_idle_indexers = stop_idle_indexers;
+        self.authentication = authentication;
+        self.rate_limit = rate_limit;
+        self.replace_indexer = replace_indexer;
+        self.accept_sql_queries = accept_sql_queries;
+        self.block_page_size = block_page_size;
+        self.deployment_grace_period = deployment_grace_period;
+
+        [
+            ("metering_points", metering_points != self.metering_points),
+            (
+                "max_memory_pages",
+                max_memory_pages != self.max_memory_pages,
+            ),
+            ("handler_timeout", handler_timeout != self.handler_timeout),
+            ("verbose", verbose != self.verbose),
+            ("local_fuel_node", local_fuel_node != self.local_fuel_node),
+            (
+                "indexer_net_config",
+                indexer_net_config != self.indexer_net_config,
+            ),
+            ("fuel_node", fuel_node != self.fuel_node),
+            (
+                "fuel_node_fallbacks",
+                fuel_node_fallbacks != self.fuel_node_fallbacks,
+            ),
+            ("web_api", web_api != self.web_api),
+            ("database", database != self.database),
+            ("metrics", metrics != self.metrics),
+            ("run_migrations", run_migrations != self.run_migrations),
+            (
+                "entity_cache_size",
+                entity_cache_size != self.entity_cache_size,
+            ),
+            ("telemetry", telemetry != self.telemetry),
+        ]
+        .into_iter()
+        .filter_map(|(setting, changed)| changed.then_some(setting))
+        .collect()
+    }
 }
 
 #[cfg(test)]
@@ -570,6 +809,7 @@ mod tests {
         let file_path: &str = "foo1.yaml";
         let config_str = r#"
     stop_idle_indexers: true
+    handler_timeout: 30
 
     ## Fuel Node configuration
     #
@@ -584,6 +824,8 @@ mod tests {
         assert!(config.stop_idle_indexers);
         assert!(config.run_migrations);
         assert!(!config.verbose);
+        assert_eq!(config.handler_timeout, Some(30));
+        assert_eq!(config.max_memory_pages, Some(defaults::MAX_MEMORY_PAGES));
 
         let DatabaseConfig::Postgres { verbose, .. } = config.database;
         assert_eq!(verbose.as_str(), "false");
@@ -648,4 +890,97 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_indexer_config_will_parse_list_of_fuel_nodes() {
+        let file_path: &str = "foo5.yaml";
+        let config_str = r#"
+        ## Fuel Node configuration
+        #
+        fuel_node:
+          - host: 1.1.1.1
+            port: 9999
+          - host: 2.2.2.2
+          - port: 4001
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+
+        assert_eq!(config.fuel_node.host, "1.1.1.1".to_string());
+        assert_eq!(config.fuel_node.port, "9999".to_string());
+        assert_eq!(
+            config
+                .fuel_nodes()
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<Vec<_>>(),
+            vec!["1.1.1.1:9999", "2.2.2.2:4000", "localhost:4001"]
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_indexer_config_will_parse_telemetry_section() {
+        let file_path: &str = "foo4.yaml";
+        let config_str = r#"
+        ## Tracing configuration options.
+        #
+        telemetry:
+          enabled: true
+          otlp_endpoint: http://collector:4318
+          sample_ratio: 0.25
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+
+        assert!(config.telemetry.enabled);
+        assert_eq!(config.telemetry.otlp_endpoint, "http://collector:4318");
+        assert_eq!(config.telemetry.service_name, "fuel-indexer");
+        assert_eq!(config.telemetry.sample_ratio, 0.25);
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_indexer_config_reload_only_applies_runtime_settings() {
+        let mut config = IndexerConfig::default();
+
+        let reloaded = IndexerConfig {
+            log_level: "debug".to_string(),
+            block_page_size: 50,
+            stop_idle_indexers: true,
+            web_api: WebApiConfig {
+                port: "8080".to_string(),
+                ..WebApiConfig::default()
+            },
+            ..IndexerConfig::default()
+        };
+        assert!(reloaded.validate().is_ok());
+
+        let requires_restart = config.reload(reloaded);
+
+        assert_eq!(requires_restart, vec!["web_api"]);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.block_page_size, 50);
+        assert!(config.stop_idle_indexers);
+        assert_eq!(config.web_api, WebApiConfig::default());
+    }
+
+    #[test]
+    fn test_indexer_config_validation_rejects_unusable_settings() {
+        let config = IndexerConfig {
+            log_level: "loud".to_string(),
+            ..IndexerConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = IndexerConfig {
+            block_page_size: 0,
+            ..IndexerConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,43 @@
+use crate::{
+    config::{Env, IndexerConfigResult},
+    defaults,
+};
+pub use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+/// Indexer service tracing (OpenTelemetry) configuration.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct TelemetryConfig {
+    /// Export tracing spans to an OTLP collector.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// OTLP/HTTP endpoint of the collector to which spans are exported.
+    #[serde(default)]
+    pub otlp_endpoint: String,
+
+    /// Service name attached to all exported spans.
+    #[serde(default)]
+    pub service_name: String,
+
+    /// Ratio of traces to sample, between 0.0 and 1.0.
+    #[serde(default)]
+    pub sample_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::TELEMETRY_ENABLED,
+            otlp_endpoint: defaults::OTLP_ENDPOINT.to_string(),
+            service_name: defaults::TELEMETRY_SERVICE_NAME.to_string(),
+            sample_ratio: defaults::TELEMETRY_SAMPLE_RATIO,
+        }
+    }
+}
+
+impl Env for TelemetryConfig {
+    fn inject_opt_env_vars(&mut self) -> IndexerConfigResult<()> {
+        Ok(())
+    }
+}
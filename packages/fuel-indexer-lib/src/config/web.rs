@@ -9,7 +9,7 @@ use std::net::SocketAddr;
 
 pub use clap::Parser;
 
-#[derive(Clone, Deserialize, Debug, Serialize)]
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq)]
 pub struct WebApiConfig {
     /// Web API host.
     #[serde(default)]
@@ -22,6 +22,10 @@ pub struct WebApiConfig {
     /// Max body size for web server requests.
     #[serde(default)]
     pub max_body_size: usize,
+
+    /// Max body size for snapshot import requests.
+    #[serde(default)]
+    pub max_snapshot_size: usize,
 }
 
 impl std::string::ToString for WebApiConfig {
@@ -45,6 +49,7 @@ impl Default for WebApiConfig {
             host: defaults::WEB_API_HOST.into(),
             port: defaults::WEB_API_PORT.into(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_snapshot_size: defaults::MAX_SNAPSHOT_SIZE,
         }
     }
 }
@@ -70,6 +70,41 @@ lazy_static! {
         "Identity"
     ]);
 
+    /// Set of numeric scalar types, which can be given a `@default` value.
+    pub static ref NUMERIC_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+        "Int1",
+        "Int16",
+        "Int4",
+        "Int8",
+        "Timestamp",
+        "UInt1",
+        "UInt16",
+        "UInt4",
+        "UInt8",
+    ]);
+
+    /// Set of numeric scalar types that cannot hold negative values.
+    pub static ref UNSIGNED_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+        "Timestamp",
+        "UInt1",
+        "UInt16",
+        "UInt4",
+        "UInt8",
+    ]);
+
+    /// Keywords that can be used in a `@check` expression.
+    pub static ref CHECK_EXPR_KEYWORDS: HashSet<&'static str> = HashSet::from([
+        "AND",
+        "BETWEEN",
+        "FALSE",
+        "IN",
+        "IS",
+        "NOT",
+        "NULL",
+        "OR",
+        "TRUE",
+    ]);
+
     /// Sway ABI types we don't support and won't in the near future.
     pub static ref IGNORED_ABI_JSON_TYPES: HashSet<&'static str> =
         HashSet::from(["()"]);
@@ -208,9 +243,6 @@ lazy_static! {
     ]);
 
 
-    /// ABI types not allowed in the contract ABI.
-    pub static ref UNSUPPORTED_ABI_JSON_TYPES: HashSet<&'static str> = HashSet::from(["Vec"]);
-
     /// Generic Sway ABI types.
     pub static ref IGNORED_GENERIC_METADATA: HashSet<&'static str> = HashSet::from([
         "generic T",
@@ -218,11 +250,6 @@ lazy_static! {
         "struct RawVec",
     ]);
 
-    pub static ref GENERIC_STRUCTS: HashSet<&'static str> = HashSet::from([
-        "Vec",
-        "Option"
-    ]);
-
     /// Set of Rust primitive types.
     pub static ref RUST_PRIMITIVES: HashSet<&'static str> =
         HashSet::from(["u8", "u16", "u32", "u64", "bool", "String"]);
@@ -37,9 +37,18 @@ pub const STOP_IDLE_INDEXERS: bool = false;
 /// Max body size for GraphQL API requests (5 MB).
 pub const MAX_BODY_SIZE: usize = 5242880;
 
+/// Max body size for snapshot import requests (1 GB).
+pub const MAX_SNAPSHOT_SIZE: usize = 1073741824;
+
 /// Size of web-API-to-executor-service channel.
 pub const SERVICE_REQUEST_CHANNEL_SIZE: usize = 100;
 
+/// How often (in seconds) to check the indexer service's config file for changes.
+pub const CONFIG_FILE_POLL_INTERVAL: u64 = 5;
+
+/// How often (in seconds) to check for retired blue/green deployments to stop and remove.
+pub const RETIRED_DEPLOYMENT_POLL_INTERVAL: u64 = 5;
+
 /// How long to wait if request to the Fuel GQL client returned no data.
 pub const IDLE_SERVICE_WAIT_SECS: u64 = 1;
 
@@ -61,6 +70,9 @@ pub const DELAY_FOR_SERVICE_ERROR: u64 = 1;
 /// Amount of time to wait before fetching new blocks, if the Fuel GQL client returns no data.
 pub const DELAY_FOR_EMPTY_PAGE: u64 = 1;
 
+/// Amount of time, in seconds, for which a Fuel node that failed is ranked below healthy nodes.
+pub const FUEL_NODE_FAILBACK_COOLDOWN_SECS: u64 = 30;
+
 /// Run migrations on startup.
 pub const RUN_MIGRATIONS: bool = true;
 
@@ -124,13 +136,38 @@ pub const LOG_LEVEL: &str = "info";
 /// The number of WASM opcodes after which the indexer's `handle_events` function will stop execution.
 pub const METERING_POINTS: u64 = 30_000_000_000;
 
+/// The maximum number of 64KiB pages of linear memory that an indexer's WASM module can use.
+pub const MAX_MEMORY_PAGES: u32 = 16_384;
+
+/// The number of seconds after which a single call to an indexer's `handle_events` function will be stopped.
+pub const HANDLER_TIMEOUT: u64 = 300;
+
 /// Whether to allow replacing an indexer.
 ///
 /// If this is disabled, then an HTTP 409 Conflict will be returned if an indexer with the same name already exists.
 pub const REPLACE_INDEXER: bool = false;
 
+/// The number of seconds for which the data of a retired blue/green deployment is kept,
+/// after the new deployment has taken over.
+pub const DEPLOYMENT_GRACE_PERIOD: u64 = 3600;
+
+/// The maximum number of entities cached by an indexer while it processes a batch of blocks.
+pub const ENTITY_CACHE_SIZE: usize = 10_000;
+
 /// Whether to remove the indexed data when replacing an indexer.
 pub const REMOVE_DATA: bool = false;
 
 /// Allow the web server to accept raw SQL queries.
 pub const ACCEPT_SQL: bool = false;
+
+/// Export tracing spans to an OTLP collector.
+pub const TELEMETRY_ENABLED: bool = false;
+
+/// OTLP/HTTP endpoint of the collector to which spans are exported.
+pub const OTLP_ENDPOINT: &str = "http://localhost:4318";
+
+/// Service name attached to all exported spans.
+pub const TELEMETRY_SERVICE_NAME: &str = "fuel-indexer";
+
+/// Ratio of traces to sample, between 0.0 and 1.0.
+pub const TELEMETRY_SAMPLE_RATIO: f64 = 1.0;
@@ -6,11 +6,14 @@ pub mod validator;
 pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema};
 pub use validator::GraphQLSchemaValidator;
 
-use async_graphql_parser::types::FieldDefinition;
+use async_graphql_parser::types::{
+    FieldDefinition, TypeDefinition, TypeKind, TypeSystemDefinition,
+};
+use async_graphql_value::ConstValue;
 use fuel_indexer_types::graphql::IndexMetadata;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use types::IdCol;
+use types::{IdCol, ProvenanceCol};
 
 /// Maximum amount of foreign key list fields that can exist on a `TypeDefinition`
 pub const MAX_FOREIGN_KEY_LIST_FIELDS: usize = 10;
@@ -130,6 +133,130 @@ pub fn extract_foreign_key_info(
     (ref_coltype, ref_colname, ref_tablename)
 }
 
+/// Return the name of the field from which a given `FieldDefinition` is derived, if it
+/// has a `@derivedFrom` directive.
+pub fn derived_from_field(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "derivedFrom")
+        .map(|d| match d.node.get_argument("field").map(|v| &v.node) {
+            Some(ConstValue::String(field)) => field.clone(),
+            _ => panic!(
+                "FieldDefinition({}) must specify the field from which it is derived, e.g. `@derivedFrom(field: \"owner\")`.",
+                f.name
+            ),
+        })
+}
+
+/// Return the default value of a given `FieldDefinition`, if it has a `@default` directive.
+pub fn field_default_value(f: &FieldDefinition) -> Option<ConstValue> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "default")
+        .map(|d| match d.node.get_argument("value") {
+            Some(v) => v.node.clone(),
+            None => panic!(
+                "FieldDefinition({}) must specify a default value, e.g. `@default(value: 0)`.",
+                f.name
+            ),
+        })
+}
+
+/// Return the check expression of a given `FieldDefinition`, if it has a `@check` directive.
+pub fn field_check_expr(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "check")
+        .map(|d| match d.node.get_argument("expr").map(|v| &v.node) {
+            Some(ConstValue::String(expr)) => expr.clone(),
+            _ => panic!(
+                "FieldDefinition({}) must specify a check expression, e.g. `@check(expr: \"amount > 0\")`.",
+                f.name
+            ),
+        })
+}
+
+/// An index over one or more fields of an object, declared with an object-level
+/// `@index` directive.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ObjectIndex {
+    /// Names of the fields to which the index is applied, in order.
+    pub fields: Vec<String>,
+
+    /// Whether this index is unique.
+    pub unique: bool,
+
+    /// Name of the `IndexType` of this index (e.g., `BTree`).
+    pub index_type: String,
+}
+
+/// Return all of the indices declared on a given `TypeDefinition` via `@index` directives.
+pub fn object_indices(typ: &TypeDefinition) -> Vec<ObjectIndex> {
+    let obj_name = typ.name.to_string();
+    typ.directives
+        .iter()
+        .filter(|d| d.node.name.to_string() == "index")
+        .map(|d| {
+            let fields = match d.node.get_argument("fields").map(|v| &v.node) {
+                Some(ConstValue::List(fields)) => fields
+                    .iter()
+                    .map(|f| match f {
+                        ConstValue::String(f) => f.clone(),
+                        _ => panic!("TypeDefinition({obj_name}) has an `@index` directive with an invalid field `{f}`. Fields must be strings."),
+                    })
+                    .collect(),
+                Some(ConstValue::String(f)) => vec![f.clone()],
+                _ => panic!("TypeDefinition({obj_name}) has an `@index` directive that does not specify any fields, e.g. `@index(fields: [\"owner\", \"pool\"])`."),
+            };
+
+            let unique = matches!(
+                d.node.get_argument("unique").map(|v| &v.node),
+                Some(ConstValue::Boolean(true))
+            );
+
+            let index_type = match d.node.get_argument("type").map(|v| &v.node) {
+                Some(ConstValue::Enum(t)) => t.to_string(),
+                _ => "BTree".to_string(),
+            };
+
+            ObjectIndex {
+                fields,
+                unique,
+                index_type,
+            }
+        })
+        .collect()
+}
+
+/// Whether a given `TypeDefinition` is marked with the `@provenance` directive.
+pub fn is_provenance_type(typ: &TypeDefinition) -> bool {
+    typ.directives
+        .iter()
+        .any(|d| d.node.name.to_string() == "provenance")
+}
+
+/// Return the `FieldDefinition`s that are added to `TypeDefinition`s marked with `@provenance`.
+///
+/// Provenance fields are nullable, since they're filled in by the indexer service rather than
+/// by handlers.
+pub fn provenance_fields() -> Vec<FieldDefinition> {
+    let fields = ProvenanceCol::fields()
+        .iter()
+        .map(|(name, typ)| format!("{name}: {typ}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let doc =
+        async_graphql_parser::parse_schema(format!("type Provenance {{\n{fields}\n}}"))
+            .expect("Bad provenance fields.");
+    match doc.definitions.into_iter().next() {
+        Some(TypeSystemDefinition::Type(t)) => match t.node.kind {
+            TypeKind::Object(o) => o.fields.into_iter().map(|f| f.node).collect(),
+            _ => unreachable!("Provenance fields are always an object."),
+        },
+        _ => unreachable!("Provenance fields are always a type definition."),
+    }
+}
+
 /// Return a fully qualified name for a given `FieldDefinition` on a given `TypeDefinition`.
 pub fn field_id(typdef_name: &str, field_name: &str) -> String {
     format!("{typdef_name}.{field_name}")
@@ -6,8 +6,10 @@
 use crate::{
     fully_qualified_namespace,
     graphql::{
-        extract_foreign_key_info, field_id, field_type_name, is_list_type,
-        list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, IdCol, BASE_SCHEMA,
+        derived_from_field, extract_foreign_key_info, field_check_expr,
+        field_default_value, field_id, field_type_name, is_list_type, is_provenance_type,
+        list_field_type_name, object_indices, provenance_fields, GraphQLSchema,
+        GraphQLSchemaValidator, IdCol, BASE_SCHEMA,
     },
     join_table_name, ExecutionSource,
 };
@@ -235,6 +237,17 @@ pub struct ParsedGraphQLSchema {
     /// All unique names of foreign key types in the schema.
     foreign_key_mappings: HashMap<String, HashMap<String, (String, String)>>,
 
+    /// Mapping of lowercase object names to their derived fields, along with the name of
+    /// the referencing object and the foreign key field from which each field is derived.
+    ///
+    /// Derived fields (i.e., fields with a `@derivedFrom` directive) are not stored in the
+    /// object's table, and are instead resolved from the table of the referencing object.
+    derived_from_mappings: HashMap<String, HashMap<String, (String, String)>>,
+
+    /// Names of objects marked with the `@provenance` directive, whose tables include
+    /// columns recording the block and transaction that last wrote each row.
+    provenance_type_names: HashSet<String>,
+
     /// All type definitions in the schema.
     type_defs: HashMap<String, TypeDefinition>,
 
@@ -282,6 +295,8 @@ impl Default for ParsedGraphQLSchema {
             field_defs: HashMap::new(),
             field_type_optionality: HashMap::new(),
             foreign_key_mappings: HashMap::new(),
+            derived_from_mappings: HashMap::new(),
+            provenance_type_names: HashSet::new(),
             type_defs: HashMap::new(),
             list_field_types: HashSet::new(),
             list_type_defs: HashMap::new(),
@@ -376,6 +391,31 @@ impl ParsedGraphQLSchema {
         &self.foreign_key_mappings
     }
 
+    /// Mapping of lowercase object names to their derived fields, along with the name of
+    /// the referencing object and the foreign key field from which each field is derived.
+    pub fn derived_from_mappings(
+        &self,
+    ) -> &HashMap<String, HashMap<String, (String, String)>> {
+        &self.derived_from_mappings
+    }
+
+    /// Whether the given field on the given object is derived from a field of another object.
+    pub fn is_derived_field(&self, obj_name: &str, field_name: &str) -> bool {
+        self.derived_from_mappings
+            .get(&obj_name.to_lowercase())
+            .is_some_and(|fields| fields.contains_key(field_name))
+    }
+
+    /// Whether the given object is marked with the `@provenance` directive.
+    pub fn is_provenance_type(&self, name: &str) -> bool {
+        self.provenance_type_names.contains(name)
+    }
+
+    /// Whether any object is marked with the `@provenance` directive.
+    pub fn has_provenance_types(&self) -> bool {
+        !self.provenance_type_names.is_empty()
+    }
+
     /// All objects and their field names and types, indexed by object name.
     pub fn object_field_mappings(&self) -> &HashMap<String, BTreeMap<String, String>> {
         &self.object_field_mappings
@@ -439,6 +479,25 @@ impl ParsedGraphQLSchema {
             .collect()
     }
 
+    /// Return the names of the fields of an object that are marked with a `@search` directive.
+    pub fn search_fields(&self, obj_name: &str) -> Vec<String> {
+        self.objects
+            .get(obj_name)
+            .map(|o| {
+                o.fields
+                    .iter()
+                    .filter(|f| {
+                        f.node
+                            .directives
+                            .iter()
+                            .any(|d| d.node.name.to_string() == "search")
+                    })
+                    .map(|f| f.node.name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Whether the given field type name is a possible foreign key.
     pub fn is_possible_foreign_key(&self, name: &str) -> bool {
         self.parsed_typedef_names.contains(name)
@@ -541,9 +600,69 @@ impl SchemaDecoder {
             self.decode_type_system_definifion(def)?;
         }
         self.build_typedef_names_to_types();
+        self.validate_derived_fields();
+        self.validate_default_values();
         Ok(())
     }
 
+    /// Ensure that each `@default` value is a literal of its field's type.
+    ///
+    /// This can only be done once all enums have been decoded, since the type of
+    /// a field may be an enum that is defined after the object on which it is found.
+    fn validate_default_values(&self) {
+        let parsed = &self.parsed_graphql_schema;
+        for (f, obj_name) in parsed.field_defs.values() {
+            if let Some(value) = field_default_value(f) {
+                let field_typ_name = field_type_name(f);
+                let enum_values = parsed
+                    .enum_names
+                    .contains(&field_typ_name)
+                    .then(|| parsed.object_field_mappings.get(&field_typ_name))
+                    .flatten()
+                    .map(|values| values.keys().cloned().collect::<Vec<String>>());
+
+                GraphQLSchemaValidator::default_value_is_well_formed(
+                    f,
+                    obj_name,
+                    &value,
+                    enum_values,
+                );
+            }
+        }
+    }
+
+    /// Ensure that each derived field is derived from a foreign key on the referencing
+    /// object that points back to the object on which the derived field is found.
+    ///
+    /// This can only be done once all objects have been decoded, since the referencing
+    /// object may be defined after the object on which the derived field is found.
+    fn validate_derived_fields(&self) {
+        let parsed = &self.parsed_graphql_schema;
+        for (f, obj_name) in parsed.field_defs.values() {
+            if let Some(derived_from) = derived_from_field(f) {
+                let ref_obj_name = field_type_name(f);
+                let references_parent = parsed
+                    .foreign_key_mappings
+                    .get(&ref_obj_name.to_lowercase())
+                    .and_then(|fks| fks.get(&derived_from))
+                    .is_some_and(|(ref_tablename, _)| {
+                        *ref_tablename == obj_name.to_lowercase()
+                    })
+                    && parsed
+                        .field_defs
+                        .get(&field_id(&ref_obj_name, &derived_from))
+                        .is_some_and(|(ref_field, _)| !is_list_type(ref_field));
+
+                GraphQLSchemaValidator::derived_field_references_parent(
+                    obj_name,
+                    &f.name.to_string(),
+                    &derived_from,
+                    references_parent,
+                );
+            }
+        }
+    }
+
     fn decode_type_system_definifion(
         &mut self,
         def: &TypeSystemDefinition,
@@ -774,6 +893,31 @@ impl SchemaDecoder {
             println!("Skipping TypeDefinition '{obj_name}', which is not marked with an @entity directive.");
             return;
         }
+
+        // Derived fields are resolved from the table of the referencing object, so they're
+        // removed from the object itself in order to prevent columns from being created for them.
+        let mut derived_fields = Vec::new();
+        let mut stored_fields = Vec::new();
+        for field in o.fields.iter() {
+            match derived_from_field(&field.node) {
+                Some(derived_from) => {
+                    derived_fields.push((field.node.clone(), derived_from))
+                }
+                None => stored_fields.push(field.clone()),
+            }
+        }
+        let o = &ObjectType {
+            implements: o.implements.clone(),
+            fields: stored_fields,
+        };
+        let node = TypeDefinition {
+            kind: TypeKind::Object(o.clone()),
+            ..node
+        };
+
+        self.parsed_graphql_schema
+            .type_defs
+            .insert(obj_name.clone(), node.clone());
         self.parsed_graphql_schema
             .objects
             .insert(obj_name.clone(), o.clone());
@@ -795,12 +939,35 @@ impl SchemaDecoder {
             GraphQLSchemaValidator::virtual_type_has_no_id_field(o, &obj_name);
         }
 
+        for index in object_indices(&node) {
+            GraphQLSchemaValidator::object_index_is_well_formed(o, &obj_name, &index);
+        }
+
+        let is_provenance = is_provenance_type(&node);
+        if is_provenance {
+            GraphQLSchemaValidator::provenance_type_is_well_formed(
+                o, &obj_name, is_virtual,
+            );
+
+            self.parsed_graphql_schema
+                .provenance_type_names
+                .insert(obj_name.clone());
+        }
+
         // Since we have to use this manual `is_list_type` for each field, we might as well
         // keep track of how many m2m fields we have for this object here. We could also move this
         // logic to the `GraphQLSchemaValidator` itself, but that means we'd have to copy over the
         // `is_list_type` logic there as well.
         let mut m2m_field_count = 0;
 
+        // Only stored, non-list fields can be referenced by `@check` expressions.
+        let column_names = o
+            .fields
+            .iter()
+            .filter(|f| !is_list_type(&f.node))
+            .map(|f| f.node.name.to_string())
+            .collect::<HashSet<String>>();
+
         let mut field_mapping = BTreeMap::new();
         for (i, field) in o.fields.iter().enumerate() {
             GraphQLSchemaValidator::id_field_is_type_id(&field.node, &obj_name);
@@ -810,6 +977,16 @@ impl SchemaDecoder {
             let fid = field_id(&obj_name, &field_name);
 
             GraphQLSchemaValidator::ensure_fielddef_is_not_nested_list(&field.node);
+            GraphQLSchemaValidator::search_field_is_charfield(&field.node, &obj_name);
+
+            if let Some(expr) = field_check_expr(&field.node) {
+                GraphQLSchemaValidator::check_expr_is_well_formed(
+                    &field.node,
+                    &obj_name,
+                    &expr,
+                    &column_names,
+                );
+            }
 
             self.parsed_graphql_schema
                 .object_ordered_fields
@@ -926,6 +1103,53 @@ impl SchemaDecoder {
                 .field_defs
                 .insert(fid, (field.node.clone(), obj_name.clone()));
         }
+
+        for (field, derived_from) in derived_fields {
+            GraphQLSchemaValidator::derived_field_is_list_type(&field, &obj_name);
+
+            let field_name = field.name.to_string();
+            let field_typ_name = field_type_name(&field);
+            let fid = field_id(&obj_name, &field_name);
+
+            self.parsed_graphql_schema
+                .derived_from_mappings
+                .entry(obj_name.to_lowercase())
+                .or_default()
+                .insert(field_name.clone(), (field_typ_name.clone(), derived_from));
+
+            field_mapping.insert(field_name, field_typ_name.clone());
+            self.parsed_graphql_schema
+                .field_type_optionality
+                .insert(fid.clone(), field.ty.node.nullable);
+            self.parsed_graphql_schema
+                .field_type_mappings
+                .insert(fid.clone(), field_typ_name);
+            self.parsed_graphql_schema
+                .field_defs
+                .insert(fid, (field, obj_name.clone()));
+        }
+
+        // Provenance fields are filled in by the indexer service rather than by handlers, so
+        // they're queryable, but aren't part of the object itself.
+        if is_provenance {
+            for field in provenance_fields() {
+                let field_name = field.name.to_string();
+                let field_typ_name = field_type_name(&field);
+                let fid = field_id(&obj_name, &field_name);
+
+                field_mapping.insert(field_name, field_typ_name.clone());
+                self.parsed_graphql_schema
+                    .field_type_optionality
+                    .insert(fid.clone(), field.ty.node.nullable);
+                self.parsed_graphql_schema
+                    .field_type_mappings
+                    .insert(fid.clone(), field_typ_name);
+                self.parsed_graphql_schema
+                    .field_defs
+                    .insert(fid, (field, obj_name.clone()));
+            }
+        }
+
         self.parsed_graphql_schema
             .object_field_mappings
             .insert(obj_name, field_mapping);
@@ -1329,6 +1553,219 @@ type Bar @entity {
     id: ID!
     foo: [Foo!]! @join(on:name)
 }
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(amount) on TypeDefinition(Foo) cannot contain a `@search` directive, as it is not a `Charfield`. Found type `UInt8!`."
+    )]
+    fn test_schema_validator_search_field_is_charfield() {
+        let schema = r#"
+type Foo @entity {
+    id: ID!
+    name: Charfield! @search
+    amount: UInt8! @search
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_excludes_derived_fields_from_objects() {
+        let schema = r#"
+type Owner @entity {
+    id: ID!
+    name: Charfield!
+    pets: [Pet!]! @derivedFrom(field: "owner")
+}
+
+type Pet @entity {
+    id: ID!
+    owner: Owner!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let owner_fields = parsed
+            .objects()
+            .get("Owner")
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.node.name.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(owner_fields, vec!["id".to_string(), "name".to_string()]);
+
+        assert!(parsed.is_derived_field("Owner", "pets"));
+        assert!(!parsed.is_derived_field("Pet", "owner"));
+        assert_eq!(
+            parsed
+                .derived_from_mappings()
+                .get("owner")
+                .unwrap()
+                .get("pets"),
+            Some(&("Pet".to_string(), "owner".to_string()))
+        );
+        assert_eq!(
+            parsed
+                .object_field_mappings()
+                .get("Owner")
+                .unwrap()
+                .get("pets"),
+            Some(&"Pet".to_string())
+        );
+        assert!(!parsed.is_list_typedef("Owner"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(pet) on TypeDefinition(Owner) has a `@derivedFrom` directive, and so must be a list type. Found type `Pet`."
+    )]
+    fn test_schema_validator_derived_field_is_list_type() {
+        let schema = r#"
+type Owner @entity {
+    id: ID!
+    pet: Pet @derivedFrom(field: "owner")
+}
+
+type Pet @entity {
+    id: ID!
+    owner: Owner!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(pets) on TypeDefinition(Owner) is derived from `name`, which is not a foreign key referencing TypeDefinition(Owner)."
+    )]
+    fn test_schema_validator_derived_field_references_parent() {
+        let schema = r#"
+type Owner @entity {
+    id: ID!
+    pets: [Pet!]! @derivedFrom(field: "name")
+}
+
+type Pet @entity {
+    id: ID!
+    name: Charfield!
+    owner: Owner!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(Position) has an `@index` directive on FieldDefinition(pool), which is not a stored field of TypeDefinition(Position)."
+    )]
+    fn test_schema_validator_object_index_is_well_formed() {
+        let schema = r#"
+type Position @entity @index(fields: ["pool", "owner"], unique: true) {
+    id: ID!
+    owner: Address!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(decimals) on TypeDefinition(Token) has a `@default` value `-1` that is not a valid `UInt1`."
+    )]
+    fn test_schema_validator_default_value_is_well_formed() {
+        let schema = r#"
+type Token @entity {
+    id: ID!
+    decimals: UInt1! @default(value: -1)
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(decimals) on TypeDefinition(Token) has a `@check` expression referencing `supply`, which is not a field of TypeDefinition(Token)."
+    )]
+    fn test_schema_validator_check_expr_is_well_formed() {
+        let schema = r#"
+type Token @entity {
+    id: ID!
+    decimals: UInt1! @check(expr: "decimals <= 18 AND supply > 0")
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(_updated_at_block) on TypeDefinition(Pool) clashes with a field added by the `@provenance` directive."
+    )]
+    fn test_schema_validator_provenance_type_is_well_formed() {
+        let schema = r#"
+type Pool @entity @provenance {
+    id: ID!
+    _updated_at_block: UInt8!
+}
 "#;
 
         let _ = ParsedGraphQLSchema::new(
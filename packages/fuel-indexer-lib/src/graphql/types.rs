@@ -37,3 +37,29 @@ impl ObjectCol {
         "Object"
     }
 }
+
+/// Represents the provenance fields added to `TypeDefinition`s marked with `@provenance`.
+pub struct ProvenanceCol;
+impl ProvenanceCol {
+    pub const CREATED_AT_BLOCK: &'static str = "_created_at_block";
+    pub const UPDATED_AT_BLOCK: &'static str = "_updated_at_block";
+    pub const UPDATED_TX_ID: &'static str = "_updated_tx_id";
+    pub const CREATED_AT: &'static str = "_created_at";
+    pub const UPDATED_AT: &'static str = "_updated_at";
+
+    /// Name and GraphQL type of each provenance field, in column order.
+    pub fn fields() -> [(&'static str, &'static str); 5] {
+        [
+            (Self::CREATED_AT_BLOCK, "BlockHeight"),
+            (Self::UPDATED_AT_BLOCK, "BlockHeight"),
+            (Self::UPDATED_TX_ID, "TxId"),
+            (Self::CREATED_AT, "Int8"),
+            (Self::UPDATED_AT, "Int8"),
+        ]
+    }
+
+    /// Whether the given column is a provenance column.
+    pub fn is_provenance_col(name: &str) -> bool {
+        Self::fields().iter().any(|(field, _)| *field == name)
+    }
+}
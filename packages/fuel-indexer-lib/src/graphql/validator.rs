@@ -1,7 +1,13 @@
-use crate::{constants::*, graphql::MAX_FOREIGN_KEY_LIST_FIELDS};
+use crate::{
+    constants::*,
+    graphql::{
+        is_list_type, types::ProvenanceCol, ObjectIndex, MAX_FOREIGN_KEY_LIST_FIELDS,
+    },
+};
 use async_graphql_parser::types::{
     FieldDefinition, ObjectType, TypeDefinition, TypeKind,
 };
+use async_graphql_value::ConstValue;
 use std::collections::HashSet;
 
 /// General container used to store a set of GraphQL schema validation functions.
@@ -117,6 +123,232 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure that a `FieldDefinition` with a `@derivedFrom` directive is a list of entities.
+    pub fn derived_field_is_list_type(f: &FieldDefinition, obj_name: &str) {
+        let name = f.name.to_string();
+        let field_type = f.ty.node.to_string();
+        if field_type.matches(['[', ']']).count() != 2 {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@derivedFrom` directive, and so must be a list type. Found type `{field_type}`.");
+        }
+    }
+
+    /// Ensure that the field from which a `FieldDefinition` is derived is a foreign key
+    /// referencing the `TypeDefinition` on which the derived field is found.
+    pub fn derived_field_references_parent(
+        obj_name: &str,
+        field_name: &str,
+        derived_from: &str,
+        references_parent: bool,
+    ) {
+        if !references_parent {
+            panic!("FieldDefinition({field_name}) on TypeDefinition({obj_name}) is derived from `{derived_from}`, which is not a foreign key referencing TypeDefinition({obj_name}).");
+        }
+    }
+
+    /// Ensure that an `@index` directive on an object is applied to stored, non-list
+    /// fields of that object, using a supported index type.
+    pub fn object_index_is_well_formed(
+        o: &ObjectType,
+        obj_name: &str,
+        index: &ObjectIndex,
+    ) {
+        if index.fields.is_empty() {
+            panic!("TypeDefinition({obj_name}) has an `@index` directive that does not specify any fields.");
+        }
+
+        for field_name in index.fields.iter() {
+            match o.fields.iter().find(|f| f.node.name.to_string() == *field_name) {
+                Some(f) => {
+                    if is_list_type(&f.node) {
+                        panic!("TypeDefinition({obj_name}) has an `@index` directive on FieldDefinition({field_name}), which is a list type.");
+                    }
+                }
+                None => panic!("TypeDefinition({obj_name}) has an `@index` directive on FieldDefinition({field_name}), which is not a stored field of TypeDefinition({obj_name})."),
+            }
+        }
+
+        match index.index_type.as_str() {
+            "BTree" => {}
+            "Hash" => {
+                if index.fields.len() > 1 || index.unique {
+                    panic!("TypeDefinition({obj_name}) has an `@index` directive of type `Hash` that is unique or applied to multiple fields. `Hash` indices may only be non-unique, and applied to a single field.");
+                }
+            }
+            other => panic!("TypeDefinition({obj_name}) has an `@index` directive of unsupported type `{other}`."),
+        }
+    }
+
+    /// Ensure that a `@provenance` object is persisted, and doesn't define any fields
+    /// that clash with the provenance fields added by the indexer.
+    pub fn provenance_type_is_well_formed(
+        o: &ObjectType,
+        obj_name: &str,
+        is_virtual: bool,
+    ) {
+        if is_virtual {
+            panic!("TypeDefinition({obj_name}) is virtual, and cannot use the `@provenance` directive.");
+        }
+
+        if let Some(f) = o
+            .fields
+            .iter()
+            .find(|f| ProvenanceCol::is_provenance_col(&f.node.name.to_string()))
+        {
+            panic!(
+                "FieldDefinition({}) on TypeDefinition({obj_name}) clashes with a field added by the `@provenance` directive.",
+                f.node.name
+            );
+        }
+    }
+
+    /// Ensure that the value of a `@default` directive on a `FieldDefinition` is
+    /// a literal of the field's type.
+    ///
+    /// Defaults are only supported on numeric, `Charfield`, `Boolean`, and enum fields.
+    pub fn default_value_is_well_formed(
+        f: &FieldDefinition,
+        obj_name: &str,
+        value: &ConstValue,
+        enum_values: Option<Vec<String>>,
+    ) {
+        let name = f.name.to_string();
+        let field_type = f.ty.node.to_string().replace('!', "");
+
+        let is_well_formed = match (field_type.as_str(), value) {
+            (_, _) if is_list_type(f) => false,
+            (typ, ConstValue::Number(n)) if NUMERIC_SCALAR_TYPES.contains(typ) => {
+                if UNSIGNED_SCALAR_TYPES.contains(typ) {
+                    n.is_u64()
+                } else {
+                    n.is_i64()
+                }
+            }
+            ("Charfield", ConstValue::String(_)) => true,
+            ("Boolean", ConstValue::Boolean(_)) => true,
+            (_, ConstValue::Enum(v)) => enum_values
+                .as_ref()
+                .is_some_and(|values| values.contains(&v.to_string())),
+            (_, ConstValue::String(v)) => enum_values
+                .as_ref()
+                .is_some_and(|values| values.contains(v)),
+            _ => false,
+        };
+
+        if !is_well_formed {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@default` value `{value}` that is not a valid `{field_type}`. Defaults are only supported on numeric, `Charfield`, `Boolean`, and enum fields.");
+        }
+    }
+
+    /// Ensure that the expression of a `@check` directive on a `FieldDefinition` is
+    /// a simple expression over the fields of its object.
+    ///
+    /// Since the expression is used verbatim in a SQL `CHECK` constraint, only field names,
+    /// numbers, strings, comparison operators, parentheses, commas, and the keywords
+    /// `AND`, `OR`, `NOT`, `IN`, `BETWEEN`, `IS`, `NULL`, `TRUE`, and `FALSE` are allowed.
+    pub fn check_expr_is_well_formed(
+        f: &FieldDefinition,
+        obj_name: &str,
+        expr: &str,
+        field_names: &HashSet<String>,
+    ) {
+        let name = f.name.to_string();
+        let unsupported = |token: &str| -> ! {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@check` expression containing unsupported token `{token}`. Only field names, numbers, strings, comparisons, and the keywords AND, OR, NOT, IN, BETWEEN, IS, NULL, TRUE, and FALSE are allowed.");
+        };
+
+        let chars = expr.chars().collect::<Vec<char>>();
+        let mut depth = 0;
+        let mut token_count = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let start = i;
+            match c {
+                c if c.is_whitespace() => {
+                    i += 1;
+                    continue;
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    while i < chars.len()
+                        && (chars[i].is_ascii_alphanumeric() || chars[i] == '_')
+                    {
+                        i += 1;
+                    }
+                    let word = chars[start..i].iter().collect::<String>();
+                    if !CHECK_EXPR_KEYWORDS.contains(word.to_uppercase().as_str())
+                        && !field_names.contains(&word)
+                    {
+                        panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@check` expression referencing `{word}`, which is not a field of TypeDefinition({obj_name}).");
+                    }
+                }
+                c if c.is_ascii_digit() || c == '-' => {
+                    i += 1;
+                    while i < chars.len()
+                        && (chars[i].is_ascii_digit() || chars[i] == '.')
+                    {
+                        i += 1;
+                    }
+                    if c == '-' && i == start + 1 {
+                        unsupported("-");
+                    }
+                }
+                '\'' => {
+                    i += 1;
+                    loop {
+                        match chars.get(i) {
+                            Some('\'') if chars.get(i + 1) == Some(&'\'') => i += 2,
+                            Some('\'') => break,
+                            Some(_) => i += 1,
+                            None => unsupported("'"),
+                        }
+                    }
+                    i += 1;
+                }
+                '<' | '>' | '!' | '=' => {
+                    i += 1;
+                    if i < chars.len() && matches!(chars[i], '=' | '>') {
+                        i += 1;
+                    }
+                    let op = chars[start..i].iter().collect::<String>();
+                    if !["=", "<>", "!=", "<", "<=", ">", ">="].contains(&op.as_str()) {
+                        unsupported(&op);
+                    }
+                }
+                '(' => {
+                    depth += 1;
+                    i += 1;
+                }
+                ')' => {
+                    depth -= 1;
+                    if depth < 0 {
+                        unsupported(")");
+                    }
+                    i += 1;
+                }
+                ',' => i += 1,
+                other => unsupported(&other.to_string()),
+            }
+            token_count += 1;
+        }
+
+        if token_count == 0 || depth != 0 {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a malformed `@check` expression `{expr}`.");
+        }
+    }
+
+    /// Ensure that any `FieldDefinition` with a `@search` directive is a `Charfield`.
+    pub fn search_field_is_charfield(f: &FieldDefinition, obj_name: &str) {
+        let name = f.name.to_string();
+        let has_search_directive = f
+            .directives
+            .iter()
+            .any(|d| d.node.name.to_string() == "search");
+        let field_type = f.ty.node.to_string();
+        if has_search_directive && field_type.replace('!', "") != "Charfield" {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot contain a `@search` directive, as it is not a `Charfield`. Found type `{field_type}`.");
+        }
+    }
+
     /// Ensure that a given `TypeDefiniton` does not contain more than `MAX_FOREIGN_KEY_LIST_FIELDS` many-to-many relationships.
     pub fn verify_m2m_relationship_count(obj_name: &str, m2m_field_count: usize) {
         if m2m_field_count > MAX_FOREIGN_KEY_LIST_FIELDS {
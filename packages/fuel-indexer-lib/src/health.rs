@@ -0,0 +1,144 @@
+//! Health of the Fuel nodes from which indexers fetch blocks.
+
+use lazy_static::lazy_static;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// Weight of the most recent response time in a node's average latency.
+const LATENCY_SAMPLE_WEIGHT: f64 = 0.2;
+
+lazy_static! {
+    /// Health of the Fuel nodes used by the indexers of this process.
+    pub static ref FUEL_NODE_HEALTH: FuelNodeHealth = FuelNodeHealth::default();
+}
+
+/// Health of a Fuel node, as observed by the indexers that fetch blocks from it.
+#[derive(Debug, Default, Clone)]
+pub struct EndpointHealth {
+    /// Moving average of the node's response time.
+    pub latency: Option<Duration>,
+
+    /// Number of failed requests since the node's last successful request.
+    pub consecutive_errors: u32,
+
+    /// Total number of failed requests.
+    pub errors: u64,
+
+    /// Latest block height reported by the node.
+    pub head_height: Option<u32>,
+
+    /// When the node's last failed request happened.
+    pub last_error: Option<Instant>,
+}
+
+impl EndpointHealth {
+    /// Record a successful request, which took `latency`, to a node whose latest
+    /// block is at `head_height`.
+    pub fn record_success(&mut self, latency: Duration, head_height: u32) {
+        self.latency = Some(match self.latency {
+            Some(average) => {
+                average.mul_f64(1.0 - LATENCY_SAMPLE_WEIGHT)
+                    + latency.mul_f64(LATENCY_SAMPLE_WEIGHT)
+            }
+            None => latency,
+        });
+        self.consecutive_errors = 0;
+        self.head_height = Some(head_height);
+    }
+
+    /// Record a failed request.
+    pub fn record_error(&mut self) {
+        self.consecutive_errors += 1;
+        self.errors += 1;
+        self.last_error = Some(Instant::now());
+    }
+
+    /// Whether the node failed within the last `cooldown`, and hasn't succeeded since.
+    pub fn is_failing(&self, cooldown: Duration) -> bool {
+        self.consecutive_errors > 0
+            && self.last_error.is_some_and(|at| at.elapsed() < cooldown)
+    }
+}
+
+/// Health of a set of Fuel nodes, keyed by address.
+///
+/// Clones share the same state, so that the health observed by one indexer is
+/// used by every other indexer fetching from the same node, and can be reported
+/// by the web API.
+#[derive(Debug, Default, Clone)]
+pub struct FuelNodeHealth {
+    endpoints: Arc<Mutex<HashMap<String, Arc<Mutex<EndpointHealth>>>>>,
+}
+
+impl FuelNodeHealth {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Arc<Mutex<EndpointHealth>>>> {
+        self.endpoints
+            .lock()
+            .expect("Poisoned Fuel node health lock.")
+    }
+
+    /// Shared health of the node at `addr`, which is tracked from here on.
+    pub fn endpoint(&self, addr: &str) -> Arc<Mutex<EndpointHealth>> {
+        self.lock().entry(addr.to_string()).or_default().clone()
+    }
+
+    /// Health of the node at `addr`, if it is tracked.
+    pub fn get(&self, addr: &str) -> Option<EndpointHealth> {
+        self.lock().get(addr).map(|health| {
+            health
+                .lock()
+                .expect("Poisoned endpoint health lock.")
+                .clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_health_is_shared_by_address() {
+        let health = FuelNodeHealth::default();
+        assert!(health.get("localhost:4000").is_none());
+
+        health
+            .endpoint("localhost:4000")
+            .lock()
+            .unwrap()
+            .record_error();
+        health
+            .clone()
+            .endpoint("localhost:4000")
+            .lock()
+            .unwrap()
+            .record_error();
+
+        let endpoint = health.get("localhost:4000").unwrap();
+        assert_eq!(endpoint.consecutive_errors, 2);
+        assert!(endpoint.is_failing(Duration::from_secs(60)));
+        assert!(!endpoint.is_failing(Duration::ZERO));
+        assert!(health.get("localhost:4001").is_none());
+    }
+
+    #[test]
+    fn test_success_resets_consecutive_errors() {
+        let mut endpoint = EndpointHealth::default();
+        endpoint.record_error();
+        endpoint.record_success(Duration::from_millis(100), 10);
+        endpoint.record_success(Duration::from_millis(200), 11);
+
+        assert_eq!(endpoint.consecutive_errors, 0);
+        assert_eq!(endpoint.errors, 1);
+        assert_eq!(endpoint.head_height, Some(11));
+        // The latency is a moving average, weighted towards earlier requests.
+        let latency = endpoint.latency.unwrap();
+        assert!(
+            latency > Duration::from_millis(100) && latency < Duration::from_millis(150)
+        );
+        assert!(!endpoint.is_failing(Duration::from_secs(60)));
+    }
+}
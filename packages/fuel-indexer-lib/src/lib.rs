@@ -7,6 +7,7 @@ pub mod config;
 pub mod constants;
 pub mod defaults;
 pub mod graphql;
+pub mod health;
 pub mod manifest;
 pub mod utils;
 
@@ -46,6 +47,8 @@ pub enum WasmIndexerError {
     KillSwitch,
     DatabaseError,
     MissingBlocksError,
+    ConstraintViolation,
+    InvalidConfig,
     GeneralError,
 }
 
@@ -60,6 +63,8 @@ impl From<u32> for WasmIndexerError {
             5 => Self::KillSwitch,
             6 => Self::DatabaseError,
             7 => Self::MissingBlocksError,
+            8 => Self::ConstraintViolation,
+            9 => Self::InvalidConfig,
             _ => Self::GeneralError,
         }
     }
@@ -95,6 +100,12 @@ impl std::fmt::Display for WasmIndexerError {
             Self::MissingBlocksError => {
                 write!(f, "Some blocks are missing")
             }
+            Self::ConstraintViolation => {
+                write!(f, "Object violates a column constraint")
+            }
+            Self::InvalidConfig => {
+                write!(f, "Indexer config does not match the expected type")
+            }
             Self::GeneralError => write!(f, "Some unspecified WASM error occurred."),
         }
     }
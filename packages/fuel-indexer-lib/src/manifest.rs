@@ -2,6 +2,7 @@ use crate::{graphql::GraphQLSchema, ExecutionSource};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
@@ -21,6 +22,10 @@ pub enum ManifestError {
     NativeModuleError,
     #[error("File IO error: {0} {1:?}.")]
     FileError(String, #[source] std::io::Error),
+    #[error("Invalid config: {0}.")]
+    InvalidConfig(String),
+    #[error("Invalid config schema: {0}.")]
+    InvalidConfigSchema(String),
 }
 
 /// Specifies which type of module is used to create this indexer.
@@ -63,6 +68,21 @@ impl AsRef<Path> for Module {
     }
 }
 
+/// Resource limits requested by an indexer for its WASM execution.
+///
+/// Each limit is capped by the corresponding limit in the indexer service's configuration.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum number of 64KiB pages of linear memory that the WASM module can use.
+    pub max_memory_pages: Option<u32>,
+
+    /// Number of WASM opcodes after which `handle_events` will stop execution.
+    pub metering_points: Option<u64>,
+
+    /// Number of seconds after which a single call to `handle_events` will be stopped.
+    pub handler_timeout: Option<u64>,
+}
+
 /// Represents the indexer manifest file.
 ///
 /// This manifest file is a simple YAML file that is read and passed
@@ -92,6 +112,7 @@ pub struct Manifest {
     module: Module,
 
     /// Whether or not to record metrics for this indexer.
+    #[serde(alias = "report_metrics")]
     metrics: Option<bool>,
 
     /// Set of contract IDs this indexer should subscribe to.
@@ -110,6 +131,15 @@ pub struct Manifest {
     /// When set to true, the indexer will resume from the block height at which it last stopped.
     #[serde(default)]
     resumable: Option<bool>,
+
+    /// Resource limits for this indexer's WASM execution.
+    #[serde(default)]
+    limits: Option<ResourceLimits>,
+
+    /// Deployment-specific configuration values, read by the indexer's handlers
+    /// at runtime.
+    #[serde(default)]
+    config: Option<serde_yaml::Value>,
 }
 
 impl Manifest {
@@ -182,6 +212,11 @@ impl Manifest {
         Ok(())
     }
 
+    /// Set the identifier for this indexer.
+    pub fn set_identifier(&mut self, identifier: String) {
+        self.identifier = identifier;
+    }
+
     /// Set the start block for this indexer.
     pub fn set_start_block(&mut self, block: u32) {
         self.start_block = Some(block);
@@ -246,6 +281,252 @@ impl Manifest {
     pub fn resumable(&self) -> Option<bool> {
         self.resumable
     }
+
+    pub fn metrics(&self) -> Option<bool> {
+        self.metrics
+    }
+
+    pub fn limits(&self) -> Option<&ResourceLimits> {
+        self.limits.as_ref()
+    }
+
+    pub fn config(&self) -> Option<&serde_yaml::Value> {
+        self.config.as_ref()
+    }
+
+    /// Check the `config` section against the field types declared by the
+    /// indexer's module.
+    ///
+    /// Every declared field must be present unless its type is nullable, and the
+    /// config can't have any undeclared fields.
+    pub fn validate_config(&self, schema: &ConfigSchema) -> ManifestResult<()> {
+        let empty = serde_yaml::Mapping::new();
+        let config = match &self.config {
+            None | Some(serde_yaml::Value::Null) => &empty,
+            Some(serde_yaml::Value::Mapping(config)) => config,
+            Some(_) => {
+                return Err(ManifestError::InvalidConfig(
+                    "`config` must be a mapping of field names to values".to_string(),
+                ))
+            }
+        };
+
+        for (key, _) in config.iter() {
+            match key.as_str() {
+                Some(field) if schema.0.contains_key(field) => {}
+                Some(field) => {
+                    return Err(ManifestError::InvalidConfig(format!(
+                        "Field '{field}' is not declared by the indexer"
+                    )))
+                }
+                None => {
+                    return Err(ManifestError::InvalidConfig(
+                        "Field names must be strings".to_string(),
+                    ))
+                }
+            }
+        }
+
+        for (field, typ) in &schema.0 {
+            let config_type = ConfigType::from_str(typ).map_err(|e| {
+                ManifestError::InvalidConfigSchema(format!("Field '{field}': {e}"))
+            })?;
+
+            match config.get(&serde_yaml::Value::String(field.clone())) {
+                None if !config_type.nullable => {
+                    return Err(ManifestError::InvalidConfig(format!(
+                        "Field '{field}' of type '{typ}' is missing"
+                    )))
+                }
+                Some(value) if !config_type.matches(value) => {
+                    return Err(ManifestError::InvalidConfig(format!(
+                        "Field '{field}' is not a value of type '{typ}'"
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Name of the custom section of a WASM module in which the `#[indexer]` macro
+/// embeds the indexer's `ConfigSchema`.
+pub const CONFIG_SCHEMA_SECTION: &str = "fuel_indexer_config_schema";
+
+/// Types of the fields of an indexer's config, as declared by the `#[config]`
+/// struct of its module.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigSchema(BTreeMap<String, String>);
+
+impl ConfigSchema {
+    /// Read the config schema embedded in a WASM module, if the module declares
+    /// a config.
+    pub fn from_wasm(bytes: &[u8]) -> ManifestResult<Option<Self>> {
+        wasm_custom_section(bytes, CONFIG_SCHEMA_SECTION)
+            .map(|section| {
+                std::str::from_utf8(section)
+                    .map_err(|e| ManifestError::InvalidConfigSchema(e.to_string()))?
+                    .parse()
+            })
+            .transpose()
+    }
+
+    /// Declare that the field `name` of the config has the given type, written in
+    /// GraphQL notation, e.g., `UInt8!` or `[Address!]`.
+    pub fn insert(&mut self, name: impl Into<String>, typ: impl Into<String>) {
+        self.0.insert(name.into(), typ.into());
+    }
+}
+
+impl FromStr for ConfigSchema {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+            .map_err(|e| ManifestError::InvalidConfigSchema(e.to_string()))
+    }
+}
+
+impl std::fmt::Display for ConfigSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| std::fmt::Error)?
+        )
+    }
+}
+
+/// Return the contents of the custom section with the given name in a WASM
+/// module, or `None` if the module has no such section.
+fn wasm_custom_section<'a>(bytes: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    // Read an unsigned LEB128 integer, and advance past it.
+    fn read_u32(bytes: &mut &[u8]) -> Option<usize> {
+        let mut result = 0;
+        for shift in (0..35).step_by(7) {
+            let (&byte, rest) = bytes.split_first()?;
+            *bytes = rest;
+            result |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    // Skip the magic number and the version.
+    let mut bytes = bytes.strip_prefix(b"\0asm")?.get(4..)?;
+    while let Some((&id, rest)) = bytes.split_first() {
+        bytes = rest;
+        let size = read_u32(&mut bytes)?;
+        let mut section = bytes.get(..size)?;
+        bytes = &bytes[size..];
+
+        // Custom sections have an ID of 0, and start with their name.
+        if id == 0 {
+            let len = read_u32(&mut section)?;
+            if section.get(..len)? == name.as_bytes() {
+                return Some(&section[len..]);
+            }
+        }
+    }
+
+    None
+}
+
+/// Type of a field of a `ConfigSchema`, written in GraphQL notation, e.g.,
+/// `UInt8!` or `[Address!]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConfigType {
+    kind: ConfigTypeKind,
+    nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigTypeKind {
+    Scalar(ConfigScalar),
+    List(Box<ConfigType>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigScalar {
+    Boolean,
+    String,
+    Int(u32),
+    UInt(u32),
+    /// A 32-byte hex-encoded value, such as an `Address` or a `ContractId`.
+    Bytes32,
+}
+
+impl ConfigType {
+    /// Whether the given config value is a value of this type.
+    fn matches(&self, value: &serde_yaml::Value) -> bool {
+        use serde_yaml::Value;
+
+        match (&self.kind, value) {
+            (_, Value::Null) => self.nullable,
+            (ConfigTypeKind::List(inner), Value::Sequence(values)) => {
+                values.iter().all(|value| inner.matches(value))
+            }
+            (ConfigTypeKind::List(_), _) => false,
+            (ConfigTypeKind::Scalar(scalar), value) => match (scalar, value) {
+                (ConfigScalar::Boolean, Value::Bool(_)) => true,
+                (ConfigScalar::String, Value::String(_)) => true,
+                (ConfigScalar::Int(bits), Value::Number(n)) => {
+                    n.as_i64().is_some_and(|n| {
+                        *bits == 64 || (n >= -(1 << (bits - 1)) && n < 1 << (bits - 1))
+                    })
+                }
+                (ConfigScalar::UInt(bits), Value::Number(n)) => {
+                    n.as_u64().is_some_and(|n| *bits == 64 || n < 1 << bits)
+                }
+                (ConfigScalar::Bytes32, Value::String(s)) => {
+                    let s = s.strip_prefix("0x").unwrap_or(s);
+                    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+impl FromStr for ConfigType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (s, nullable) = match s.strip_suffix('!') {
+            Some(s) => (s.trim(), false),
+            None => (s, true),
+        };
+
+        let kind = match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            Some(inner) => ConfigTypeKind::List(Box::new(inner.parse()?)),
+            None => {
+                let scalar = match s {
+                    "Boolean" => ConfigScalar::Boolean,
+                    "String" => ConfigScalar::String,
+                    "Int1" => ConfigScalar::Int(8),
+                    "Int2" => ConfigScalar::Int(16),
+                    "Int4" => ConfigScalar::Int(32),
+                    "Int8" => ConfigScalar::Int(64),
+                    "UInt1" => ConfigScalar::UInt(8),
+                    "UInt2" => ConfigScalar::UInt(16),
+                    "UInt4" => ConfigScalar::UInt(32),
+                    "UInt8" => ConfigScalar::UInt(64),
+                    "Address" | "AssetId" | "ContractId" | "Bytes32" => {
+                        ConfigScalar::Bytes32
+                    }
+                    _ => return Err(format!("Unknown type '{s}'")),
+                };
+                ConfigTypeKind::Scalar(scalar)
+            }
+        };
+
+        Ok(Self { kind, nullable })
+    }
 }
 
 impl TryFrom<&str> for Manifest {
@@ -336,3 +617,119 @@ impl FromStr for ContractIds {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(config: &str) -> Manifest {
+        Manifest::try_from(
+            format!(
+                r#"
+namespace: test_namespace
+identifier: index1
+graphql_schema: schema.graphql
+module:
+  wasm: indexer.wasm
+contract_id: ~
+start_block: ~
+end_block: ~
+{config}"#
+            )
+            .as_str(),
+        )
+        .unwrap()
+    }
+
+    fn schema(schema: &str) -> ConfigSchema {
+        schema.parse().unwrap()
+    }
+
+    #[test]
+    fn test_config_schema_is_read_from_wasm_custom_section() {
+        let schema = r#"{"fee_basis_points":"UInt8!"}"#;
+
+        let mut section = vec![CONFIG_SCHEMA_SECTION.len() as u8];
+        section.extend_from_slice(CONFIG_SCHEMA_SECTION.as_bytes());
+        section.extend_from_slice(schema.as_bytes());
+
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        // An empty type section, followed by the custom section.
+        module.extend_from_slice(&[1, 1, 0, 0, section.len() as u8]);
+        module.extend_from_slice(&section);
+
+        assert_eq!(
+            ConfigSchema::from_wasm(&module).unwrap(),
+            Some(self::schema(schema))
+        );
+        assert_eq!(
+            ConfigSchema::from_wasm(b"\0asm\x01\0\0\0\x01\x01\0").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_config_matching_schema_is_valid() {
+        let manifest = manifest(
+            r#"
+config:
+  treasury: "0x39150017c9e38e5e280432d546fae345d6ce6d8fe4710162c2e3a95a6faff051"
+  fee_basis_points: 30
+  whitelist:
+    - "0000000000000000000000000000000000000000000000000000000000000000"
+"#,
+        );
+        let schema = schema(
+            r#"{
+                "treasury": "Address!",
+                "fee_basis_points": "UInt2!",
+                "whitelist": "[AssetId!]!",
+                "label": "String"
+            }"#,
+        );
+
+        assert!(manifest.validate_config(&schema).is_ok());
+    }
+
+    #[test]
+    fn test_config_not_matching_schema_is_invalid() {
+        let schema = schema(r#"{"treasury": "Address!", "fee_basis_points": "UInt2!"}"#);
+        let treasury =
+            "0x39150017c9e38e5e280432d546fae345d6ce6d8fe4710162c2e3a95a6faff051";
+
+        for config in [
+            // Missing a non-nullable field.
+            format!("config:\n  treasury: \"{treasury}\"\n"),
+            // Wrong type.
+            format!("config:\n  treasury: \"{treasury}\"\n  fee_basis_points: \"30\"\n"),
+            // Out of range.
+            format!("config:\n  treasury: \"{treasury}\"\n  fee_basis_points: 70000\n"),
+            // Not a 32-byte value.
+            "config:\n  treasury: \"0x1234\"\n  fee_basis_points: 30\n".to_string(),
+            // Undeclared field.
+            format!(
+                "config:\n  treasury: \"{treasury}\"\n  fee_basis_points: 30\n  fee: 1\n"
+            ),
+        ] {
+            let manifest = manifest(&config);
+            assert!(
+                matches!(
+                    manifest.validate_config(&schema),
+                    Err(ManifestError::InvalidConfig(_))
+                ),
+                "{config}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_schema_with_unknown_type_is_invalid() {
+        let manifest = manifest("config:\n  fee_basis_points: 30\n");
+        let schema = schema(r#"{"fee_basis_points": "Float8!"}"#);
+
+        assert!(matches!(
+            manifest.validate_config(&schema),
+            Err(ManifestError::InvalidConfigSchema(_))
+        ));
+    }
+}
@@ -9,16 +9,24 @@ use std::{
     net::{SocketAddr, ToSocketAddrs},
     path::Path,
     str::FromStr,
+    sync::OnceLock,
 };
 use tokio::time::{sleep, Duration};
 use tracing::{debug, info, warn};
-use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::{
+    filter::EnvFilter, layer::Identity, prelude::*, reload, Layer, Registry,
+};
 
 const RUST_LOG: &str = "RUST_LOG";
 const HUMAN_LOGGING: &str = "HUMAN_LOGGING";
 
 const ROOT_DIRECTORY_NAME: &str = "fuel-indexer";
 
+/// Replaces the filter of the log output, once logging has been initialized.
+type ReloadLogFilter = Box<dyn Fn(EnvFilter) -> anyhow::Result<()> + Send + Sync>;
+
+static RELOAD_LOG_FILTER: OnceLock<ReloadLogFilter> = OnceLock::new();
+
 /// Serialize a generic byte array reference.
 pub fn serialize(obj: &impl Serialize) -> Vec<u8> {
     bincode::serialize(obj).expect("Serialize failed")
@@ -208,16 +216,26 @@ pub struct FuelClientHealthResponse {
 
 /// Initialize the logging context for the indexer service.
 pub async fn init_logging(config: &IndexerConfig) -> anyhow::Result<()> {
+    init_logging_with_layer(config, Identity::new()).await
+}
+
+/// Initialize logging along with an additional subscriber layer (e.g., a span exporter).
+///
+/// The `RUST_LOG` filter only applies to log output, so `layer` should provide its own
+/// filtering if needed.
+pub async fn init_logging_with_layer<L>(
+    config: &IndexerConfig,
+    layer: L,
+) -> anyhow::Result<()>
+where
+    L: Layer<Registry> + Send + Sync + 'static,
+{
     let level = env::var_os(RUST_LOG)
         .map(|x| x.into_string().unwrap())
         .unwrap_or("info".to_string());
 
-    // We manually suppress some of the more verbose crate logging.
     if !config.verbose {
-        std::env::set_var(
-            RUST_LOG,
-            format!("{level},wasmer_compiler_cranelift=warn,regalloc=warn,cranelift_codegen=warn"),
-        );
+        std::env::set_var(RUST_LOG, log_directives(&level, config.verbose));
     }
 
     let filter = match env::var_os(RUST_LOG) {
@@ -226,6 +244,7 @@ pub async fn init_logging(config: &IndexerConfig) -> anyhow::Result<()> {
         }
         None => EnvFilter::new("info"),
     };
+    let (filter, handle) = reload::Layer::new(filter);
 
     let human_logging = env::var_os(HUMAN_LOGGING)
         .map(|s| {
@@ -234,25 +253,47 @@ pub async fn init_logging(config: &IndexerConfig) -> anyhow::Result<()> {
         })
         .unwrap_or(true);
 
-    let sub = tracing_subscriber::fmt::Subscriber::builder()
+    let fmt = tracing_subscriber::fmt::layer()
         .with_writer(std::io::stderr)
-        .with_env_filter(filter);
+        .with_level(true)
+        .with_line_number(true);
 
-    if human_logging {
-        sub.with_ansi(true)
-            .with_level(true)
-            .with_line_number(true)
-            .init();
+    let fmt = if human_logging {
+        fmt.with_ansi(true).with_filter(filter).boxed()
     } else {
-        sub.with_ansi(false)
-            .with_level(true)
-            .with_line_number(true)
-            .json()
-            .init();
+        fmt.with_ansi(false).json().with_filter(filter).boxed()
+    };
+
+    tracing_subscriber::registry().with(layer).with(fmt).init();
+
+    let _ = RELOAD_LOG_FILTER.set(Box::new(move |filter| Ok(handle.reload(filter)?)));
+
+    Ok(())
+}
+
+/// Change the level of the log output to the `log_level` of the given configuration.
+///
+/// This is a no-op if logging has not been initialized.
+pub fn set_log_level(config: &IndexerConfig) -> anyhow::Result<()> {
+    if let Some(reload_filter) = RELOAD_LOG_FILTER.get() {
+        let directives = log_directives(&config.log_level, config.verbose);
+        reload_filter(EnvFilter::try_new(directives)?)?;
     }
+
     Ok(())
 }
 
+// We manually suppress some of the more verbose crate logging.
+fn log_directives(level: &str, verbose: bool) -> String {
+    if verbose {
+        level.to_string()
+    } else {
+        format!(
+            "{level},wasmer_compiler_cranelift=warn,regalloc=warn,cranelift_codegen=warn"
+        )
+    }
+}
+
 /// Format a SQL query for logging.
 pub fn format_sql_query(s: String) -> String {
     s.replace('\n', " ")
@@ -5,7 +5,10 @@ use async_graphql_parser::types::{
 use async_graphql_parser::{Pos, Positioned};
 use async_graphql_value::Name;
 use fuel_indexer_lib::{
-    graphql::{field_id, types::IdCol, ParsedGraphQLSchema, MAX_FOREIGN_KEY_LIST_FIELDS},
+    graphql::{
+        field_default_value, field_id, types::IdCol, ParsedGraphQLSchema,
+        MAX_FOREIGN_KEY_LIST_FIELDS,
+    },
     ExecutionSource,
 };
 use fuel_indexer_types::type_id;
@@ -31,6 +34,9 @@ pub struct ImplementationDecoder {
     /// Token stream of hasher.
     hasher: proc_macro2::TokenStream,
 
+    /// Token stream of statements applying the `@default` values of omitted params.
+    defaults: proc_macro2::TokenStream,
+
     /// Token stream of struct fields.
     struct_fields: TokenStream,
 
@@ -52,6 +58,7 @@ impl Default for ImplementationDecoder {
         Self {
             parameters: quote! {},
             hasher: quote! {},
+            defaults: quote! {},
             struct_fields: quote! {},
             exec_source: ExecutionSource::Wasm,
             typdef: TypeDefinition {
@@ -79,6 +86,7 @@ impl Decoder for ImplementationDecoder {
                 let mut struct_fields = quote! {};
                 let mut parameters = quote! {};
                 let mut hasher = quote! { Sha256::new() };
+                let mut defaults = quote! {};
 
                 let obj_field_names = parsed
                     .object_field_mappings()
@@ -118,11 +126,40 @@ impl Decoder for ImplementationDecoder {
                         to_bytes_tokens(field_typ_name, &processed_type_result);
 
                     if can_derive_id(&obj_field_names, field_name) {
-                        parameters = parameters_tokens(
-                            &parameters,
-                            &field_name_ident,
-                            field_type_tokens,
-                        );
+                        // Fields with a `@default` value are optional params, which
+                        // take on their default value when omitted. Nullable fields are
+                        // wrapped in a second `Option`, so that `Some(None)` still
+                        // stores `NULL`.
+                        match field_default_value(&field.node) {
+                            Some(value) => {
+                                let default = default_value_tokens(&value);
+                                let param_type_tokens =
+                                    quote! { Option<#field_type_tokens> };
+                                parameters = parameters_tokens(
+                                    &parameters,
+                                    &field_name_ident,
+                                    &param_type_tokens,
+                                );
+                                defaults = if *nullable {
+                                    quote! {
+                                        #defaults
+                                        let #field_name_ident = #field_name_ident.unwrap_or_else(|| Some(#default));
+                                    }
+                                } else {
+                                    quote! {
+                                        #defaults
+                                        let #field_name_ident = #field_name_ident.unwrap_or_else(|| #default);
+                                    }
+                                };
+                            }
+                            None => {
+                                parameters = parameters_tokens(
+                                    &parameters,
+                                    &field_name_ident,
+                                    field_type_tokens,
+                                );
+                            }
+                        }
                         if let Some(tokens) = hasher_tokens(
                             field_typ_name,
                             field_name,
@@ -145,6 +182,7 @@ impl Decoder for ImplementationDecoder {
                 ImplementationDecoder {
                     parameters,
                     hasher,
+                    defaults,
                     struct_fields,
                     exec_source: parsed.exec_source().clone(),
                     typdef: typ.clone(),
@@ -230,6 +268,7 @@ impl From<ImplementationDecoder> for TokenStream {
         let ImplementationDecoder {
             parameters,
             hasher,
+            defaults,
             struct_fields,
             exec_source,
             typdef,
@@ -254,12 +293,12 @@ impl From<ImplementationDecoder> for TokenStream {
         let impl_get_or_create = match exec_source {
             ExecutionSource::Native => {
                 quote! {
-                    pub async fn get_or_create(self) -> Self {
-                        match Self::load(self.id.clone()).await {
-                            Some(instance) => instance,
+                    pub async fn get_or_create(self) -> IndexerResult<Self> {
+                        match Self::load(self.id.clone()).await? {
+                            Some(instance) => Ok(instance),
                             None => {
-                                self.save().await;
-                                self
+                                self.save().await?;
+                                Ok(self)
                             },
                         }
                     }
@@ -299,6 +338,7 @@ impl From<ImplementationDecoder> for TokenStream {
                     impl #ident {
                         #[allow(clippy::too_many_arguments)]
                         pub fn new(#parameters) -> Self {
+                            #defaults
                             let hashed = #hasher.chain_update(#typdef_name).finalize();
                             let id = UID::new(format!("{:x}", hashed)).expect("Bad ID.");
                             Self {
@@ -819,63 +859,52 @@ impl From<ObjectDecoder> for TokenStream {
                         ]
                     }
 
-                    async fn save_many_to_many(&self) {
+                    async fn save_many_to_many(&self) -> IndexerResult<()> {
                         unsafe {
-                            match &db {
-                                Some(d) => {
-                                    if let Some(meta) = Self::JOIN_METADATA {
-                                        let items = meta.iter().filter_map(|x| x.clone()).collect::<Vec<_>>();
-                                        let row = self.to_row();
-                                        let queries = items
-                                            .iter()
-                                            .map(|item| RawQuery::from_metadata(item, &row))
-                                            .filter(|query| !query.is_empty())
-                                            .map(|query| query.to_string())
-                                            .collect::<Vec<_>>();
-
-                                        d.lock().await.put_many_to_many_record(queries).await.expect(&format!("Entity::save_many_to_many for {} failed.", stringify!(#ident)));
-                                    }
-                                }
-                                None => {}
+                            if let (Some(d), Some(meta)) = (&db, Self::JOIN_METADATA) {
+                                let items = meta.iter().filter_map(|x| x.clone()).collect::<Vec<_>>();
+                                let row = self.to_row();
+                                let queries = items
+                                    .iter()
+                                    .map(|item| RawQuery::from_metadata(item, &row))
+                                    .filter(|query| !query.is_empty())
+                                    .collect::<Vec<_>>();
+
+                                d.lock().await.put_many_to_many_record(queries).await?;
                             }
                         }
+                        Ok(())
                     }
 
-                    async fn load(id: UID) -> Option<Self> {
+                    async fn load(id: UID) -> IndexerResult<Option<Self>> {
                         unsafe {
                             match &db {
                                 Some(d) => {
-                                    match d.lock().await.get_object(Self::TYPE_ID, id.to_string()).await {
-                                        Ok(Some(bytes)) => {
-                                            let columns: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Failed to deserialize Vec<FtColumn> for Entity::load.");
-                                            let obj = Self::from_row(columns);
-                                            Some(obj)
+                                    match d.lock().await.get_object(Self::TYPE_ID, id.to_string()).await? {
+                                        Some(bytes) => {
+                                            let columns: Vec<FtColumn> = deserialize(&bytes).map_err(IndexerError::Unknown)?;
+                                            Ok(Some(Self::from_row(columns)))
                                         },
-                                        Ok(None) => None,
-                                        Err(e) => {
-                                            panic!("Entity::load for {} failed.", stringify!(#ident))
-                                        }
+                                        None => Ok(None),
                                     }
                                 }
-                                None => None,
+                                None => Ok(None),
                             }
                         }
                     }
 
-                    async fn save(&self) {
+                    async fn save(&self) -> IndexerResult<()> {
                         unsafe {
-                            match &db {
-                                Some(d) => {
-                                    self.save_many_to_many().await;
-                                    d.lock().await.put_object(
-                                        Self::TYPE_ID,
-                                        self.to_row(),
-                                        serialize(&self.to_row())
-                                    ).await.expect(&format!("Entity::save for {} failed.", stringify!(#ident)));
-                                }
-                                None => {},
+                            if let Some(d) = &db {
+                                self.save_many_to_many().await?;
+                                d.lock().await.put_object(
+                                    Self::TYPE_ID,
+                                    self.to_row(),
+                                    serialize(&self.to_row())
+                                ).await?;
                             }
                         }
+                        Ok(())
                     }
                 }
             },
@@ -1,19 +1,25 @@
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
 use async_graphql_parser::types::{BaseType, FieldDefinition, Type as AsyncGraphQLType};
-use async_graphql_value::Name;
-use fuel_abi_types::abi::program::{
-    ABIFunction, LoggedType, ProgramABI, TypeDeclaration,
-};
+use async_graphql_value::{ConstValue, Name};
+use fuel_abi_types::abi::program::{ProgramABI, TypeApplication, TypeDeclaration};
 use fuel_indexer_lib::{
     constants::*,
     graphql::{list_field_type_name, types::IdCol, ParsedGraphQLSchema},
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
+use fuels::types::{
+    bech32::{Bech32Address, Bech32ContractId},
+    param_types::ParamType,
+    Address, ContractId,
+};
 use fuels_code_gen::utils::Source;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{format_ident, quote};
-use syn::{GenericArgument, Ident, PathArguments, Type, TypePath};
+use syn::{GenericArgument, Ident, LitStr, PathArguments, Type};
+
+use crate::parse::{HandlerConfig, HandlerStatus};
 
 /// Provides a TokenStream to be used for unwrapping `Option`s for external types.
 ///
@@ -90,10 +96,31 @@ pub fn is_unit_type(typ: &TypeDeclaration) -> bool {
         && type_field_chars.next().is_some_and(|c| c == ')')
 }
 
+/// Whether a `TypeDeclaration` is an array type
+pub fn is_array_type(typ: &TypeDeclaration) -> bool {
+    typ.type_field.starts_with("[_;")
+}
+
+/// Whether a `TypeDeclaration` has generic type parameters
+pub fn is_generic_type(typ: &TypeDeclaration) -> bool {
+    typ.type_parameters.is_some()
+}
+
+/// Whether a `TypeDeclaration` is a type parameter of a generic type (e.g., `generic T`)
+pub fn is_type_parameter(typ: &TypeDeclaration) -> bool {
+    typ.type_field.starts_with("generic ")
+}
+
 /// Whether the TypeDeclaration should be used to build struct fields and decoders
+///
+/// Tuples, arrays, and generic types can only be decoded once they are applied
+/// to concrete types, and are handled by `monomorphize`.
 pub fn is_non_decodable_type(typ: &TypeDeclaration) -> bool {
     is_tuple_type(typ)
         || is_unit_type(typ)
+        || is_array_type(typ)
+        || is_generic_type(typ)
+        || is_type_parameter(typ)
         || IGNORED_GENERIC_METADATA.contains(typ.type_field.as_str())
 }
 
@@ -101,36 +128,12 @@ pub fn is_non_decodable_type(typ: &TypeDeclaration) -> bool {
 ///
 /// These idents are used as fields for the `Decoder` struct.
 fn decoded_ident(typ: &TypeDeclaration) -> Ident {
-    let name = {
-        let name = derive_type_name(typ);
-        if typ.components.is_some() {
-            name
-        } else if name.starts_with("Option") {
-            typ.type_field.replace(['<', '>'], "_")
-        } else {
-            typ.type_field.replace(['[', ']'], "_")
-        }
-    };
+    let name = derive_type_name(typ)
+        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        .trim_matches('_')
+        .to_ascii_lowercase();
 
-    if is_generic_type(typ) {
-        let gt = GenericType::from(name.as_str());
-        match gt {
-            GenericType::Vec => {
-                let name = name.replace(['<', '>'], "_").to_ascii_lowercase();
-                format_ident! { "{}decoded", name }
-            }
-            GenericType::Option => {
-                let name = name.replace(['<', '>'], "_").to_ascii_lowercase();
-                format_ident! { "{}decoded", name }
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Could not derive decoded ident for generic type: {:?}.",
-                name
-            ),
-        }
-    } else {
-        format_ident! { "{}_decoded", name.to_ascii_lowercase() }
-    }
+    format_ident! { "{}_decoded", name }
 }
 
 /// Given a `TypeDeclaration`, return name of the base of its typed path.
@@ -160,12 +163,6 @@ pub fn is_rust_primitive(ty: &proc_macro2::TokenStream) -> bool {
     RUST_PRIMITIVES.contains(ident_str.as_str())
 }
 
-/// Whether or not the given tokens are a generic type
-pub fn is_generic_type(typ: &TypeDeclaration) -> bool {
-    let gt = GenericType::from(typ);
-    matches!(gt, GenericType::Vec | GenericType::Option)
-}
-
 /// Given a `TokenStream` representing this `TypeDeclaration`'s fully typed path,
 /// return the associated `match` arm for decoding this type in the `Decoder`.
 pub fn decode_snippet(
@@ -179,7 +176,7 @@ pub fn decode_snippet(
         quote! {
             #ty_id => {
                 let obj: #type_tokens = bincode::deserialize(&data).expect("Bad bincode.");
-                self.#name.push(obj);
+                self.#name.push((self.current_origin.clone(), obj));
             }
         }
     } else if is_rust_primitive(type_tokens) {
@@ -188,39 +185,20 @@ pub fn decode_snippet(
                 Logger::warn("Skipping primitive decoder.");
             }
         }
-    } else if is_generic_type(typ) {
-        let gt = GenericType::from(typ);
-        match gt {
-            GenericType::Vec => {
-                // https://github.com/FuelLabs/fuel-indexer/issues/503
-                quote! {
-                    #ty_id => {
-                        Logger::warn("Skipping unsupported vec decoder.");
-                    }
-                }
-            }
-            GenericType::Option => {
-                let (inner, typ) = inner_typedef(typ);
-                let inner = format_ident! { "{}", inner };
-                quote! {
-                    #ty_id => {
-                        let decoded = ABIDecoder::decode_single(&#typ::<#inner>::param_type(), &data).expect("Failed decoding.");
-                        let obj = #typ::<#inner>::from_token(decoded).expect("Failed detokenizing.");
-                        self.#name.push(obj);
-                    }
-                }
+    } else if is_monomorphized_name(&typ.type_field) {
+        quote! {
+            #ty_id => {
+                let decoded = ABIDecoder::decode_single(&<#type_tokens as Parameterize>::param_type(), &data).expect("Failed decoding.");
+                let obj = <#type_tokens as Tokenizable>::from_token(decoded).expect("Failed detokenizing.");
+                self.#name.push((self.current_origin.clone(), obj));
             }
-            _ => proc_macro_error::abort_call_site!(
-                "Decoder snippet is unsupported for generic type: {:?}.",
-                gt
-            ),
         }
     } else {
         quote! {
             #ty_id => {
                 let decoded = ABIDecoder::decode_single(&#type_tokens::param_type(), &data).expect("Failed decoding.");
                 let obj = #type_tokens::from_token(decoded).expect("Failed detokenizing.");
-                self.#name.push(obj);
+                self.#name.push((self.current_origin.clone(), obj));
             }
         }
     }
@@ -256,7 +234,7 @@ impl Codegen for TypeDeclaration {
                 "bool" => quote! { bool },
                 "Burn" => quote! { Burn },
                 "Call" => quote! { Call },
-                "generic T" => quote! {},
+                o if o.starts_with("generic ") => quote! {},
                 "Identity" => quote! { Identity },
                 "Log" => quote! { Log },
                 "LogData" => quote! { LogData },
@@ -684,6 +662,23 @@ pub fn parameters_tokens(
     quote! { #parameters #ident: #typ_tokens, }
 }
 
+/// Get tokens for the `@default` value of a field, as a value of the field's type.
+pub fn default_value_tokens(value: &ConstValue) -> TokenStream {
+    match value {
+        ConstValue::String(s) => quote! { #s.to_string() },
+        ConstValue::Enum(name) => {
+            let name = name.to_string();
+            quote! { #name.to_string() }
+        }
+        ConstValue::Boolean(b) => quote! { #b },
+        ConstValue::Number(n) => n
+            .to_string()
+            .parse::<TokenStream>()
+            .unwrap_or_else(|_| panic!("Invalid default value `{n}`.")),
+        _ => panic!("Unsupported default value `{value}`."),
+    }
+}
+
 /// Get tokens for a field decoder.
 pub fn field_decoder_tokens(
     field_name: &Ident,
@@ -753,7 +748,7 @@ pub fn can_derive_id(field_set: &HashSet<String>, field_name: &str) -> bool {
 /// Path separators are not allowed to be used as part of an identifier, so this
 /// function removes the qualifying path while keeping the type keyword.
 pub fn strip_callpath_from_type_field(mut typ: TypeDeclaration) -> TypeDeclaration {
-    if is_non_decodable_type(&typ) {
+    if is_non_decodable_type(&typ) && !is_generic_type(&typ) {
         return typ;
     }
 
@@ -771,359 +766,551 @@ pub fn strip_callpath_from_type_field(mut typ: TypeDeclaration) -> TypeDeclarati
     typ
 }
 
-/// Simply represents a value for a generic type.
-#[derive(Debug)]
-pub enum GenericType {
-    /// `Vec<T>`
-    Vec,
+/// A concrete instance of a tuple, array or generic ABI type, with all of its type
+/// parameters resolved (e.g., `Vec<Ping>`, `Result<Ping,UserError>`, or `(u64,bool)`).
+#[derive(Debug, Clone)]
+pub struct MonomorphizedType {
+    /// Name of the type, as it would be written in a handler function argument,
+    /// without whitespace or call paths.
+    pub name: String,
 
-    /// `Option<T>`
-    Option,
-    #[allow(unused)]
-    Other,
-}
+    /// Tokens of the fully typed path of the type.
+    pub tokens: TokenStream,
 
-impl From<&TypeDeclaration> for GenericType {
-    fn from(t: &TypeDeclaration) -> Self {
-        Self::from(derive_type_name(t))
-    }
+    /// Type ID derived from the name of the type.
+    pub type_id: usize,
+
+    /// Whether the type nests heap types (e.g., `Vec<Vec<u8>>`), which can't be decoded.
+    pub has_nested_heap_types: bool,
 }
 
-impl From<String> for GenericType {
-    fn from(s: String) -> Self {
-        if s.starts_with("Vec") {
-            GenericType::Vec
-        } else if s.starts_with("Option") {
-            GenericType::Option
-        } else {
-            GenericType::Other
+impl MonomorphizedType {
+    /// Return the `TypeDeclaration` under which this type is decoded.
+    pub fn type_declaration(&self) -> TypeDeclaration {
+        TypeDeclaration {
+            type_id: self.type_id,
+            type_field: self.name.clone(),
+            components: None,
+            type_parameters: None,
         }
     }
 }
 
-impl From<GenericType> for &str {
-    fn from(t: GenericType) -> Self {
-        match t {
-            GenericType::Vec => "Vec",
-            GenericType::Option => "Option",
-            GenericType::Other => unimplemented!("Generic type not implemented."),
-        }
-    }
+/// Whether a type name is the name of a `MonomorphizedType`.
+fn is_monomorphized_name(name: &str) -> bool {
+    name.contains(['<', '(', '['])
 }
 
-impl From<&str> for GenericType {
-    fn from(s: &str) -> Self {
-        if s.starts_with("Vec") {
-            GenericType::Vec
-        } else if s.starts_with("Option") {
-            GenericType::Option
-        } else {
-            GenericType::Other
-        }
-    }
+/// Whether the given `TypeApplication` has to be monomorphized in order to be decoded.
+pub fn is_monomorphized_application(
+    application: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> bool {
+    abi_types.get(&application.type_id).is_some_and(|typ| {
+        is_generic_type(typ) || is_tuple_type(typ) || is_array_type(typ)
+    })
+}
+
+/// Monomorphize the given `TypeApplication`, resolving the type arguments of
+/// generic types recursively.
+///
+/// Returns `None` if the application contains a type that can't be represented
+/// in an indexer, such as a type parameter that is never resolved.
+pub fn monomorphize(
+    application: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> Option<MonomorphizedType> {
+    let (name, tokens) = resolve_type_application(application, abi_types)?;
+    let has_nested_heap_types =
+        ParamType::try_from_type_application(application, abi_types)
+            .map(|p| p.contains_nested_heap_types())
+            .unwrap_or(true);
+
+    Some(MonomorphizedType {
+        type_id: type_id(FUEL_TYPES_NAMESPACE, &name) as usize,
+        name,
+        tokens,
+        has_nested_heap_types,
+    })
 }
 
-impl From<GenericType> for TokenStream {
-    fn from(t: GenericType) -> Self {
-        match t {
-            GenericType::Vec => quote! { Vec },
-            GenericType::Option => quote! { Option },
-            GenericType::Other => unimplemented!("Generic type not implemented."),
+/// Return the type ID under which values of the given `TypeApplication` are decoded.
+pub fn application_type_id(
+    application: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> usize {
+    if is_monomorphized_application(application, abi_types) {
+        if let Some(typ) = monomorphize(application, abi_types) {
+            return typ.type_id;
         }
     }
+    application.type_id
 }
 
-/// Same as `derive_generic_inner_typedefs` but specifically for log types.
-///
-/// Where as `derive_generic_inner_typedefs` can return multiple inner types, this
-/// only returns the single inner type associated with this log specific logged type
-pub fn derive_log_generic_inner_typedefs<'a>(
-    typ: &'a LoggedType,
-    abi: &ProgramABI,
-    abi_types: &'a HashMap<usize, TypeDeclaration>,
-) -> &'a TypeDeclaration {
-    let result =
-        abi.logged_types
-            .iter()
-            .flatten()
-            .filter_map(|log| {
-                if log.log_id == typ.log_id && log.application.type_arguments.is_some() {
-                    let args = log.application.type_arguments.as_ref().unwrap();
-                    let inner = args.first().expect("No type args found.");
-                    return Some(abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                        panic!("Inner type not in ABI: {:?}", inner)
-                    }));
+/// Return the name and the tokens of the fully typed path of a `TypeApplication`.
+fn resolve_type_application(
+    application: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> Option<(String, TokenStream)> {
+    let typ = abi_types.get(&application.type_id)?;
+    let field = typ.type_field.as_str();
+
+    match field {
+        "u8" | "u16" | "u32" | "u64" | "bool" => {
+            let ident = format_ident! { "{}", field };
+            Some((field.to_string(), quote! { #ident }))
+        }
+        "()" => Some((field.to_string(), quote! { () })),
+        "b256" => Some(("Bits256".to_string(), quote! { Bits256 })),
+        "raw untyped slice" => {
+            Some(("RawSlice".to_string(), quote! { ::fuels::types::RawSlice }))
+        }
+        _ if field.starts_with("str[") => {
+            let len = field[4..field.len() - 1].parse::<usize>().ok()?;
+            let lit = Literal::usize_unsuffixed(len);
+            Some((
+                format!("SizedAsciiString<{len}>"),
+                quote! { SizedAsciiString<#lit> },
+            ))
+        }
+        _ if is_tuple_type(typ) => {
+            let (names, tokens): (Vec<_>, Vec<_>) = typ
+                .components
+                .as_ref()?
+                .iter()
+                .map(|c| resolve_type_application(c, abi_types))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            Some((format!("({})", names.join(",")), quote! { (#(#tokens,)*) }))
+        }
+        _ if is_array_type(typ) => {
+            let len = field[4..field.len() - 1].trim().parse::<usize>().ok()?;
+            let lit = Literal::usize_unsuffixed(len);
+            let inner = typ.components.as_ref()?.first()?;
+            let (name, tokens) = resolve_type_application(inner, abi_types)?;
+            Some((format!("[{name};{len}]"), quote! { [#tokens; #lit] }))
+        }
+        _ if field.starts_with("struct ") || field.starts_with("enum ") => {
+            let name = derive_type_name(typ);
+            let path = match name.as_str() {
+                "Vec" => quote! { ::std::vec::Vec },
+                "Option" => quote! { ::core::option::Option },
+                "Result" => quote! { ::core::result::Result },
+                "String" => quote! { ::std::string::String },
+                "Address" | "B512" | "Bytes" | "ContractId" | "EvmAddress"
+                | "Identity" | "U256" => {
+                    let ident = format_ident! { "{}", name };
+                    quote! { ::fuels::types::#ident }
                 }
-                None
-            })
-            .collect::<Vec<_>>();
+                "U128" => quote! { u128 },
+                _ => {
+                    let ident = format_ident! { "{}", name };
+                    quote! { #ident }
+                }
+            };
+
+            let params = typ.type_parameters.as_ref().map(Vec::len).unwrap_or(0);
+            if params == 0 {
+                return Some((name, path));
+            }
+
+            let args = application.type_arguments.as_ref()?;
+            if args.len() != params {
+                return None;
+            }
 
-    result.first().expect("No inner type found.")
+            let (names, tokens): (Vec<_>, Vec<_>) = args
+                .iter()
+                .map(|a| resolve_type_application(a, abi_types))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+
+            Some((
+                format!("{name}<{}>", names.join(",")),
+                quote! { #path<#(#tokens),*> },
+            ))
+        }
+        _ => None,
+    }
 }
 
-/// Derive the inner ident names for collections types.
+/// Return the name of a handler function argument type, in the same format as the
+/// names of ABI types (e.g., `Vec<Ping>`, `(u64,bool)` or `[u8;32]`).
 ///
-/// Given a `GenericType`, and ABI JSON metadata, derive the inner `TypeDefinition`s associated with the given
-/// generic `TypeDefinition`.
-///
-/// So this function will parse all function inputs/outputs and log types, find all generics (e.g., `Vec<T>`, `Option<U>`),
-/// and return the inner `TypeDefinition`s associated with those generics (e.g., `T` and `U`)
-pub fn derive_generic_inner_typedefs<'a>(
-    typ: &'a TypeDeclaration,
-    funcs: &[ABIFunction],
-    log_types: &[LoggedType],
-    abi_types: &'a HashMap<usize, TypeDeclaration>,
-) -> Vec<&'a TypeDeclaration> {
-    let name = typ.type_field.split(' ').last().unwrap();
-    let t = GenericType::from(name);
-
-    // Per Ahmed from fuels-rs:
-    //
-    // "So if you wish to see all the various Ts used with SomeStruct<T> (in this case Vec<T>)
-    // you have no choice but to go through all functions and find inputs/outputs that reference
-    // SomeStruct<T> and see what the typeArguments are. Those will replace the typeParameters inside
-    // the type declaration."
-    match t {
-        GenericType::Option | GenericType::Vec => {
-            let mut typs = funcs
-                .iter()
-                .flat_map(|func| {
-                    func.inputs
+/// Returns `None` if the argument type can't be an ABI type.
+pub fn handler_arg_type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => {
+            let base = p.path.segments.last()?;
+            let name = base.ident.to_string();
+
+            match &base.arguments {
+                PathArguments::None => Some(name),
+                PathArguments::AngleBracketed(inner) => {
+                    let args = inner
+                        .args
                         .iter()
-                        .filter_map(|i| {
-                            if i.type_id == typ.type_id && i.type_arguments.is_some() {
-                                let args = i.type_arguments.as_ref().unwrap();
-                                let inner = args.first().expect("No type args found.");
-                                return Some(
-                                    abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                                        panic!("Inner type not in ABI: {:?}", inner)
-                                    }),
-                                );
+                        .map(|arg| match arg {
+                            GenericArgument::Type(t) => handler_arg_type_name(t),
+                            GenericArgument::Const(c) => {
+                                Some(quote! { #c }.to_string().replace(' ', ""))
                             }
-                            None
+                            _ => None,
                         })
-                        .collect::<Vec<_>>()
-                })
-                .collect::<Vec<_>>();
-
-            let mut output_typs = funcs
-                .iter()
-                .flat_map(|func| {
-                    if func.output.type_id == typ.type_id
-                        && func.output.type_arguments.is_some()
-                    {
-                        let args = func.output.type_arguments.as_ref().unwrap();
-                        let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
-                    }
-                    None
-                })
-                .collect::<Vec<_>>();
-
-            // Parse these as well because we will need to add them to our
-            // mapping of type IDs and `TypeDeclaration`s so we can use them when
-            // we parse log types.
-            let mut log_types = log_types
+                        .collect::<Option<Vec<_>>>()?;
+                    Some(format!("{name}<{}>", args.join(",")))
+                }
+                PathArguments::Parenthesized(_) => None,
+            }
+        }
+        Type::Tuple(t) => {
+            let elems = t
+                .elems
                 .iter()
-                .filter_map(|log| {
-                    if log.application.type_id == typ.type_id
-                        && log.application.type_arguments.is_some()
-                    {
-                        let args = log.application.type_arguments.as_ref().unwrap();
-                        let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
-                    }
-                    None
-                })
-                .collect::<Vec<_>>();
-
-            typs.append(&mut output_typs);
-            typs.append(&mut log_types);
-            typs
+                .map(handler_arg_type_name)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({})", elems.join(",")))
+        }
+        Type::Array(a) => {
+            let elem = handler_arg_type_name(&a.elem)?;
+            let len = &a.len;
+            let len = quote! { #len }.to_string().replace(' ', "");
+            Some(format!("[{elem};{len}]"))
         }
-        _ => proc_macro_error::abort_call_site!(
-            "Can't derive idents for unsupported generic type: {:?}",
-            t
+        Type::Paren(p) => handler_arg_type_name(&p.elem),
+        Type::Group(g) => handler_arg_type_name(&g.elem),
+        _ => None,
+    }
+}
+
+/// Parse a contract ID or address given as either a bech32 or a hex string.
+fn parse_id<B, H>(lit: &LitStr) -> [u8; 32]
+where
+    B: FromStr,
+    H: FromStr + From<B>,
+    [u8; 32]: From<H>,
+{
+    let value = lit.value();
+    let id = B::from_str(&value)
+        .map(H::from)
+        .or_else(|_| H::from_str(&value));
+
+    match id {
+        Ok(id) => <[u8; 32]>::from(id),
+        Err(_) => proc_macro_error::abort!(
+            lit.span(),
+            "'{}' is neither a bech32 nor a hex encoded ID.",
+            value
         ),
     }
 }
 
-/// Extract the full type ident from a given path.
-pub fn typed_path_name(p: &TypePath) -> String {
-    let base = p
-        .path
-        .segments
-        .last()
-        .expect("Could not get last path segment.");
+/// Return the expression that checks whether a decoded value's `DecodedOrigin`
+/// (bound to `origin`) satisfies the filters of a `#[handler(...)]` attribute.
+///
+/// Values decoded outside of a transaction (e.g., `BlockData`) always pass.
+pub fn handler_filter(config: &HandlerConfig) -> Option<TokenStream> {
+    let mut checks = Vec::new();
+
+    if let Some(contract) = &config.contract {
+        let bytes = parse_id::<Bech32ContractId, ContractId>(contract);
+        checks.push(quote! {
+            origin.contract_id == Some(ContractId::from([#(#bytes),*]))
+        });
+    }
 
-    let base_name = base.ident.to_string();
+    if let Some(status) = &config.status {
+        checks.push(match status {
+            HandlerStatus::Success => quote! {
+                matches!(origin.tx_status, Some(fuel::TransactionStatus::Success { .. }))
+            },
+            HandlerStatus::Failure => quote! {
+                matches!(origin.tx_status, Some(fuel::TransactionStatus::Failure { .. }))
+            },
+        });
+    }
 
-    if GENERIC_STRUCTS.contains(base_name.as_str()) {
-        typed_path_string(p)
-    } else {
-        base_name
+    if let Some(sender) = &config.sender {
+        let bytes = parse_id::<Bech32Address, Address>(sender);
+        checks.push(quote! {
+            origin.senders.contains(&Address::from([#(#bytes),*]))
+        });
+    }
+
+    if checks.is_empty() {
+        return None;
     }
+
+    Some(quote! {
+        origin.tx_status.is_none() || (#(#checks)&&*)
+    })
 }
 
-/// Extract the fully typed path for this generic type.
-///
-/// When given a generic's `TypedPath` (e.g., `Vec<T>`), we need to extract the struct type (e.g., `Vec`)
-/// and also inner type `T`.
+/// Return the type of a field of an indexer's `#[config]` struct, in the GraphQL
+/// notation used by `ConfigSchema` (e.g., `UInt8!` or `[Address!]`).
 ///
-/// The following assumes a generic type definition format of:
-///      `$struct_name $bracket(open) $inner_t_name  $bracket(close)` (e.g., `Vec<T>` or `Option<T>`)
-fn typed_path_string(p: &TypePath) -> String {
-    let mut result = String::new();
-
-    let base = p
-        .path
-        .segments
-        .last()
-        .expect("Could not get last path segment.");
+/// Returns `None` if the field type can't be a config value.
+pub fn config_field_type(ty: &Type) -> Option<String> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let base = p.path.segments.last()?;
+    let inner = match &base.arguments {
+        PathArguments::None => None,
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                GenericArgument::Type(t) => Some(t),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let name = match (base.ident.to_string().as_str(), inner) {
+        ("Option", Some(inner)) => {
+            return config_field_type(inner).map(|t| t.trim_end_matches('!').to_string())
+        }
+        ("Vec", Some(inner)) => format!("[{}]", config_field_type(inner)?),
+        ("bool", None) => "Boolean".to_string(),
+        ("String", None) => "String".to_string(),
+        ("i8", None) => "Int1".to_string(),
+        ("i16", None) => "Int2".to_string(),
+        ("i32", None) => "Int4".to_string(),
+        ("i64", None) => "Int8".to_string(),
+        ("u8", None) => "UInt1".to_string(),
+        ("u16", None) => "UInt2".to_string(),
+        ("u32", None) => "UInt4".to_string(),
+        ("u64", None) => "UInt8".to_string(),
+        (name @ ("Address" | "AssetId" | "ContractId" | "Bytes32"), None) => {
+            name.to_string()
+        }
+        _ => return None,
+    };
 
-    result.push_str(&base.ident.to_string());
-    result.push('<');
+    Some(format!("{name}!"))
+}
 
-    match base.arguments {
-        PathArguments::AngleBracketed(ref inner) => {
-            let _ = inner
-                .args
-                .iter()
-                .map(|arg| match arg {
-                    GenericArgument::Type(Type::Path(p)) => {
-                        let segment = p
-                            .path
-                            .segments
-                            .last()
-                            .expect("Could not get last path segment.");
-                        let name = segment.ident.to_string();
-                        result.push_str(&name);
-                        result.push('>');
-                    }
-                    _ => panic!("Unsupported generic argument."),
-                })
-                .collect::<Vec<_>>();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn application(type_id: usize, args: Vec<TypeApplication>) -> TypeApplication {
+        TypeApplication {
+            name: String::new(),
+            type_id,
+            type_arguments: (!args.is_empty()).then_some(args),
         }
-        _ => panic!("Unsupported generic argument."),
     }
-    result
-}
 
-/// Retrieve the inner `T` from the given generic `TypeDefinition`s type field.
-///
-/// E.g., extrac `T` from `Vec<T>`
-pub fn inner_typedef(typ: &TypeDeclaration) -> (String, proc_macro2::TokenStream) {
-    let name = derive_type_name(typ);
-    let gt = GenericType::from(name.clone());
-    match gt {
-        GenericType::Vec => (name[4..name.len() - 1].to_string(), gt.into()),
-        GenericType::Option => (name[7..name.len() - 1].to_string(), gt.into()),
-        _ => proc_macro_error::abort_call_site!("Unsupported generic type: {:?}", gt),
+    fn declaration(
+        type_id: usize,
+        type_field: &str,
+        components: Vec<TypeApplication>,
+        type_parameters: Vec<usize>,
+    ) -> TypeDeclaration {
+        TypeDeclaration {
+            type_id,
+            type_field: type_field.to_string(),
+            components: (!components.is_empty()).then_some(components),
+            type_parameters: (!type_parameters.is_empty()).then_some(type_parameters),
+        }
     }
-}
 
-/// Derive the output type ID for a given ABI function.
-///
-/// For non-generic `TypeDeclaration`s, we can just return the `type_id` associated with
-/// that function output. But for generic `TypeDeclaration`s, we need to derive the `type_id`
-/// using the fully typed path of the generic type (e.g., `Vec<T>`) in order to match
-/// the type ID of the `TypeDefinition` we manually inserted into the `#[indexer]` `abi_types_tyid`
-/// mapping.
-pub fn function_output_type_id(
-    f: &ABIFunction,
-    abi_types: &HashMap<usize, TypeDeclaration>,
-) -> usize {
-    let outer_typ = abi_types.get(&f.output.type_id).unwrap_or_else(|| {
-        panic!(
-            "function_output_type_id: Type with TypeID({}) is missing from the JSON ABI",
-            f.output.type_id
-        )
-    });
-    if is_generic_type(outer_typ) {
-        let name = derive_type_name(outer_typ);
-        let gt = GenericType::from(name);
-        let inner = f
-            .output
-            .type_arguments
-            .as_ref()
-            .unwrap()
-            .first()
-            .expect("Missing inner type.");
-
-        match gt {
-            GenericType::Option | GenericType::Vec => {
-                let inner_typ = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("function_output_type_id: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
-                });
-                let (typ_name, _) =
-                    typed_path_components(outer_typ, inner_typ, abi_types);
-                type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Unsupported generic type for function outputs: {:?}",
-                gt
+    // The type declarations of an ABI using `Option`, `Result`, `Vec`, tuples, and
+    // a user-defined `struct Wrapper<T> { inner: T }`.
+    fn abi_types() -> HashMap<usize, TypeDeclaration> {
+        [
+            declaration(0, "b256", vec![], vec![]),
+            declaration(1, "generic T", vec![], vec![]),
+            declaration(2, "generic E", vec![], vec![]),
+            declaration(3, "raw untyped ptr", vec![], vec![]),
+            declaration(
+                4,
+                "struct RawVec",
+                vec![application(3, vec![]), application(8, vec![])],
+                vec![1],
             ),
-        }
-    } else {
-        f.output.type_id
+            declaration(
+                5,
+                "struct SomeEvent",
+                vec![application(8, vec![]), application(0, vec![])],
+                vec![],
+            ),
+            declaration(
+                6,
+                "struct Vec",
+                vec![
+                    application(4, vec![application(1, vec![])]),
+                    application(8, vec![]),
+                ],
+                vec![1],
+            ),
+            declaration(
+                7,
+                "enum Option",
+                vec![application(10, vec![]), application(1, vec![])],
+                vec![1],
+            ),
+            declaration(8, "u64", vec![], vec![]),
+            declaration(
+                9,
+                "enum Result",
+                vec![application(1, vec![]), application(2, vec![])],
+                vec![1, 2],
+            ),
+            declaration(10, "()", vec![], vec![]),
+            declaration(
+                11,
+                "(_, _)",
+                vec![application(8, vec![]), application(12, vec![])],
+                vec![],
+            ),
+            declaration(12, "bool", vec![], vec![]),
+            declaration(13, "struct Wrapper", vec![application(1, vec![])], vec![1]),
+        ]
+        .into_iter()
+        .map(|typ| (typ.type_id, typ))
+        .collect()
     }
-}
 
-/// Given two `TypeDeclaration`s for a generic struct (e.g., `Vec`) and an inner type (e.g., `T`),
-/// return the associated fully typed path. (e.g., `Vec<T>`)
-///
-/// We do this by recursively deriving the inner `T` for the provided inner `T`, so long as
-/// the inner `T` is itself, generic.
-pub fn typed_path_components(
-    outer: &TypeDeclaration,
-    inner: &TypeDeclaration,
-    abi_types: &HashMap<usize, TypeDeclaration>,
-) -> (String, TokenStream) {
-    let outer_name = derive_type_name(outer);
-    let outer_ident = format_ident! { "{}", outer_name };
-    let inner_name = derive_type_name(inner);
-    let inner_ident = format_ident! { "{}", inner_name };
-    let mut tokens = quote! { #inner_ident };
-
-    let mut curr = inner;
-    while is_generic_type(curr) {
-        let gt = GenericType::from(curr);
-        match gt {
-            GenericType::Option | GenericType::Vec => {
-                curr = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("typed_path_components: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
-                });
-                let name = derive_type_name(curr);
-                let ident = format_ident! { "{}", name };
-                tokens = quote! { #tokens<#ident> }
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Unsupported generic type for typed path: {:?}",
-                gt
+    fn assert_monomorphized(
+        application: TypeApplication,
+        handler_arg: Type,
+        tokens: &str,
+    ) {
+        let abi_types = abi_types();
+        assert!(is_monomorphized_application(&application, &abi_types));
+
+        let typ = monomorphize(&application, &abi_types).unwrap();
+        assert_eq!(Some(typ.name.clone()), handler_arg_type_name(&handler_arg));
+        assert_eq!(typ.tokens.to_string(), tokens);
+        assert_eq!(
+            typ.type_id,
+            type_id(FUEL_TYPES_NAMESPACE, &typ.name) as usize
+        );
+        assert_eq!(application_type_id(&application, &abi_types), typ.type_id);
+        assert!(!typ.has_nested_heap_types);
+    }
+
+    #[test]
+    fn test_can_monomorphize_option() {
+        assert_monomorphized(
+            application(7, vec![application(5, vec![])]),
+            parse_quote! { Option<SomeEvent> },
+            ":: core :: option :: Option < SomeEvent >",
+        );
+    }
+
+    #[test]
+    fn test_can_monomorphize_result() {
+        assert_monomorphized(
+            application(9, vec![application(5, vec![]), application(8, vec![])]),
+            parse_quote! { Result<SomeEvent, u64> },
+            ":: core :: result :: Result < SomeEvent , u64 >",
+        );
+    }
+
+    #[test]
+    fn test_can_monomorphize_tuple() {
+        assert_monomorphized(
+            application(11, vec![]),
+            parse_quote! { (u64, bool) },
+            "(u64 , bool ,)",
+        );
+    }
+
+    #[test]
+    fn test_can_monomorphize_user_defined_generic_struct() {
+        assert_monomorphized(
+            application(13, vec![application(5, vec![])]),
+            parse_quote! { Wrapper<SomeEvent> },
+            "Wrapper < SomeEvent >",
+        );
+    }
+
+    #[test]
+    fn test_can_monomorphize_nested_generics() {
+        assert_monomorphized(
+            application(
+                7,
+                vec![application(
+                    13,
+                    vec![application(9, vec![application(11, vec![]), application(0, vec![])])],
+                )],
             ),
-        }
+            parse_quote! { Option<Wrapper<Result<(u64, bool), Bits256>>> },
+            ":: core :: option :: Option < Wrapper < :: core :: result :: Result < (u64 , bool ,) , Bits256 > > >",
+        );
     }
 
-    tokens = quote! { #outer_ident<#tokens> };
+    #[test]
+    fn test_monomorphizing_nested_heap_types_is_flagged() {
+        let abi_types = abi_types();
+        let application =
+            application(6, vec![application(6, vec![application(8, vec![])])]);
 
-    // Remove white space from path`
-    let name = tokens.to_string().replace(' ', "");
+        let typ = monomorphize(&application, &abi_types).unwrap();
+        assert_eq!(typ.name, "Vec<Vec<u64>>");
+        assert!(typ.has_nested_heap_types);
+    }
 
-    (name, tokens)
-}
+    #[test]
+    fn test_cannot_monomorphize_unresolved_type_parameters() {
+        let abi_types = abi_types();
 
-/// Determine whether or not the given type name is an unsupported type.
-///
-/// Since we allow unsupported types in the ABI JSON, this check is only
-/// performed on indexer handler function arg typed paths.
-pub fn is_unsupported_type(type_name: &str) -> bool {
-    let gt = GenericType::from(type_name);
-    match gt {
-        GenericType::Vec => UNSUPPORTED_ABI_JSON_TYPES.contains(gt.into()),
-        _ => UNSUPPORTED_ABI_JSON_TYPES.contains(type_name),
+        // `Option` applied without its type argument.
+        assert!(monomorphize(&application(7, vec![]), &abi_types).is_none());
+
+        // `Result` applied to only one of its two type arguments.
+        let application = application(9, vec![application(8, vec![])]);
+        assert!(monomorphize(&application, &abi_types).is_none());
+    }
+
+    #[test]
+    fn test_type_parameters_and_generic_types_are_not_decodable() {
+        let abi_types = abi_types();
+
+        for type_id in [1, 2, 4, 6, 7, 9, 10, 11, 13] {
+            assert!(is_non_decodable_type(&abi_types[&type_id]));
+        }
+
+        for type_id in [0, 5, 8, 12] {
+            assert!(!is_non_decodable_type(&abi_types[&type_id]));
+        }
+    }
+
+    #[test]
+    fn test_handler_arg_type_names() {
+        let names = [
+            (parse_quote! { SomeEvent }, Some("SomeEvent")),
+            (parse_quote! { Vec<u8> }, Some("Vec<u8>")),
+            (parse_quote! { [u8; 32] }, Some("[u8;32]")),
+            (parse_quote! { ((u64), bool) }, Some("(u64,bool)")),
+            (parse_quote! { &SomeEvent }, None),
+        ];
+
+        for (ty, name) in names {
+            assert_eq!(handler_arg_type_name(&ty).as_deref(), name);
+        }
+    }
+
+    #[test]
+    fn test_config_field_types() {
+        let types = [
+            (parse_quote! { u64 }, Some("UInt8!")),
+            (parse_quote! { Option<String> }, Some("String")),
+            (parse_quote! { Vec<Address> }, Some("[Address!]!")),
+            (parse_quote! { Option<Vec<Option<i16>>> }, Some("[Int2]")),
+            (parse_quote! { fuel::AssetId }, Some("AssetId!")),
+            (parse_quote! { f64 }, None),
+            (parse_quote! { HashMap<String, u64> }, None),
+        ];
+
+        for (ty, name) in types {
+            assert_eq!(config_field_type(&ty).as_deref(), name);
+        }
     }
 }
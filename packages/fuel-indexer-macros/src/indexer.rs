@@ -1,26 +1,134 @@
 use crate::{
     helpers::*,
     native::{handler_block_native, native_main},
-    parse::IndexerConfig,
+    parse::{HandlerConfig, IndexerConfig, IntervalHandlerConfig},
     schema::process_graphql_schema,
     wasm::handler_block_wasm,
 };
 use fuel_abi_types::abi::program::TypeDeclaration;
 use fuel_indexer_lib::{
-    constants::*, manifest::ContractIds, manifest::Manifest,
-    utils::workspace_manifest_prefix, ExecutionSource,
+    constants::*,
+    graphql::ParsedGraphQLSchema,
+    manifest::{ConfigSchema, ContractIds, Manifest, CONFIG_SCHEMA_SECTION},
+    utils::workspace_manifest_prefix,
+    ExecutionSource,
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
 use fuels::{core::codec::resolve_fn_selector, types::param_types::ParamType};
 use fuels_code_gen::{Abigen, AbigenTarget, ProgramType};
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use syn::{parse_macro_input, FnArg, Item, ItemMod, PatType, Type};
+use syn::{
+    parse_macro_input, Attribute, FnArg, Item, ItemFn, ItemMod, ItemStruct, PatType,
+    ReturnType,
+};
+
+/// Return the tokens that complete a call to a handler function.
+///
+/// Native handlers are awaited, and may return an `IndexerResult`, whose error is
+/// propagated to the executor.
+fn handler_call_suffix(
+    fn_item: &ItemFn,
+    exec_source: &ExecutionSource,
+) -> proc_macro2::TokenStream {
+    match (exec_source, &fn_item.sig.output) {
+        (ExecutionSource::Native, ReturnType::Type(..)) => quote! { .await? },
+        (ExecutionSource::Native, ReturnType::Default) => quote! { .await },
+        (ExecutionSource::Wasm, _) => quote! {},
+    }
+}
+
+/// Return the dispatcher of a handler function with a `#[block_handler]` or an
+/// `#[interval_handler(every = N)]` attribute.
+///
+/// These handlers run once per block (or once every `N` blocks), after the handlers
+/// of the types decoded from the block's receipts, and can only take `BlockData`
+/// and `HandlerContext` arguments.
+fn block_handler_dispatcher(
+    fn_item: &ItemFn,
+    attr: &Attribute,
+    exec_source: &ExecutionSource,
+    records_provenance: bool,
+) -> proc_macro2::TokenStream {
+    let fn_name = &fn_item.sig.ident;
+    let (_, awaitness) = exec_source.async_awaitness();
+    let call_suffix = handler_call_suffix(fn_item, exec_source);
+
+    let interval_filter = if attr.path().is_ident("interval_handler") {
+        let every = match attr.parse_args::<IntervalHandlerConfig>() {
+            Ok(config) => config.every,
+            Err(e) => proc_macro_error::abort!(e.span(), "{}", e),
+        };
+
+        quote! { .filter(|(_, block)| block.height % #every == 0) }
+    } else {
+        if let Err(e) = attr.meta.require_path_only() {
+            proc_macro_error::abort!(e.span(), "{}", e)
+        }
+
+        quote! {}
+    };
+
+    let mut takes_context = false;
+    let args = fn_item
+        .sig
+        .inputs
+        .iter()
+        .map(|inp| {
+            let ty = match inp {
+                FnArg::Receiver(_) => proc_macro_error::abort_call_site!(
+                    "`self` argument not allowed in handler function."
+                ),
+                FnArg::Typed(PatType { ty, .. }) => ty,
+            };
+
+            match handler_arg_type_name(ty).as_deref() {
+                Some("BlockData") => quote! { block.clone() },
+                Some("HandlerContext") => {
+                    takes_context = true;
+                    quote! { ctx.clone() }
+                }
+                _ => proc_macro_error::abort_call_site!(
+                    "Block handler '{}' can only take `BlockData` and `HandlerContext` arguments.",
+                    fn_name.to_string(),
+                ),
+            }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    let record_provenance = if records_provenance {
+        quote! { self.set_provenance(&[])#awaitness; }
+    } else {
+        quote! {}
+    };
+    let call = quote! {
+        #record_provenance
+        unsafe {
+            current_receipt = None;
+        }
+        #fn_name(#(#args),*)#call_suffix;
+    };
+    let call = if takes_context {
+        quote! {
+            let ctx = self.handler_context(&[]);
+            #call
+        }
+    } else {
+        call
+    };
+
+    quote! {
+        if let Some((_, block)) = self.blockdata_decoded.first() #interval_filter {
+            #call
+        }
+    }
+}
 
 fn process_fn_items(
     manifest: &Manifest,
+    schema: &ParsedGraphQLSchema,
     abi_path: Option<String>,
     indexer_module: ItemMod,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
@@ -43,6 +151,7 @@ fn process_fn_items(
     let mut decoded_log_match_arms = HashSet::new();
     let mut decoded_type_fields = HashSet::new();
     let mut abi_dispatchers = Vec::new();
+    let mut block_dispatchers = Vec::new();
 
     let funcs = abi.clone().functions;
     let abi_types: Vec<TypeDeclaration> = abi
@@ -93,7 +202,7 @@ fn process_fn_items(
         .iter()
         .map(|typ| {
             let message_type_id = typ.message_id;
-            let ty_id = typ.application.type_id;
+            let ty_id = application_type_id(&typ.application, &abi_types_tyid);
 
             quote! {
                 #message_type_id => {
@@ -122,60 +231,11 @@ fn process_fn_items(
                 )
             }
 
-            if is_generic_type(typ) {
-                let gt = GenericType::from(typ);
-                match gt {
-                    GenericType::Vec | GenericType::Option => {
-                        let ab_types = abi_types_tyid.clone();
-                        let inner_typs = derive_generic_inner_typedefs(
-                            typ,
-                            &funcs,
-                            &abi_log_types,
-                            &ab_types,
-                        );
-
-                        return Some(
-                            inner_typs
-                                .iter()
-                                .filter_map(|inner_typ| {
-                                    let (typ_name, type_tokens) = typed_path_components(
-                                        typ,
-                                        inner_typ,
-                                        &abi_types_tyid,
-                                    );
-                                    let ty_id =
-                                        type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-
-                                    let typ = TypeDeclaration {
-                                        type_id: ty_id,
-                                        type_field: typ_name.clone(),
-                                        ..typ.clone()
-                                    };
-
-                                    if decoded_type_snippets.contains(&ty_id) {
-                                        return None;
-                                    }
-
-                                    abi_types_tyid.insert(ty_id, typ.clone());
-                                    type_ids.insert(typ_name.clone(), ty_id);
-
-                                    decoded_type_snippets.insert(ty_id);
-
-                                    Some(decode_snippet(&type_tokens, &typ))
-                                })
-                                .collect::<Vec<proc_macro2::TokenStream>>(),
-                        );
-                    }
-                    _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
-                }
-            } else {
-                let type_tokens = typ.rust_tokens();
-                type_ids.insert(type_tokens.to_string(), typ.type_id);
-                decoded_type_snippets.insert(typ.type_id);
-                Some(vec![decode_snippet(&type_tokens, typ)])
-            }
+            let type_tokens = typ.rust_tokens();
+            type_ids.insert(type_tokens.to_string(), typ.type_id);
+            decoded_type_snippets.insert(typ.type_id);
+            Some(decode_snippet(&type_tokens, typ))
         })
-        .flatten()
         .collect::<Vec<proc_macro2::TokenStream>>();
 
     let fuel_type_decoders = fuel_types
@@ -190,8 +250,6 @@ fn process_fn_items(
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let decoders = [fuel_type_decoders, abi_type_decoders].concat();
-
     let abi_struct_fields = abi_types
         .iter()
         .filter_map(|typ| {
@@ -206,55 +264,15 @@ fn process_fn_items(
                 )
             }
 
-            if is_generic_type(typ) {
-                let inner_typs = derive_generic_inner_typedefs(
-                    typ,
-                    &funcs,
-                    &abi_log_types,
-                    &abi_types_tyid,
-                );
-
-                return Some(
-                    inner_typs
-                        .iter()
-                        .filter_map(|inner_typ| {
-                            let (typ_name, type_tokens) =
-                                typed_path_components(typ, inner_typ, &abi_types_tyid);
-                            let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-
-                            if decoded_type_fields.contains(&ty_id) {
-                                return None;
-                            }
-
-                            let typ = TypeDeclaration {
-                                type_id: ty_id,
-                                type_field: typ_name.clone(),
-                                ..typ.clone()
-                            };
-
-                            let ident = typ.decoder_field_ident();
-
-                            type_ids.insert(typ_name.clone(), ty_id);
-                            decoded_type_fields.insert(ty_id);
+            let ident = typ.decoder_field_ident();
+            let type_tokens = typ.rust_tokens();
+            type_ids.insert(typ.rust_tokens().to_string(), typ.type_id);
+            decoded_type_fields.insert(typ.type_id);
 
-                            Some(quote! {
-                                #ident: Vec<#type_tokens>
-                            })
-                        })
-                        .collect::<Vec<proc_macro2::TokenStream>>(),
-                );
-            } else {
-                let ident = typ.decoder_field_ident();
-                let type_tokens = typ.rust_tokens();
-                type_ids.insert(typ.rust_tokens().to_string(), typ.type_id);
-                decoded_type_fields.insert(typ.type_id);
-
-                Some(vec![quote! {
-                    #ident: Vec<#type_tokens>
-                }])
-            }
+            Some(quote! {
+                #ident: Vec<(DecodedOrigin, #type_tokens)>
+            })
         })
-        .flatten()
         .collect::<Vec<proc_macro2::TokenStream>>();
 
     let fuel_struct_fields = fuel_types
@@ -275,60 +293,87 @@ fn process_fn_items(
             }
 
             Some(quote! {
-                #name: Vec<#ty>
+                #name: Vec<(DecodedOrigin, #ty)>
             })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let decoder_struct_fields = [abi_struct_fields, fuel_struct_fields].concat();
+    // Tuples, arrays, and generic types (e.g., `Vec<T>` or `Result<T, E>`) are
+    // monomorphized for each concrete type they're applied to in function outputs,
+    // logs, and messages, with each monomorphized type getting its own decoder.
+    let mut nested_heap_types = HashSet::new();
+    let mut monomorphized_type_decoders = Vec::new();
+    let mut monomorphized_struct_fields = Vec::new();
+
+    let applications = funcs
+        .iter()
+        .map(|f| &f.output)
+        .chain(abi_log_types.iter().map(|log| &log.application))
+        .chain(abi_msg_types.iter().map(|msg| &msg.application))
+        .filter(|app| is_monomorphized_application(app, &abi_types_tyid))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for application in applications.iter() {
+        let Some(typ) = monomorphize(application, &abi_types_tyid) else {
+            continue;
+        };
+
+        if typ.has_nested_heap_types {
+            nested_heap_types.insert(typ.name);
+            continue;
+        }
+
+        if decoded_type_snippets.contains(&typ.type_id) {
+            continue;
+        }
+
+        let decl = typ.type_declaration();
+        let ident = decl.decoder_field_ident();
+        let type_tokens = &typ.tokens;
+
+        monomorphized_type_decoders.push(decode_snippet(type_tokens, &decl));
+        monomorphized_struct_fields.push(quote! {
+            #ident: Vec<(DecodedOrigin, #type_tokens)>
+        });
+
+        type_ids.insert(typ.name.clone(), typ.type_id);
+        decoded_type_snippets.insert(typ.type_id);
+        decoded_type_fields.insert(typ.type_id);
+        abi_types_tyid.insert(typ.type_id, decl);
+    }
+
+    let decoders = [
+        fuel_type_decoders,
+        abi_type_decoders,
+        monomorphized_type_decoders,
+    ]
+    .concat();
+
+    let decoder_struct_fields = [
+        abi_struct_fields,
+        fuel_struct_fields,
+        monomorphized_struct_fields,
+    ]
+    .concat();
 
-    // Since log type decoders use `TypeDeclaration`s that were manually created specifically
-    // for generics, we parsed log types after other ABI types.
     let log_type_decoders = abi_log_types
         .iter()
         .filter_map(|log| {
-            let ty_id = log.application.type_id;
+            let ty_id = application_type_id(&log.application, &abi_types_tyid);
             let log_id = log.log_id as usize;
-            let typ = abi_types_tyid.get(&log.application.type_id).unwrap();
 
-            if is_non_decodable_type(typ) {
+            if !decoded_type_snippets.contains(&ty_id) {
                 return None;
             }
 
-            if is_generic_type(typ) {
-                let gt = GenericType::from(typ);
-                match gt {
-                    GenericType::Vec | GenericType::Option => {
-                        let inner_typ =
-                            derive_log_generic_inner_typedefs(log, &abi, &abi_types_tyid);
-
-                        let (typ_name, _) =
-                            typed_path_components(typ, inner_typ, &abi_types_tyid);
+            decoded_log_match_arms.insert(log_id);
 
-                        let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-                        let _typ = abi_types_tyid.get(&ty_id).expect(
-                            "Could not get generic log type reference from ABI types.",
-                        );
-
-                        decoded_log_match_arms.insert(log_id);
-
-                        Some(quote! {
-                            #log_id => {
-                                self.decode_type(#ty_id, data);
-                            }
-                        })
-                    }
-                    _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
+            Some(quote! {
+                #log_id => {
+                    self.decode_type(#ty_id, data);
                 }
-            } else {
-                decoded_log_match_arms.insert(log_id);
-
-                Some(quote! {
-                    #log_id => {
-                        self.decode_type(#ty_id, data);
-                    }
-                })
-            }
+            })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
@@ -345,7 +390,7 @@ fn process_fn_items(
                 .collect();
             let sig = resolve_fn_selector(&function.name, &params[..]);
             let selector = u64::from_be_bytes(sig);
-            let ty_id = function_output_type_id(function, &abi_types_tyid);
+            let ty_id = application_type_id(&function.output, &abi_types_tyid);
 
             quote! {
                 #selector => #ty_id,
@@ -380,6 +425,7 @@ fn process_fn_items(
         .1;
 
     let mut handler_fns = Vec::with_capacity(contents.len());
+    let mut config_struct: Option<ItemStruct> = None;
 
     let start_block = match manifest.start_block() {
         Some(start_block) => {
@@ -392,8 +438,31 @@ fn process_fn_items(
         None => quote! {},
     };
 
+    // Contract IDs registered by handlers are reloaded for every batch of blocks,
+    // so that IDs registered in a reverted transaction are not tracked.
+    let load_tracked_contract_ids = match manifest.execution_source() {
+        ExecutionSource::Native => quote! {
+            unsafe {
+                if let Some(d) = &db {
+                    let ids = d.lock().await.contract_ids().await?;
+                    tracked_contract_ids = Some(
+                        ids.iter()
+                            .map(|id| ContractId::from_str(id).expect("Failed to parse tracked contract ID"))
+                            .collect(),
+                    );
+                }
+            }
+        },
+        ExecutionSource::Wasm => quote! {
+            unsafe {
+                tracked_contract_ids = Some(load_contract_ids().into_iter().collect());
+            }
+        },
+    };
+
     let subscribed_contract_ids = match &manifest.contract_id() {
-        ContractIds::Single(_) => quote! {},
+        ContractIds::Single(None) => quote! {},
+        ContractIds::Single(Some(_)) => load_tracked_contract_ids,
         ContractIds::Multiple(contract_ids) => {
             let contract_ids = contract_ids
                 .iter()
@@ -409,6 +478,8 @@ fn process_fn_items(
 
             quote! {
                 let contract_ids = HashSet::from([#(#contract_ids),*]);
+
+                #load_tracked_contract_ids
             }
         }
     };
@@ -423,7 +494,7 @@ fn process_fn_items(
                         let contract_id = ContractId::from_str(&#contract_id).expect("Failed to parse manifest 'contract_id'");
                         Bech32ContractId::from(contract_id)
                     });
-                    if bech32_id != manifest_contract_id {
+                    if bech32_id != manifest_contract_id && !is_tracked_contract(&ContractId::from(id_bytes)) {
                         debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
                         continue;
                     }
@@ -436,7 +507,7 @@ fn process_fn_items(
                 let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
                 let bech32_id = Bech32ContractId::new("fuel", id_bytes);
 
-                if !contract_ids.contains(&bech32_id) {
+                if !contract_ids.contains(&bech32_id) && !is_tracked_contract(&ContractId::from(id_bytes)) {
                     debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
                     continue;
                 }
@@ -446,11 +517,94 @@ fn process_fn_items(
 
     let (asyncness, awaitness) = manifest.execution_source().async_awaitness();
 
+    // Native dispatchers return the errors of handlers and of saving entities, rather
+    // than exiting early.
+    let (dispatch_output, dispatch_ok, try_op) = match manifest.execution_source() {
+        ExecutionSource::Native => (
+            quote! { -> IndexerResult<()> },
+            quote! { Ok(()) },
+            quote! { ? },
+        ),
+        ExecutionSource::Wasm => (quote! {}, quote! {}, quote! {}),
+    };
+
+    // Provenance is only recorded if the schema has entities with provenance columns.
+    let records_provenance = schema.has_provenance_types();
+
+    let set_provenance = match manifest.execution_source() {
+        ExecutionSource::Native => quote! {
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.set_provenance(Provenance {
+                        block_height: block.height,
+                        block_time: block.time,
+                        tx_id: tx_id.map(|id| format!("{id:x}")),
+                    });
+                }
+            }
+        },
+        ExecutionSource::Wasm => quote! {
+            set_provenance(block.height, block.time, tx_id.as_ref());
+        },
+    };
+
     for item in contents {
         match item {
-            Item::Fn(fn_item) => {
-                let mut input_checks = Vec::new();
+            Item::Fn(mut fn_item) => {
+                let mut handler_attrs = Vec::new();
+                fn_item.attrs.retain(|attr| {
+                    let path = attr.path();
+                    if path.is_ident("handler")
+                        || path.is_ident("block_handler")
+                        || path.is_ident("interval_handler")
+                    {
+                        handler_attrs.push(attr.clone());
+                        return false;
+                    }
+                    true
+                });
+
+                if handler_attrs.len() > 1 {
+                    proc_macro_error::abort_call_site!(
+                        "Handler function '{}' can have at most one of the `#[handler]`, `#[block_handler]`, and `#[interval_handler]` attributes.",
+                        fn_item.sig.ident.to_string(),
+                    )
+                }
+
+                if let Some(attr) = handler_attrs
+                    .first()
+                    .filter(|attr| !attr.path().is_ident("handler"))
+                {
+                    block_dispatchers.push(block_handler_dispatcher(
+                        &fn_item,
+                        attr,
+                        &manifest.execution_source(),
+                        records_provenance,
+                    ));
+                    handler_fns.push(fn_item);
+                    continue;
+                }
+
+                let handler_config = match handler_attrs.first() {
+                    Some(attr) => match attr.parse_args::<HandlerConfig>() {
+                        Ok(config) => config,
+                        Err(e) => proc_macro_error::abort!(e.span(), "{}", e),
+                    },
+                    None => HandlerConfig::default(),
+                };
+
+                // Without filters, handlers get the first decoded value of each argument
+                // type. With filters, they get the first value that passes the filters.
+                let find_arg = match handler_filter(&handler_config) {
+                    Some(filter) => quote! { .iter().find(|(origin, _)| #filter) },
+                    None => quote! { .first() },
+                };
+
+                let mut arg_lookups = Vec::new();
                 let mut arg_list = Vec::new();
+                let mut call_args = Vec::new();
+                let mut takes_context = false;
+                let mut takes_receipt_data = false;
 
                 for inp in &fn_item.sig.inputs {
                     match inp {
@@ -460,52 +614,61 @@ fn process_fn_items(
                             )
                         }
                         FnArg::Typed(PatType { ty, .. }) => {
-                            if let Type::Path(path) = &**ty {
-                                let path_seg = path
-                                    .path
-                                    .segments
-                                    .last()
-                                    .expect("Could not get last path segment.");
-
-                                let path_type_name = typed_path_name(path);
+                            let Some(type_name) = handler_arg_type_name(ty) else {
+                                proc_macro_error::abort_call_site!(
+                                    "Type '{}' is not currently supported.",
+                                    quote! { #ty }
+                                )
+                            };
 
-                                if is_unsupported_type(&path_type_name) {
+                            if type_name == "HandlerContext" {
+                                if takes_context {
                                     proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' is not currently supported.",
-                                        path_seg.ident
+                                        "Handler function '{}' can take at most one `HandlerContext`.",
+                                        fn_item.sig.ident.to_string(),
                                     )
                                 }
 
-                                if !type_ids.contains_key(&path_type_name) {
-                                    proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' not defined in the ABI.",
-                                        path_seg.ident
-                                    );
-                                };
-
-                                let ty_id = type_ids.get(&path_type_name).unwrap();
-                                let typ = match abi_types_tyid.get(ty_id) {
-                                    Some(typ) => typ,
-                                    None => fuel_types.get(ty_id).unwrap(),
-                                };
-
-                                let dispatcher_name = typ.decoder_field_ident();
-
-                                input_checks
-                                    .push(quote! { self.#dispatcher_name.len() > 0 });
+                                takes_context = true;
+                                call_args.push(quote! { ctx });
+                                continue;
+                            }
 
-                                arg_list
-                                    .push(quote! { self.#dispatcher_name[0].clone() });
-                            } else {
+                            if nested_heap_types.contains(&type_name) {
                                 proc_macro_error::abort_call_site!(
-                                    "Arguments must be types defined in the ABI."
+                                    "Type '{}' nests heap types, which can't be decoded.",
+                                    type_name
                                 )
                             }
+
+                            let Some(ty_id) = type_ids.get(&type_name) else {
+                                proc_macro_error::abort_call_site!(
+                                    "Type '{}' not defined in the ABI.",
+                                    type_name
+                                )
+                            };
+
+                            let typ = match abi_types_tyid.get(ty_id) {
+                                Some(typ) => typ,
+                                None => fuel_types.get(ty_id).unwrap(),
+                            };
+
+                            let dispatcher_name = typ.decoder_field_ident();
+                            takes_receipt_data |= type_name != "BlockData";
+
+                            let arg = format_ident! { "arg{}", arg_list.len() };
+
+                            arg_lookups.push(quote! { self.#dispatcher_name #find_arg });
+
+                            call_args.push(quote! { #arg.clone() });
+                            arg_list.push(arg);
                         }
                     }
                 }
 
                 let fn_name = &fn_item.sig.ident;
+                let call_suffix =
+                    handler_call_suffix(&fn_item, &manifest.execution_source());
 
                 if arg_list.is_empty() {
                     proc_macro_error::abort_call_site!(
@@ -514,14 +677,66 @@ fn process_fn_items(
                     );
                 }
 
-                abi_dispatchers.push(quote! {
-                    if ( #(#input_checks)&&* ) {
-                        #fn_name(#(#arg_list),*)#awaitness;
+                let origins = (0..arg_list.len())
+                    .map(|i| format_ident! { "origin{}", i })
+                    .collect::<Vec<_>>();
+                let record_provenance = if records_provenance {
+                    quote! { self.set_provenance(&[#(#origins),*])#awaitness; }
+                } else {
+                    quote! {}
+                };
+                let call = quote! {
+                    #record_provenance
+                    unsafe {
+                        current_receipt = DecodedOrigin::latest_position(&[#(#origins),*]);
+                    }
+                    #fn_name(#(#call_args),*)#call_suffix;
+                };
+                let call = if takes_context {
+                    quote! {
+                        let ctx = self.handler_context(&[#(#origins),*]);
+                        #call
+                    }
+                } else {
+                    call
+                };
+
+                let dispatcher = quote! {
+                    if let ( #(Some((#origins, #arg_list)),)* ) = ( #(#arg_lookups,)* ) {
+                        #call
+                    }
+                };
+
+                // Handlers that only take the block have already been called when the
+                // block's receipts are dispatched again.
+                abi_dispatchers.push(if takes_receipt_data {
+                    dispatcher
+                } else {
+                    quote! {
+                        if !self.replay {
+                            #dispatcher
+                        }
                     }
                 });
 
                 handler_fns.push(fn_item);
             }
+            Item::Struct(mut struct_item)
+                if struct_item
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("config")) =>
+            {
+                if config_struct.is_some() {
+                    proc_macro_error::abort_call_site!(
+                        "An indexer module can only have one #[config] struct."
+                    )
+                }
+                struct_item
+                    .attrs
+                    .retain(|attr| !attr.path().is_ident("config"));
+                config_struct = Some(struct_item);
+            }
             i => {
                 proc_macro_error::abort_call_site!(
                     "Unsupported item in indexer module '{:?}'.",
@@ -531,10 +746,104 @@ fn process_fn_items(
         }
     }
 
+    let set_provenance_fn = if records_provenance {
+        quote! {
+            // Record the block being processed, and the transaction that the arguments of
+            // the handler about to be called came from, in the provenance columns of the
+            // entities that the handler saves. Does nothing if no block has been decoded yet.
+            #asyncness fn set_provenance(&self, origins: &[&DecodedOrigin]) {
+                let Some((_, block)) = self.blockdata_decoded.first() else {
+                    return;
+                };
+                let tx_id = origins.iter().find_map(|origin| origin.tx_id);
+
+                #set_provenance
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let config_schema = config_struct.as_ref().map(|config_struct| {
+        let mut config_schema = ConfigSchema::default();
+        for field in config_struct.fields.iter() {
+            let Some(name) = &field.ident else {
+                proc_macro_error::abort_call_site!(
+                    "The #[config] struct must have named fields."
+                )
+            };
+            let Some(typ) = config_field_type(&field.ty) else {
+                proc_macro_error::abort!(
+                    field.ty,
+                    "Unsupported type for config field '{}'.",
+                    name
+                )
+            };
+            config_schema.insert(name.to_string(), typ);
+        }
+        config_schema.to_string()
+    });
+
+    // The config schema is embedded in WASM modules, for the config in the manifest
+    // to be validated before the indexer is deployed, and checked by the `main`
+    // function of native modules.
+    let config_schema = match (manifest.execution_source(), config_schema) {
+        (ExecutionSource::Wasm, Some(config_schema)) => {
+            let len = config_schema.len();
+            let bytes = proc_macro2::Literal::byte_string(config_schema.as_bytes());
+            quote! {
+                #[cfg_attr(target_arch = "wasm32", link_section = #CONFIG_SCHEMA_SECTION)]
+                #[used]
+                static INDEXER_CONFIG_SCHEMA: [u8; #len] = *#bytes;
+            }
+        }
+        (ExecutionSource::Wasm, None) => quote! {},
+        (ExecutionSource::Native, Some(config_schema)) => quote! {
+            const INDEXER_CONFIG_SCHEMA: Option<&str> = Some(#config_schema);
+        },
+        (ExecutionSource::Native, None) => quote! {
+            const INDEXER_CONFIG_SCHEMA: Option<&str> = None;
+        },
+    };
+
     let decoder_struct = quote! {
+        // Where a decoded value came from, which is used to filter the values that
+        // are passed to handlers with a `#[handler(...)]` attribute.
+        #[derive(Debug, Clone, Default)]
+        struct DecodedOrigin {
+            // Contract that emitted the receipt the value was decoded from.
+            contract_id: Option<ContractId>,
+            // Transaction the value was decoded from, if any.
+            tx_id: Option<TxId>,
+            // Index of the transaction the value was decoded from, within its block.
+            tx_index: Option<usize>,
+            // Status of the transaction the value was decoded from, if any.
+            tx_status: Option<fuel::TransactionStatus>,
+            // Index of the receipt the value was decoded from, within its transaction.
+            receipt_index: Option<usize>,
+            // Owners of the coin and message inputs of the transaction.
+            senders: Vec<Address>,
+        }
+
+        impl DecodedOrigin {
+            // Position, as a transaction and receipt index, of the latest receipt that
+            // any of the given values was decoded from.
+            fn latest_position(origins: &[&DecodedOrigin]) -> Option<(usize, usize)> {
+                origins
+                    .iter()
+                    .filter_map(|origin| Some((origin.tx_index?, origin.receipt_index?)))
+                    .max()
+            }
+        }
+
         #[derive(Default)]
         struct Decoders {
-            #(#decoder_struct_fields),*
+            #(#decoder_struct_fields,)*
+            // Origin of the values being decoded.
+            current_origin: DecodedOrigin,
+            // Whether these are the receipts of contracts registered while the block
+            // was dispatched, which are dispatched again after the block's handlers ran.
+            replay: bool,
         }
 
         impl Decoders {
@@ -588,8 +897,29 @@ fn process_fn_items(
                 }
             }
 
+            // Build the context of a handler from the origins of its arguments.
+            pub fn handler_context(&self, origins: &[&DecodedOrigin]) -> HandlerContext {
+                let (_, block) = self
+                    .blockdata_decoded
+                    .first()
+                    .expect("Block must be decoded before its handlers are called.");
+                let origin = origins.iter().find(|origin| origin.tx_id.is_some());
+
+                HandlerContext {
+                    block_height: block.height,
+                    block_id: block.id,
+                    block_time: block.time,
+                    header: block.header.clone(),
+                    tx_id: origin.and_then(|origin| origin.tx_id),
+                    tx_status: origin.and_then(|origin| origin.tx_status.clone()),
+                    receipt_index: origin.and_then(|origin| origin.receipt_index),
+                }
+            }
+
+            #set_provenance_fn
+
             pub fn decode_block(&mut self, data: BlockData) {
-                self.blockdata_decoded.push(data);
+                self.blockdata_decoded.push((DecodedOrigin::default(), data));
             }
 
             pub fn decode_return_type(&mut self, sel: u64, data: Vec<u8>) {
@@ -611,230 +941,339 @@ fn process_fn_items(
                 }
             }
 
-            pub #asyncness fn dispatch(&self) {
+            pub #asyncness fn dispatch(&self) #dispatch_output {
                 #(#abi_dispatchers)*
+
+                if !self.replay {
+                    #(#block_dispatchers)*
+                }
+
+                #dispatch_ok
             }
         }
     };
-    (
-        quote! {
-            #subscribed_contract_ids
+    let reset_provenance = if records_provenance {
+        quote! { decoder.set_provenance(&[])#awaitness; }
+    } else {
+        quote! {}
+    };
 
-            for block in blocks {
+    // Decode the receipts of the block being processed, and dispatch them to the
+    // handlers. `replayed` holds the contracts registered the previous time the block
+    // was dispatched, if any, along with the position of the receipt that registered
+    // each.
+    let decode_and_dispatch = quote! {
+        let mut decoder = Decoders {
+            replay: replayed.is_some(),
+            ..Decoders::default()
+        };
 
-                #start_block
+        let ty_id = BlockData::type_id();
+        let data = serialize(&block);
+        decoder.decode_type(ty_id, data);
 
-                let mut decoder = Decoders::default();
-
-                let ty_id = BlockData::type_id();
-                let data = serialize(&block);
-                decoder.decode_type(ty_id, data);
-
-                for tx in block.transactions {
-
-                    let mut return_types = Vec::new();
-                    let mut callees = HashSet::new();
-
-                    for receipt in tx.receipts {
-                        match receipt {
-                            fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
-                                #check_if_subscribed_to_contract
-
-                                let fn_name = decoder.selector_to_fn_name(param1);
-                                return_types.push(param1);
-                                callees.insert(id);
-
-                                let data = serialize(
-                                    &Call {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
-                                        to: ContractId::from(<[u8; 32]>::from(id)),
-                                        amount,
-                                        asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
-                                        gas,
-                                        fn_name
-                                    }
-                                );
-                                let ty_id = Call::type_id();
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Log { id, ra, rb, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Log::type_id();
-                                let data = serialize(
-                                    &Log {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                        ra,
-                                        rb
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+        for (tx_index, tx) in block.transactions.iter().enumerate() {
+
+            let inputs = match &tx.transaction {
+                fuel::Transaction::Script(script) => script.inputs.as_slice(),
+                fuel::Transaction::Create(create) => create.inputs.as_slice(),
+                fuel::Transaction::Mint(_) => &[],
+            };
+
+            decoder.current_origin = DecodedOrigin {
+                contract_id: None,
+                tx_id: Some(tx.id),
+                tx_index: Some(tx_index),
+                tx_status: Some(tx.status.clone()),
+                receipt_index: None,
+                senders: inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        fuel::Input::Coin(coin) => Some(coin.owner),
+                        fuel::Input::Message(message) => Some(message.sender),
+                        fuel::Input::Contract(_) => None,
+                    })
+                    .collect(),
+            };
+
+            let mut return_types = Vec::new();
+            let mut callees = HashSet::new();
+
+            for (receipt_index, receipt) in tx.receipts.iter().cloned().enumerate() {
+                decoder.current_origin.receipt_index = Some(receipt_index);
+                decoder.current_origin.contract_id = match &receipt {
+                    fuel::Receipt::Call { to, .. } => Some(to),
+                    receipt => receipt.id(),
+                }
+                .map(|id| ContractId::from(<[u8; 32]>::from(*id)));
+
+                // When dispatching the block again, only decode the receipts of the
+                // contracts registered the last time around, that follow the receipt
+                // that registered them. Receipts that aren't filtered by contract
+                // have already been dispatched.
+                if let Some(replayed) = &replayed {
+                    let is_filtered = !matches!(
+                        receipt,
+                        fuel::Receipt::MessageOut { .. }
+                            | fuel::Receipt::ScriptResult { .. }
+                            | fuel::Receipt::Mint { .. }
+                            | fuel::Receipt::Burn { .. }
+                    );
+                    let is_replayed = decoder
+                        .current_origin
+                        .contract_id
+                        .and_then(|id| replayed.get(&id))
+                        .map_or(false, |registered_at| *registered_at < (tx_index, receipt_index));
+                    if !is_filtered || !is_replayed {
+                        continue;
+                    }
+                }
+
+                match receipt {
+                    fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
+                        #check_if_subscribed_to_contract
+
+                        let fn_name = decoder.selector_to_fn_name(param1);
+                        return_types.push(param1);
+                        callees.insert(id);
+
+                        let data = serialize(
+                            &Call {
+                                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
+                                to: ContractId::from(<[u8; 32]>::from(id)),
+                                amount,
+                                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
+                                gas,
+                                fn_name
                             }
-                            fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
-                                #check_if_subscribed_to_contract
-                                decoder.decode_logdata(rb as usize, data.unwrap_or(Vec::<u8>::new()));
+                        );
+                        let ty_id = Call::type_id();
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Log { id, ra, rb, .. } => {
+                        #check_if_subscribed_to_contract
+                        let ty_id = Log::type_id();
+                        let data = serialize(
+                            &Log {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                ra,
+                                rb
                             }
-                            fuel::Receipt::Return { id, val, pc, is } => {
-                                #check_if_subscribed_to_contract
-                                if callees.contains(&id) {
-                                    let ty_id = Return::type_id();
-                                    let data = serialize(
-                                        &Return {
-                                            contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                            val,
-                                            pc,
-                                            is
-                                        }
-                                    );
-                                    decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
+                        #check_if_subscribed_to_contract
+                        decoder.decode_logdata(rb as usize, data.unwrap_or(Vec::<u8>::new()));
+                    }
+                    fuel::Receipt::Return { id, val, pc, is } => {
+                        #check_if_subscribed_to_contract
+                        if callees.contains(&id) {
+                            let ty_id = Return::type_id();
+                            let data = serialize(
+                                &Return {
+                                    contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                    val,
+                                    pc,
+                                    is
                                 }
-                            }
-                            fuel::Receipt::ReturnData { data, id, .. } => {
-                                #check_if_subscribed_to_contract
-                                if callees.contains(&id) {
-                                    let selector = return_types.pop().expect("No return type available. <('-'<)");
-                                    decoder.decode_return_type(selector, data.unwrap_or(Vec::<u8>::new()));
+                            );
+                            decoder.decode_type(ty_id, data);
+                        }
+                    }
+                    fuel::Receipt::ReturnData { data, id, .. } => {
+                        #check_if_subscribed_to_contract
+                        if callees.contains(&id) {
+                            let selector = return_types.pop().expect("No return type available. <('-'<)");
+                            decoder.decode_return_type(selector, data.unwrap_or(Vec::<u8>::new()));
+                        }
+                    }
+                    fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
+                        let sender = Address::from(<[u8; 32]>::from(sender));
+                        let recipient = Address::from(<[u8; 32]>::from(recipient));
+                        let message_id = decoder.compute_message_id(&sender, &recipient, nonce, amount, data.clone());
+
+                        // It's possible that the data field was generated from an empty Sway `Bytes` array
+                        // in the send_message() instruction in which case the data field in the receipt will
+                        // have no type information or data to decode. Thus, we check for a None value or
+                        // an empty byte vector; if either condition is present, then we decode to a unit struct instead.
+                        let (type_id, data) = data
+                            .map_or((u64::MAX, Vec::<u8>::new()), |buffer| {
+                                if buffer.is_empty() {
+                                    (u64::MAX, Vec::<u8>::new())
+                                } else {
+                                    let (type_id_bytes, data_bytes) = buffer.split_at(8);
+                                    let type_id = u64::from_be_bytes(
+                                        <[u8; 8]>::try_from(type_id_bytes)
+                                        .expect("Could not get type ID for data in MessageOut receipt")
+                                    );
+                                    let data = data_bytes.to_vec();
+                                    (type_id, data)
                                 }
+                            });
+
+
+                        decoder.decode_messagedata(type_id, data.clone());
+
+                        let ty_id = MessageOut::type_id();
+                        let data = serialize(
+                            &MessageOut {
+                                message_id,
+                                sender,
+                                recipient,
+                                amount,
+                                nonce,
+                                len,
+                                digest,
+                                data
                             }
-                            fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
-                                let sender = Address::from(<[u8; 32]>::from(sender));
-                                let recipient = Address::from(<[u8; 32]>::from(recipient));
-                                let message_id = decoder.compute_message_id(&sender, &recipient, nonce, amount, data.clone());
-
-                                // It's possible that the data field was generated from an empty Sway `Bytes` array
-                                // in the send_message() instruction in which case the data field in the receipt will
-                                // have no type information or data to decode. Thus, we check for a None value or
-                                // an empty byte vector; if either condition is present, then we decode to a unit struct instead.
-                                let (type_id, data) = data
-                                    .map_or((u64::MAX, Vec::<u8>::new()), |buffer| {
-                                        if buffer.is_empty() {
-                                            (u64::MAX, Vec::<u8>::new())
-                                        } else {
-                                            let (type_id_bytes, data_bytes) = buffer.split_at(8);
-                                            let type_id = u64::from_be_bytes(
-                                                <[u8; 8]>::try_from(type_id_bytes)
-                                                .expect("Could not get type ID for data in MessageOut receipt")
-                                            );
-                                            let data = data_bytes.to_vec();
-                                            (type_id, data)
-                                        }
-                                    });
-
-
-                                decoder.decode_messagedata(type_id, data.clone());
-
-                                let ty_id = MessageOut::type_id();
-                                let data = serialize(
-                                    &MessageOut {
-                                        message_id,
-                                        sender,
-                                        recipient,
-                                        amount,
-                                        nonce,
-                                        len,
-                                        digest,
-                                        data
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::ScriptResult { result, gas_used } => {
-                                let ty_id = ScriptResult::type_id();
-                                let data = serialize(&ScriptResult{ result: u64::from(result), gas_used });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Transfer::type_id();
-                                let data = serialize(
-                                    &Transfer {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                        to: ContractId::from(<[u8; 32]>::from(to)),
-                                        asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
-                                        amount,
-                                        pc,
-                                        is
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = TransferOut::type_id();
-                                let data = serialize(
-                                    &TransferOut {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                        to: Address::from(<[u8; 32]>::from(to)),
-                                        asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
-                                        amount,
-                                        pc,
-                                        is
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::ScriptResult { result, gas_used } => {
+                        let ty_id = ScriptResult::type_id();
+                        let data = serialize(&ScriptResult{ result: u64::from(result), gas_used });
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
+                        #check_if_subscribed_to_contract
+                        let ty_id = Transfer::type_id();
+                        let data = serialize(
+                            &Transfer {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                to: ContractId::from(<[u8; 32]>::from(to)),
+                                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
+                                amount,
+                                pc,
+                                is
                             }
-                            fuel::Receipt::Panic { id, reason, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Panic::type_id();
-                                let data = serialize(
-                                    &Panic {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                        reason: *reason.reason() as u32
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
+                        #check_if_subscribed_to_contract
+                        let ty_id = TransferOut::type_id();
+                        let data = serialize(
+                            &TransferOut {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                to: Address::from(<[u8; 32]>::from(to)),
+                                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
+                                amount,
+                                pc,
+                                is
                             }
-                            fuel::Receipt::Revert { id, ra, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Revert::type_id();
-                                let data = serialize(
-                                    &Revert {
-                                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                        error_val: u64::from(ra & 0xF)
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Panic { id, reason, .. } => {
+                        #check_if_subscribed_to_contract
+                        let ty_id = Panic::type_id();
+                        let data = serialize(
+                            &Panic {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                reason: *reason.reason() as u32
                             }
-                            fuel::Receipt::Mint { sub_id, contract_id, val, pc, is } => {
-                                let ty_id = Mint::type_id();
-                                let data = serialize(
-                                    &Mint {
-                                        sub_id: AssetId::from(<[u8; 32]>::from(sub_id)),
-                                        contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
-                                        val,
-                                        pc,
-                                        is
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Revert { id, ra, .. } => {
+                        #check_if_subscribed_to_contract
+                        let ty_id = Revert::type_id();
+                        let data = serialize(
+                            &Revert {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                error_val: u64::from(ra & 0xF)
                             }
-                            fuel::Receipt::Burn { sub_id, contract_id, val, pc, is } => {
-                                let ty_id = Burn::type_id();
-                                let data = serialize(
-                                    &Burn {
-                                        sub_id: AssetId::from(<[u8; 32]>::from(sub_id)),
-                                        contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
-                                        val,
-                                        pc,
-                                        is
-                                    }
-                                );
-                                decoder.decode_type(ty_id, data);
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Mint { sub_id, contract_id, val, pc, is } => {
+                        let ty_id = Mint::type_id();
+                        let data = serialize(
+                            &Mint {
+                                sub_id: AssetId::from(<[u8; 32]>::from(sub_id)),
+                                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
+                                val,
+                                pc,
+                                is
                             }
-                            _ => {
-                                info!("This type is not handled yet. (>'.')>");
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    fuel::Receipt::Burn { sub_id, contract_id, val, pc, is } => {
+                        let ty_id = Burn::type_id();
+                        let data = serialize(
+                            &Burn {
+                                sub_id: AssetId::from(<[u8; 32]>::from(sub_id)),
+                                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
+                                val,
+                                pc,
+                                is
                             }
-                        }
+                        );
+                        decoder.decode_type(ty_id, data);
+                    }
+                    _ => {
+                        info!("This type is not handled yet. (>'.')>");
                     }
                 }
-                decoder.dispatch()#awaitness;
+            }
+        }
+        #reset_provenance
+
+        decoder.dispatch()#awaitness #try_op;
+    };
+
+    let dispatch_block = match &manifest.contract_id() {
+        ContractIds::Single(None) => quote! {
+            let replayed: Option<HashMap<ContractId, (usize, usize)>> = None;
+
+            #decode_and_dispatch
+        },
+        // Contracts registered by a handler take effect from the next receipt, so the
+        // receipts that they emit later in the same block are dispatched again.
+        _ => quote! {
+            let mut replayed: Option<HashMap<ContractId, (usize, usize)>> = None;
+
+            loop {
+                #decode_and_dispatch
+
+                let registered = unsafe { registered_contract_ids.take() }.unwrap_or_default();
+                if registered.is_empty() {
+                    break;
+                }
+                replayed = Some(registered.into_iter().collect());
+            }
+        },
+    };
+
+    (
+        quote! {
+            #subscribed_contract_ids
+
+            for block in blocks {
+
+                #start_block
+
+                // Forget the contracts registered in an earlier block, or in a batch
+                // that failed.
+                unsafe {
+                    registered_contract_ids = None;
+                }
+
+                #dispatch_block
 
                 let metadata = IndexMetadataEntity::new(block.time as u64, block.header.height, block.id);
-                metadata.save()#awaitness;
+                metadata.save()#awaitness #try_op;
             }
         },
         quote! {
             #decoder_struct
 
+            #config_struct
+
+            #config_schema
+
             #(#handler_fns)*
         },
     )
@@ -923,7 +1362,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
     };
 
     // NOTE: https://nickb.dev/blog/cargo-workspace-and-the-feature-unification-pitfall/
-    let graphql_tokens = process_graphql_schema(
+    let (graphql_tokens, schema) = process_graphql_schema(
         manifest.namespace(),
         manifest.identifier(),
         &schema_string,
@@ -933,7 +1372,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
     let output = match manifest.execution_source() {
         ExecutionSource::Native => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, &schema, abi, indexer_module);
             let handler_block = handler_block_native(handler_block);
             let naitve_main_tokens = native_main();
 
@@ -952,7 +1391,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
         }
         ExecutionSource::Wasm => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, &schema, abi, indexer_module);
             let handler_block = handler_block_wasm(handler_block);
             quote! {
 
@@ -1,9 +1,13 @@
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{LitStr, Token};
+use syn::{LitInt, LitStr, Token};
 
 mod kw {
     syn::custom_keyword!(manifest);
+    syn::custom_keyword!(contract);
+    syn::custom_keyword!(status);
+    syn::custom_keyword!(sender);
+    syn::custom_keyword!(every);
 }
 
 pub(crate) struct IndexerConfig {
@@ -80,3 +84,100 @@ impl Parse for Manifest {
         Ok(Manifest { name })
     }
 }
+
+/// Transaction status a handler can be restricted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HandlerStatus {
+    Success,
+    Failure,
+}
+
+/// Filters specified on a handler function with `#[handler(...)]`.
+#[derive(Debug, Default)]
+pub(crate) struct HandlerConfig {
+    /// Contract that must have emitted the handler's arguments.
+    pub(crate) contract: Option<LitStr>,
+
+    /// Status of the transaction that emitted the handler's arguments.
+    pub(crate) status: Option<HandlerStatus>,
+
+    /// Owner of an input of the transaction that emitted the handler's arguments.
+    pub(crate) sender: Option<LitStr>,
+}
+
+impl Parse for HandlerConfig {
+    fn parse(input: ParseStream) -> syn::Result<HandlerConfig> {
+        let mut config = HandlerConfig::default();
+
+        let items = Punctuated::<HandlerConfigItem, Token![,]>::parse_terminated(input)?;
+
+        for item in items {
+            match item {
+                HandlerConfigItem::Contract(s) => config.contract = Some(s),
+                HandlerConfigItem::Status(s) => config.status = Some(s),
+                HandlerConfigItem::Sender(s) => config.sender = Some(s),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+enum HandlerConfigItem {
+    Contract(LitStr),
+    Status(HandlerStatus),
+    Sender(LitStr),
+}
+
+impl Parse for HandlerConfigItem {
+    fn parse(input: ParseStream) -> syn::Result<HandlerConfigItem> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(kw::contract) {
+            let _: kw::contract = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(HandlerConfigItem::Contract(input.parse()?))
+        } else if lookahead.peek(kw::status) {
+            let _: kw::status = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let value: LitStr = input.parse()?;
+            match value.value().as_str() {
+                "success" => Ok(HandlerConfigItem::Status(HandlerStatus::Success)),
+                "failure" => Ok(HandlerConfigItem::Status(HandlerStatus::Failure)),
+                _ => Err(syn::Error::new(
+                    value.span(),
+                    "`status` must be either \"success\" or \"failure\".",
+                )),
+            }
+        } else if lookahead.peek(kw::sender) {
+            let _: kw::sender = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(HandlerConfigItem::Sender(input.parse()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// Configuration of a handler function with `#[interval_handler(...)]`.
+pub(crate) struct IntervalHandlerConfig {
+    /// Number of blocks between each execution of the handler.
+    pub(crate) every: u32,
+}
+
+impl Parse for IntervalHandlerConfig {
+    fn parse(input: ParseStream) -> syn::Result<IntervalHandlerConfig> {
+        let _: kw::every = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let every: LitInt = input.parse()?;
+        let _: Option<Token![,]> = input.parse()?;
+
+        match every.base10_parse::<u32>()? {
+            0 => Err(syn::Error::new(
+                every.span(),
+                "`every` must be greater than zero.",
+            )),
+            every => Ok(IntervalHandlerConfig { every }),
+        }
+    }
+}
@@ -51,5 +51,47 @@ fn wasm_prelude() -> proc_macro2::TokenStream {
             core::{codec::ABIDecoder, Configurables, traits::{Parameterize, Tokenizable}},
             types::{StringToken, param_types::ParamType},
         };
+        static mut tracked_contract_ids: Option<HashSet<ContractId>> = None;
+
+        // Position, as a transaction and receipt index, of the latest receipt that the
+        // handler being called was passed a value from.
+        static mut current_receipt: Option<(usize, usize)> = None;
+
+        // Contract IDs registered while dispatching the current block, along with the
+        // position of the receipt whose handler registered them.
+        static mut registered_contract_ids: Option<Vec<(ContractId, (usize, usize))>> = None;
+
+        /// Track the given contract ID, so that its receipts are dispatched to
+        /// this indexer's handlers from the receipt after the one being handled onward.
+        pub fn register_contract(contract_id: &ContractId) {
+            save_contract_id(contract_id);
+            unsafe {
+                track_contract(contract_id);
+            }
+        }
+
+        /// Add the given contract ID to the tracked ones. If it is registered by the
+        /// handler of a receipt, the receipts it emits later in the same block are
+        /// dispatched again once the block's handlers have run.
+        unsafe fn track_contract(contract_id: &ContractId) {
+            let is_new = tracked_contract_ids
+                .get_or_insert_with(HashSet::new)
+                .insert(*contract_id);
+            if let (true, Some(position)) = (is_new, current_receipt) {
+                registered_contract_ids
+                    .get_or_insert_with(Vec::new)
+                    .push((*contract_id, position));
+            }
+        }
+
+        /// Whether the given contract ID was registered by one of this indexer's handlers.
+        #[allow(dead_code)]
+        fn is_tracked_contract(contract_id: &ContractId) -> bool {
+            unsafe {
+                tracked_contract_ids
+                    .as_ref()
+                    .map_or(false, |ids| ids.contains(contract_id))
+            }
+        }
     }
 }
@@ -2,7 +2,7 @@ use lazy_static::lazy_static;
 use prometheus::{self, register_int_counter, IntCounter};
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::{family::Family, histogram::Histogram},
+    metrics::{counter::Counter, family::Family, gauge::Gauge, histogram::Histogram},
     registry::Registry,
 };
 
@@ -101,9 +101,204 @@ impl Web {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct IndexerLabel {
+    namespace: String,
+    identifier: String,
+}
+
+impl IndexerLabel {
+    pub fn new(namespace: &str, identifier: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct EntityLabel {
+    namespace: String,
+    identifier: String,
+    entity: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ErrorLabel {
+    namespace: String,
+    identifier: String,
+    kind: String,
+}
+
+/// Metrics recorded for each indexer that has metrics enabled in its manifest.
+pub struct Indexer {
+    pub registry: Registry,
+    blocks_processed: Family<IndexerLabel, Counter>,
+    block_height: Family<IndexerLabel, Gauge>,
+    blocks_behind_head: Family<IndexerLabel, Gauge>,
+    handler_duration: Family<IndexerLabel, Histogram>,
+    metering_points_used: Family<IndexerLabel, Counter>,
+    entity_writes: Family<EntityLabel, Counter>,
+    entity_cache_hits: Family<EntityLabel, Counter>,
+    entity_cache_misses: Family<EntityLabel, Counter>,
+    errors: Family<ErrorLabel, Counter>,
+}
+
+impl Metric for Indexer {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+
+        let blocks_processed = Family::<IndexerLabel, Counter>::default();
+        registry.register(
+            "indexer_blocks_processed",
+            "Count of blocks processed by the indexer.",
+            blocks_processed.clone(),
+        );
+
+        let block_height = Family::<IndexerLabel, Gauge>::default();
+        registry.register(
+            "indexer_block_height",
+            "Height of the last block processed by the indexer.",
+            block_height.clone(),
+        );
+
+        let blocks_behind_head = Family::<IndexerLabel, Gauge>::default();
+        registry.register(
+            "indexer_blocks_behind_head",
+            "Number of blocks between the indexer and the head of the chain.",
+            blocks_behind_head.clone(),
+        );
+
+        let handler_duration =
+            Family::<IndexerLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(TIMING_HISTOGRAM_BUCKETS.iter().cloned())
+            });
+        registry.register(
+            "indexer_handler_duration",
+            "Duration (ms) of calls to the indexer's handler.",
+            handler_duration.clone(),
+        );
+
+        let metering_points_used = Family::<IndexerLabel, Counter>::default();
+        registry.register(
+            "indexer_metering_points_used",
+            "Count of WASM metering points used by the indexer.",
+            metering_points_used.clone(),
+        );
+
+        let entity_writes = Family::<EntityLabel, Counter>::default();
+        registry.register(
+            "indexer_entity_writes",
+            "Count of entities written by the indexer, by entity type.",
+            entity_writes.clone(),
+        );
+
+        let entity_cache_hits = Family::<EntityLabel, Counter>::default();
+        registry.register(
+            "indexer_entity_cache_hits",
+            "Count of entity loads served from the indexer's entity cache, by entity type.",
+            entity_cache_hits.clone(),
+        );
+
+        let entity_cache_misses = Family::<EntityLabel, Counter>::default();
+        registry.register(
+            "indexer_entity_cache_misses",
+            "Count of entity loads that missed the indexer's entity cache, by entity type.",
+            entity_cache_misses.clone(),
+        );
+
+        let errors = Family::<ErrorLabel, Counter>::default();
+        registry.register(
+            "indexer_errors",
+            "Count of errors encountered by the indexer, by kind.",
+            errors.clone(),
+        );
+
+        Self {
+            registry,
+            blocks_processed,
+            block_height,
+            blocks_behind_head,
+            handler_duration,
+            metering_points_used,
+            entity_writes,
+            entity_cache_hits,
+            entity_cache_misses,
+            errors,
+        }
+    }
+}
+
+impl Indexer {
+    /// Record a batch of processed blocks, ending at `height`.
+    pub fn record_blocks(&self, label: &IndexerLabel, count: u64, height: u32) {
+        self.blocks_processed.get_or_create(label).inc_by(count);
+        self.block_height.get_or_create(label).set(height as i64);
+    }
+
+    /// Record the distance between the indexer's last processed block and the chain head.
+    pub fn record_blocks_behind_head(&self, label: &IndexerLabel, blocks: u32) {
+        self.blocks_behind_head
+            .get_or_create(label)
+            .set(blocks as i64);
+    }
+
+    pub fn record_handler_duration(&self, label: &IndexerLabel, time: f64) {
+        self.handler_duration.get_or_create(label).observe(time);
+    }
+
+    pub fn record_metering_points_used(&self, label: &IndexerLabel, points: u64) {
+        self.metering_points_used
+            .get_or_create(label)
+            .inc_by(points);
+    }
+
+    pub fn record_entity_write(&self, label: &IndexerLabel, entity: &str) {
+        self.entity_writes
+            .get_or_create(&EntityLabel {
+                namespace: label.namespace.clone(),
+                identifier: label.identifier.clone(),
+                entity: entity.to_string(),
+            })
+            .inc();
+    }
+
+    /// Record a load of the given entity, and whether it was served from the cache.
+    pub fn record_entity_cache_lookup(
+        &self,
+        label: &IndexerLabel,
+        entity: &str,
+        hit: bool,
+    ) {
+        let counter = if hit {
+            &self.entity_cache_hits
+        } else {
+            &self.entity_cache_misses
+        };
+        counter
+            .get_or_create(&EntityLabel {
+                namespace: label.namespace.clone(),
+                identifier: label.identifier.clone(),
+                entity: entity.to_string(),
+            })
+            .inc();
+    }
+
+    pub fn record_error(&self, label: &IndexerLabel, kind: &str) {
+        self.errors
+            .get_or_create(&ErrorLabel {
+                namespace: label.namespace.clone(),
+                identifier: label.identifier.clone(),
+                kind: kind.to_string(),
+            })
+            .inc();
+    }
+}
+
 pub struct Metrics {
     pub web: Web,
     pub db: Database,
+    pub indexer: Indexer,
 }
 
 impl Metric for Metrics {
@@ -111,6 +306,43 @@ impl Metric for Metrics {
         Self {
             web: Web::init(),
             db: Database::init(),
+            indexer: Indexer::init(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus_client::encoding::text::encode;
+
+    #[test]
+    fn test_indexer_metrics_are_labeled_per_indexer() {
+        let metrics = Indexer::init();
+        let label = IndexerLabel::new("fuel_indexer_test", "index1");
+
+        metrics.record_blocks(&label, 10, 110);
+        metrics.record_blocks_behind_head(&label, 5);
+        metrics.record_entity_write(&label, "pingentity");
+        metrics.record_error(&label, "sqlx_error");
+
+        let mut encoded = String::new();
+        encode(&mut encoded, &metrics.registry).unwrap();
+
+        assert!(encoded.contains(
+            r#"indexer_blocks_processed_total{namespace="fuel_indexer_test",identifier="index1"} 10"#
+        ));
+        assert!(encoded.contains(
+            r#"indexer_block_height{namespace="fuel_indexer_test",identifier="index1"} 110"#
+        ));
+        assert!(encoded.contains(
+            r#"indexer_blocks_behind_head{namespace="fuel_indexer_test",identifier="index1"} 5"#
+        ));
+        assert!(encoded.contains(
+            r#"indexer_entity_writes_total{namespace="fuel_indexer_test",identifier="index1",entity="pingentity"} 1"#
+        ));
+        assert!(encoded.contains(
+            r#"indexer_errors_total{namespace="fuel_indexer_test",identifier="index1",kind="sqlx_error"} 1"#
+        ));
+    }
+}
@@ -7,10 +7,11 @@ use fuel_indexer_schema::{join::JoinMetadata, FtColumn};
 pub use anyhow;
 pub use fuel_indexer::prelude::{
     Arc, Database, IndexerError, IndexerResult, IndexerService, Mutex,
-    NativeIndexExecutor,
+    NativeIndexExecutor, Provenance,
 };
 pub use fuel_indexer_api_server::api::WebApi;
 pub use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_lib::utils::{deserialize, serialize};
 pub use fuel_indexer_lib::{
     config::{cli::Parser, IndexerArgs, IndexerConfig},
     defaults::SERVICE_REQUEST_CHANNEL_SIZE,
@@ -19,6 +20,7 @@ pub use fuel_indexer_lib::{
     utils::{init_logging, ServiceRequest},
 };
 use fuel_indexer_types::scalar::UID;
+use serde::{de::DeserializeOwned, Serialize};
 pub use sha2::{Digest, Sha256};
 pub use std::{
     collections::{HashMap, HashSet},
@@ -53,22 +55,153 @@ impl Logger {
     }
 }
 
+/// Database of the indexer, used by `State` and `Config`. Set by the indexer's
+/// `handle_events`.
+static INDEXER_DB: std::sync::RwLock<Option<Arc<Mutex<Database>>>> =
+    std::sync::RwLock::new(None);
+
+#[doc(hidden)]
+pub fn set_indexer_db(db: Arc<Mutex<Database>>) {
+    *INDEXER_DB.write().expect("Indexer database lock poisoned.") = Some(db);
+}
+
+fn indexer_db() -> Option<Arc<Mutex<Database>>> {
+    INDEXER_DB
+        .read()
+        .expect("Indexer database lock poisoned.")
+        .clone()
+}
+
+/// Return the indexer's database, or an error if it has not been set, e.g., when
+/// a handler is called outside of the indexer service.
+fn require_indexer_db() -> IndexerResult<Arc<Mutex<Database>>> {
+    indexer_db().ok_or_else(|| {
+        IndexerError::NativeExecutionRuntimeError(
+            "Indexer database has not been set".to_string(),
+        )
+    })
+}
+
+/// Key-value store for small pieces of indexer state, such as running totals.
+///
+/// Values are written in the same transaction as the entities saved while a block
+/// is being processed, and are reverted along with them.
+pub struct State;
+
+impl State {
+    /// Return the value stored under the given key, if any.
+    pub async fn get<T: DeserializeOwned>(key: &str) -> IndexerResult<Option<T>> {
+        let Some(db) = indexer_db() else {
+            return Ok(None);
+        };
+        let bytes = db.lock().await.get_state(key).await?;
+        match bytes {
+            Some(bytes) => deserialize(&bytes).map(Some).map_err(IndexerError::Unknown),
+            None => Ok(None),
+        }
+    }
+
+    /// Store the given value under the given key, replacing any previous value.
+    ///
+    /// Returns an error if the indexer's database has not been set.
+    pub async fn set<T: Serialize>(key: &str, value: &T) -> IndexerResult<()> {
+        let db = require_indexer_db()?;
+        db.lock().await.put_state(key, &serialize(value)).await?;
+        Ok(())
+    }
+
+    /// Remove the value stored under the given key.
+    ///
+    /// Returns an error if the indexer's database has not been set.
+    pub async fn delete(key: &str) -> IndexerResult<()> {
+        let db = require_indexer_db()?;
+        db.lock().await.delete_state(key).await?;
+        Ok(())
+    }
+}
+
+/// Deployment-specific configuration of the indexer, from the `config` section
+/// of its manifest.
+pub struct Config;
+
+impl Config {
+    /// Return the indexer's config as a value of type `T`.
+    ///
+    /// Returns an `InvalidConfig` error if the config does not match `T`.
+    pub async fn get<T: DeserializeOwned>() -> IndexerResult<T> {
+        let config = match indexer_db() {
+            Some(db) => db.lock().await.runtime_config().map(str::to_string),
+            None => None,
+        };
+
+        serde_json::from_str(config.as_deref().unwrap_or("null"))
+            .map_err(|e| IndexerError::InvalidConfig(e.to_string()))
+    }
+}
+
+/// Any entity type that will be processed through a native indexer is required to
+/// implement this trait.
+///
+/// Errors are returned to the handler, which can propagate them with `?` to stop
+/// the indexer and revert the block's changes.
 #[async_trait::async_trait]
-pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
+pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug + Send + Sync {
+    /// Unique identifier for a type.
     const TYPE_ID: i64;
+
+    /// Necessary metadata for saving an entity's list type fields.
     const JOIN_METADATA: Option<[Option<JoinMetadata<'a>>; MAX_FOREIGN_KEY_LIST_FIELDS]>;
 
-    async fn save_many_to_many(&self);
+    /// Saves a record that contains a list of multiple elements.
+    async fn save_many_to_many(&self) -> IndexerResult<()>;
 
+    /// Convert database row representation into an instance of an entity.
     fn from_row(vec: Vec<FtColumn>) -> Self;
 
+    /// Convert an instance of an entity into row representation for use in a database.
     fn to_row(&self) -> Vec<FtColumn>;
 
+    /// Returns an entity's internal type ID.
     fn type_id(&self) -> i64 {
         Self::TYPE_ID
     }
 
-    async fn load(id: UID) -> Option<Self>;
+    /// Loads a record given a UID.
+    async fn load(id: UID) -> IndexerResult<Option<Self>>;
+
+    /// Loads the records whose `field` is equal to `value`.
+    async fn find_many(field: &str, value: FtColumn) -> IndexerResult<Vec<Self>> {
+        let Some(db) = indexer_db() else {
+            return Ok(Vec::new());
+        };
+        let objects = db
+            .lock()
+            .await
+            .find_objects(Self::TYPE_ID, field, &value)
+            .await?;
+
+        objects
+            .iter()
+            .map(|bytes| {
+                deserialize(bytes)
+                    .map(Self::from_row)
+                    .map_err(IndexerError::Unknown)
+            })
+            .collect()
+    }
+
+    /// Saves a record.
+    async fn save(&self) -> IndexerResult<()>;
 
-    async fn save(&self);
+    /// Deletes a record. Returns `false` if the record had not been saved.
+    async fn delete(&self) -> IndexerResult<bool> {
+        let id = match self.to_row().first() {
+            Some(FtColumn::ID(Some(id))) => id.to_string(),
+            _ => return Err(IndexerError::Unknown(format!("{self:?} has no ID"))),
+        };
+        match indexer_db() {
+            Some(db) => db.lock().await.delete_object(Self::TYPE_ID, id).await,
+            None => Ok(false),
+        }
+    }
 }
@@ -9,7 +9,12 @@ use fuel_indexer_schema::{
     join::{JoinMetadata, RawQuery},
     FtColumn,
 };
-use fuel_indexer_types::{ffi::*, scalar::UID};
+use fuel_indexer_types::{
+    ffi::*,
+    fuel::TxId,
+    scalar::{ContractId, UID},
+};
+use serde::{de::DeserializeOwned, Serialize};
 
 pub use bincode;
 pub use hex::FromHex;
@@ -23,8 +28,17 @@ extern "C" {
     fn ff_get_object(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
+    fn ff_find_many(type_id: i64, ptr: *const u8, len: u32, len_ptr: *mut u8) -> *mut u8;
+    fn ff_delete_object(type_id: i64, ptr: *const u8, len: u32) -> u32;
     fn ff_put_many_to_many_record(ptr: *const u8, len: u32);
     fn ff_early_exit(err_code: u32);
+    fn ff_set_provenance(block_height: u32, block_time: i64, ptr: *const u8, len: u32);
+    fn ff_register_contract(ptr: *const u8, len: u32);
+    fn ff_get_contract_ids(len: *mut u8) -> *mut u8;
+    fn ff_get_state(ptr: *const u8, len: u32, value_len: *mut u8) -> *mut u8;
+    fn ff_put_state(key_ptr: *const u8, key_len: u32, ptr: *const u8, len: u32);
+    fn ff_delete_state(ptr: *const u8, len: u32);
+    fn ff_get_config(len: *mut u8) -> *mut u8;
 }
 
 // TODO: more to do here, hook up to 'impl log::Log for Logger'
@@ -52,6 +66,123 @@ impl Logger {
     }
 }
 
+/// Set the block and transaction being processed, which are recorded in the
+/// provenance columns of `@provenance` entities saved from here on.
+pub fn set_provenance(block_height: u32, block_time: i64, tx_id: Option<&TxId>) {
+    let tx_id = tx_id.map(|id| format!("{id:x}")).unwrap_or_default();
+    unsafe {
+        ff_set_provenance(block_height, block_time, tx_id.as_ptr(), tx_id.len() as u32)
+    }
+}
+
+/// Persist the given contract ID as one tracked by the indexer.
+pub fn save_contract_id(contract_id: &ContractId) {
+    let contract_id = format!("{contract_id:x}");
+    unsafe { ff_register_contract(contract_id.as_ptr(), contract_id.len() as u32) }
+}
+
+/// Load the contract IDs tracked by the indexer.
+pub fn load_contract_ids() -> Vec<ContractId> {
+    unsafe {
+        let mut bufflen = 0u32.to_le_bytes();
+        let ptr = ff_get_contract_ids(bufflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return Vec::new();
+        }
+
+        let len = u32::from_le_bytes(bufflen) as usize;
+        let bytes = Vec::from_raw_parts(ptr, len, len);
+        match bincode::deserialize::<Vec<String>>(&bytes) {
+            Ok(ids) => ids
+                .iter()
+                .map(|id| {
+                    id.parse::<ContractId>().unwrap_or_else(|_| {
+                        early_exit(WasmIndexerError::DeserializationError)
+                    })
+                })
+                .collect(),
+            Err(_) => early_exit(WasmIndexerError::DeserializationError),
+        }
+    }
+}
+
+/// Key-value store for small pieces of indexer state, such as running totals.
+///
+/// Values are written in the same transaction as the entities saved while a block
+/// is being processed, and are reverted along with them.
+pub struct State;
+
+impl State {
+    /// Return the value stored under the given key, if any.
+    pub fn get<T: DeserializeOwned>(key: &str) -> Option<T> {
+        unsafe {
+            let mut bufflen = 0u32.to_le_bytes();
+            let ptr = ff_get_state(key.as_ptr(), key.len() as u32, bufflen.as_mut_ptr());
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            let len = u32::from_le_bytes(bufflen) as usize;
+            let bytes = Vec::from_raw_parts(ptr, len, len);
+            match deserialize(&bytes) {
+                Ok(value) => Some(value),
+                Err(_) => early_exit(WasmIndexerError::DeserializationError),
+            }
+        }
+    }
+
+    /// Store the given value under the given key, replacing any previous value.
+    pub fn set<T: Serialize>(key: &str, value: &T) {
+        let bytes = serialize(value);
+        unsafe {
+            ff_put_state(
+                key.as_ptr(),
+                key.len() as u32,
+                bytes.as_ptr(),
+                bytes.len() as u32,
+            )
+        }
+    }
+
+    /// Remove the value stored under the given key.
+    pub fn delete(key: &str) {
+        unsafe { ff_delete_state(key.as_ptr(), key.len() as u32) }
+    }
+}
+
+/// Deployment-specific configuration of the indexer, from the `config` section
+/// of its manifest.
+pub struct Config;
+
+impl Config {
+    /// Return the indexer's config as a value of type `T`.
+    ///
+    /// Execution is terminated if the config does not match `T`.
+    pub fn get<T: DeserializeOwned>() -> T {
+        let bytes = unsafe {
+            let mut bufflen = 0u32.to_le_bytes();
+            let ptr = ff_get_config(bufflen.as_mut_ptr());
+
+            if ptr.is_null() {
+                b"null".to_vec()
+            } else {
+                let len = u32::from_le_bytes(bufflen) as usize;
+                Vec::from_raw_parts(ptr, len, len)
+            }
+        };
+
+        match serde_json::from_slice(&bytes) {
+            Ok(config) => config,
+            Err(e) => {
+                Logger::error(&format!("Invalid indexer config: {e}"));
+                early_exit(WasmIndexerError::InvalidConfig)
+            }
+        }
+    }
+}
+
 /// Trait for a type entity.
 ///
 /// Any entity type that will be processed through a WASM indexer is required to implement this trait.
@@ -123,11 +254,54 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
         }
     }
 
+    /// Loads the records whose `field` is equal to `value`.
+    fn find_many(field: &str, value: FtColumn) -> Vec<Self> {
+        unsafe {
+            let buff = serialize(&(field.to_string(), value));
+            let mut bufflen = 0u32.to_le_bytes();
+
+            let ptr = ff_find_many(
+                Self::TYPE_ID,
+                buff.as_ptr(),
+                buff.len() as u32,
+                bufflen.as_mut_ptr(),
+            );
+
+            if ptr.is_null() {
+                return Vec::new();
+            }
+
+            let len = u32::from_le_bytes(bufflen) as usize;
+            let bytes = Vec::from_raw_parts(ptr, len, len);
+            let objects: Vec<Vec<u8>> = match deserialize(&bytes) {
+                Ok(objects) => objects,
+                Err(_) => early_exit(WasmIndexerError::DeserializationError),
+            };
+
+            objects
+                .iter()
+                .map(|bytes| match deserialize(bytes) {
+                    Ok(vec) => Self::from_row(vec),
+                    Err(_) => early_exit(WasmIndexerError::DeserializationError),
+                })
+                .collect()
+        }
+    }
+
     /// Saves a record.
     fn save(&self) {
         self.save_unsafe()
     }
 
+    /// Deletes a record. Returns `false` if the record had not been saved.
+    fn delete(&self) -> bool {
+        let id = match self.to_row().first() {
+            Some(FtColumn::ID(Some(id))) => id.to_string(),
+            _ => early_exit(WasmIndexerError::GeneralError),
+        };
+        unsafe { ff_delete_object(Self::TYPE_ID, id.as_ptr(), id.len() as u32) != 0 }
+    }
+
     /// Saves a record through the FFI with the WASM runtime and checks for errors.
     fn save_unsafe(&self) {
         unsafe {
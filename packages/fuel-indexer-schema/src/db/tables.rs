@@ -206,12 +206,15 @@ impl IndexerSchema {
         identifier: &str,
     ) -> IndexerSchemaDbResult<Self> {
         let mut conn = pool.acquire().await?;
-        let root = queries::graph_root_latest(&mut conn, namespace, identifier).await?;
 
-        let indexer_id =
-            queries::get_indexer_id(&mut conn, namespace, identifier).await?;
+        // If the indexer has blue/green deployments, the schema of the active one is loaded.
+        let indexer =
+            queries::get_serving_indexer(&mut conn, namespace, identifier).await?;
+        let identifier = indexer.identifier.as_str();
+
+        let root = queries::graph_root_latest(&mut conn, namespace, identifier).await?;
         let IndexerAsset { bytes, .. } =
-            queries::indexer_asset(&mut conn, &indexer_id, IndexerAssetType::Manifest)
+            queries::indexer_asset(&mut conn, &indexer.id, IndexerAssetType::Manifest)
                 .await?;
         let manifest = Manifest::try_from(&bytes)?;
 
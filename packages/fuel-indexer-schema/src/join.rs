@@ -24,6 +24,9 @@ pub struct JoinMetadata<'a> {
     pub child_position: usize,
 }
 
+/// Start of every query rendered by `RawQuery::from_metadata`.
+const INSERT_INTO: &str = "INSERT INTO ";
+
 /// A raw SQL query.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct RawQuery(pub String);
@@ -44,6 +47,20 @@ impl RawQuery {
         self.0.is_empty()
     }
 
+    /// Point the query at the join table in the given namespace.
+    ///
+    /// The namespace is compiled into the module that rendered the query, so the
+    /// records of a blue/green deployment have to be redirected to its own tables.
+    pub fn set_namespace(&mut self, namespace: &str) {
+        if let Some((_, table)) = self
+            .0
+            .strip_prefix(INSERT_INTO)
+            .and_then(|query| query.split_once('.'))
+        {
+            self.0 = format!("{INSERT_INTO}{namespace}.{table}");
+        }
+    }
+
     /// Create a new `RawQuery` from the given metadata and columns.
     pub fn from_metadata(metadata: &JoinMetadata<'_>, columns: &[FtColumn]) -> Self {
         let JoinMetadata {
@@ -57,7 +74,7 @@ impl RawQuery {
         let (parent_typedef_name, child_typedef_name) =
             join_table_typedefs_name(table_name);
         let mut query = format!(
-            "INSERT INTO {namespace}.{table_name} ({parent_typedef_name}_{parent_column_name}, {child_typedef_name}_{child_column_name}) VALUES"
+            "{INSERT_INTO}{namespace}.{table_name} ({parent_typedef_name}_{parent_column_name}, {child_typedef_name}_{child_column_name}) VALUES"
         );
 
         let id_index: usize = columns
@@ -5,6 +5,12 @@ use fuel_indexer_utils::prelude::*;
 )]
 mod fuel_indexer_test {
 
+    #[config]
+    #[derive(serde::Deserialize)]
+    struct TestConfig {
+        ping_count_key: String,
+    }
+
     fn fuel_indexer_test_blocks(block_data: BlockData) {
         let block = BlockEntity::new(block_data.height, block_data.time).get_or_create();
 
@@ -16,10 +22,25 @@ mod fuel_indexer_test {
         }
     }
 
-    fn fuel_indexer_test_ping(ping: Ping) {
+    #[handler(
+        contract = "fuel1h97g4w7wrwv78ad5xquxs3ecl30xstuqgzsw4uvpakg7yyh8d86spsken2",
+        status = "success"
+    )]
+    fn fuel_indexer_test_ping(ping: Ping, ctx: HandlerContext) {
         info!("fuel_indexer_test_ping handling a Ping event: {:?}.", ping);
 
+        assert!(ctx.tx_id.is_some());
+        assert!(ctx.receipt_index.is_some());
+        assert!(matches!(
+            ctx.tx_status,
+            Some(fuel::TransactionStatus::Success { .. })
+        ));
+
         PingEntity::new(ping.value, ping.message.to_string()).get_or_create();
+
+        let key = Config::get::<TestConfig>().ping_count_key;
+        let pings = State::get::<u64>(&key).unwrap_or(0);
+        State::set(&key, &(pings + 1));
     }
 
     fn fuel_indexer_test_u16(_ping: Ping) {
@@ -47,6 +68,9 @@ mod fuel_indexer_test {
         } = transfer;
 
         TransferEntity::new(contract_id, to, amount, asset_id).get_or_create();
+
+        // Track the receipts of the contracts that the test contract transfers to.
+        register_contract(&to);
     }
 
     fn fuel_indexer_test_transferout(transferout: TransferOut) {
@@ -303,6 +327,7 @@ mod fuel_indexer_test {
         SportsTeam::new("The Indexers".to_string(), municipality.name).get_or_create();
     }
 
+    #[handler(status = "failure")]
     fn fuel_indexer_test_panic(panic: Panic) {
         info!("fuel_indexer_test_panic handling Panic event.");
 
@@ -314,6 +339,7 @@ mod fuel_indexer_test {
         PanicEntity::new(contract_id, reason).get_or_create();
     }
 
+    #[handler(status = "failure")]
     fn fuel_indexer_trigger_revert(revert: Revert) {
         info!("fuel_indexer_trigger_revert handling trigger_revert event.");
 
@@ -569,4 +595,58 @@ mod fuel_indexer_test {
 
         ping.save();
     }
+
+    #[interval_handler(every = 2)]
+    fn fuel_indexer_test_interval_handler(block: BlockData, ctx: HandlerContext) {
+        info!(
+            "fuel_indexer_test_interval_handler handling block {}.",
+            block.height
+        );
+
+        assert_eq!(block.height % 2, 0);
+        assert_eq!(ctx.block_height, block.height);
+        assert!(ctx.tx_id.is_none());
+
+        IntervalEntity::new(block.height).get_or_create();
+    }
+
+    fn fuel_indexer_test_handler_context(_transferout: TransferOut, ctx: HandlerContext) {
+        info!(
+            "fuel_indexer_test_handler_context handling a TransferOut event at block {}.",
+            ctx.block_height
+        );
+
+        let tx_status = match ctx.tx_status {
+            Some(fuel::TransactionStatus::Success { .. }) => "success",
+            Some(fuel::TransactionStatus::Failure { .. }) => "failure",
+            _ => "unknown",
+        };
+
+        HandlerContextEntity::new(
+            ctx.block_height,
+            ctx.block_id,
+            ctx.block_time,
+            ctx.tx_id
+                .expect("TransferOut is decoded from a transaction."),
+            tx_status.to_string(),
+            ctx.receipt_index
+                .expect("TransferOut is decoded from a receipt.") as u64,
+        )
+        .get_or_create();
+    }
+
+    fn fuel_indexer_test_trigger_vec_pong_logdata(pongs: Vec<Pong>) {
+        info!("fuel_indexer_test_trigger_vec_pong_logdata handling Vec<Pong> event.");
+
+        assert_eq!(pongs.len(), 5);
+
+        for pong in pongs {
+            let pong = PongEntity {
+                id: uid(pong.id.to_le_bytes()),
+                value: pong.value,
+            };
+
+            pong.save();
+        }
+    }
 }
@@ -3,8 +3,12 @@ use crate::{
 };
 use actix_service::Service;
 use actix_web::test;
+use async_std::{fs::File, io::ReadExt};
 use axum::routing::Router;
-use fuel_indexer::IndexerService;
+use fuel_indexer::{
+    prelude::fuel::{BlockData, Consensus, Header},
+    IndexerService, WasmIndexExecutor,
+};
 use fuel_indexer_api_server::api::WebApi;
 use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{
@@ -14,6 +18,7 @@ use fuel_indexer_lib::{
     utils::{derive_socket_addr, ServiceRequest},
 };
 use fuel_indexer_postgres;
+use fuel_types::Bytes32;
 use fuels::{
     macros::abigen,
     prelude::{
@@ -412,6 +417,103 @@ pub async fn indexer_service_postgres(
     IndexerService::new(config, pool, rx).await.unwrap()
 }
 
+/// Register the test indexer in `test_db`, which creates its tables, and return its
+/// manifest.
+pub async fn register_test_indexer(test_db: &TestPostgresDb) -> Manifest {
+    if let Ok(mut current_dir) = std::env::current_dir() {
+        if current_dir.ends_with("fuel-indexer-tests") {
+            current_dir.pop();
+            current_dir.pop();
+        }
+
+        if let Err(e) = std::env::set_current_dir(current_dir) {
+            eprintln!("Failed to change directory: {}", e);
+        }
+    }
+
+    let manifest = Manifest::from_file(
+        "packages/fuel-indexer-tests/indexers/fuel-indexer-test/fuel_indexer_test.yaml",
+    )
+    .unwrap();
+
+    let mut service = indexer_service_postgres(Some(&test_db.url), None).await;
+    service
+        .register_indexer_from_manifest(
+            manifest.clone(),
+            fuel_indexer_lib::defaults::REMOVE_DATA,
+        )
+        .await
+        .unwrap();
+
+    manifest
+}
+
+/// A block at the given height, without any transactions.
+pub fn test_block(height: u32) -> BlockData {
+    BlockData {
+        height,
+        id: Bytes32::zeroed(),
+        header: Header {
+            id: Bytes32::zeroed(),
+            da_height: 1,
+            transactions_count: 0,
+            message_receipt_count: 0,
+            transactions_root: Bytes32::zeroed(),
+            message_receipt_root: Bytes32::zeroed(),
+            height,
+            prev_root: Bytes32::zeroed(),
+            time: 0,
+            application_hash: Bytes32::zeroed(),
+        },
+        producer: None,
+        time: 0,
+        consensus: Consensus::Unknown,
+        transactions: vec![],
+    }
+}
+
+/// The ID of the contract that the test indexer subscribes to in its manifest.
+pub fn test_contract_id() -> fuel_types::ContractId {
+    Bech32ContractId::from_str(
+        "fuel1h97g4w7wrwv78ad5xquxs3ecl30xstuqgzsw4uvpakg7yyh8d86spsken2",
+    )
+    .unwrap()
+    .into()
+}
+
+/// Create a `WasmIndexExecutor` running the module of the test indexer.
+pub async fn test_wasm_executor(
+    test_db: &TestPostgresDb,
+    manifest: &Manifest,
+) -> WasmIndexExecutor {
+    let mut bytes = Vec::<u8>::new();
+    let module = match &manifest.module() {
+        fuel_indexer_lib::manifest::Module::Wasm(ref module) => module.clone(),
+        _ => panic!(
+            "Expected a WASM module in the manifest but got a Native module instead."
+        ),
+    };
+    let mut file = File::open(module).await.unwrap();
+    file.read_to_end(&mut bytes).await.unwrap();
+
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let schema_version = manifest
+        .graphql_schema_content()
+        .unwrap()
+        .version()
+        .to_string();
+
+    WasmIndexExecutor::new(
+        &IndexerConfig::default(),
+        manifest,
+        bytes,
+        pool,
+        schema_version,
+    )
+    .await
+    .unwrap()
+}
+
 pub async fn connect_to_deployed_contract(
 ) -> Result<FuelIndexerTest<WalletUnlocked>, Box<dyn std::error::Error>> {
     let wallet_path = Path::new(WORKSPACE_ROOT).join("test-chain-config.json");
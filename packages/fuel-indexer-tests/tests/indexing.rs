@@ -660,6 +660,52 @@ async fn test_generics() {
     );
 }
 
+#[actix_web::test]
+async fn test_vec_generics() {
+    let IndexingTestComponents { ref db, .. } =
+        setup_indexing_test_components(None).await;
+
+    mock_request("/vec_logdata").await;
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    let rows = sqlx::query(
+        "SELECT * FROM fuel_indexer_test_index1.pongentity ORDER BY value ASC",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .unwrap();
+
+    let values = rows
+        .iter()
+        .map(|row| row.get::<BigDecimal, usize>(1).to_u64().unwrap())
+        .collect::<Vec<u64>>();
+
+    assert_eq!(values, vec![5555, 6666, 7777, 8888, 9999]);
+}
+
+#[actix_web::test]
+async fn test_index_state() {
+    let IndexingTestComponents { ref db, .. } =
+        setup_indexing_test_components(None).await;
+
+    mock_request("/ping").await;
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    let row = sqlx::query(
+        "SELECT value FROM index_state
+        WHERE namespace = 'fuel_indexer_test'
+        AND identifier = 'index1'
+        AND key = 'ping_count'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    let value: Vec<u8> = row.get(0);
+    let pings = u64::from_le_bytes(value.try_into().unwrap());
+    assert_eq!(pings, 1);
+}
+
 #[actix_web::test]
 async fn test_no_missing_blocks() {
     let IndexingTestComponents {
@@ -719,3 +765,50 @@ async fn test_no_missing_blocks() {
 
     assert_eq!(start, 2);
 }
+
+#[actix_web::test]
+async fn test_dry_run_over_blocks_that_were_already_indexed() {
+    let IndexingTestComponents {
+        ref node,
+        ref db,
+        ref manifest,
+        ..
+    } = setup_indexing_test_components(None).await;
+
+    mock_request("/block").await;
+    mock_request("/block").await;
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    let count_blocks = "SELECT COUNT(*) FROM fuel_indexer_test_index1.blockentity";
+    let indexed = sqlx::query(count_blocks)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap()
+        .get::<i64, usize>(0);
+
+    // The blocks were already indexed, and each is dry run in its own page.
+    let config = fuel_indexer::IndexerConfig {
+        block_page_size: 1,
+        ..Default::default()
+    };
+    let pool = fuel_indexer_database::IndexerConnectionPool::Postgres(db.pool.clone());
+    let records = fuel_indexer::dry_run::dry_run(&config, manifest, pool, 1, 3)
+        .await
+        .unwrap();
+
+    node.abort();
+
+    let heights = records
+        .iter()
+        .filter_map(|record| record.block_height)
+        .collect::<HashSet<u32>>();
+    assert_eq!(heights, HashSet::from([1, 2, 3]));
+
+    // Nothing was written.
+    let after = sqlx::query(count_blocks)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap()
+        .get::<i64, usize>(0);
+    assert_eq!(after, indexed);
+}
@@ -1,8 +1,16 @@
 use fuel_indexer::prelude::fuel::{BlockData, Consensus, Header};
-use fuel_indexer::{Executor, IndexerConfig, WasmIndexExecutor};
+use fuel_indexer::prelude::{Arc, Mutex};
+use fuel_indexer::{
+    Database, Executor, FtColumn, IndexerConfig, IndexerError, IndexerResult,
+    NativeIndexExecutor, WasmIndexExecutor,
+};
 use fuel_indexer_lib::WasmIndexerError;
-use fuel_indexer_lib::{config::DatabaseConfig, manifest::Manifest};
-use fuel_indexer_tests::fixtures::TestPostgresDb;
+use fuel_indexer_lib::{config::DatabaseConfig, manifest::Manifest, utils::serialize};
+use fuel_indexer_tests::fixtures::{
+    register_test_indexer, test_block, test_contract_id, test_wasm_executor,
+    TestPostgresDb,
+};
+use fuel_indexer_types::scalar::UID;
 use fuel_types::Bytes32;
 use std::str::FromStr;
 
@@ -209,3 +217,598 @@ async fn test_wasm_executor_error_codes() {
         ),
     }
 }
+
+#[tokio::test]
+async fn test_wasm_executor_stops_handler_that_never_returns() {
+    if let Ok(mut current_dir) = std::env::current_dir() {
+        if current_dir.ends_with("fuel-indexer-tests") {
+            current_dir.pop();
+            current_dir.pop();
+        }
+
+        if let Err(e) = std::env::set_current_dir(current_dir) {
+            eprintln!("Failed to change directory: {}", e);
+        }
+    }
+
+    let manifest = Manifest::from_file(
+        "packages/fuel-indexer-tests/indexers/fuel-indexer-test/fuel_indexer_test.yaml",
+    )
+    .unwrap();
+
+    let schema_version = manifest
+        .graphql_schema_content()
+        .unwrap()
+        .version()
+        .to_string();
+
+    // A module that implements just enough of the indexer ABI to be instantiated,
+    // and whose handler loops forever.
+    let wat = format!(
+        r#"(module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{schema_version}")
+            (func (export "get_version_ptr") (result i32) i32.const 0)
+            (func (export "get_version_len") (result i32) i32.const {})
+            (func (export "alloc_fn") (param i32) (result i32) i32.const 1024)
+            (func (export "dealloc_fn") (param i32 i32))
+            (func (export "handle_events") (param i32 i32) (loop $l (br $l))))"#,
+        schema_version.len()
+    );
+    let bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool =
+        fuel_indexer_database::IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        handler_timeout: Some(1),
+        ..Default::default()
+    };
+
+    let mut executor =
+        WasmIndexExecutor::new(&config, &manifest, bytes, pool, schema_version)
+            .await
+            .unwrap();
+
+    let blocks = vec![test_block(1)];
+
+    // The handler is called twice, to check that the executor can still be used
+    // after a handler was stopped.
+    for _ in 0..2 {
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            executor.handle_events(blocks.clone()),
+        )
+        .await
+        .expect("Handler was not stopped after its timeout.");
+
+        assert!(matches!(
+            result,
+            Err(fuel_indexer::IndexerError::HandlerTimeoutError)
+        ));
+    }
+
+    // Timing out doesn't shut the indexer down.
+    assert!(!executor
+        .kill_switch()
+        .load(std::sync::atomic::Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_native_executor_returns_handler_errors() {
+    async fn handle_events(
+        _blocks: Vec<BlockData>,
+        _db: Arc<Mutex<Database>>,
+    ) -> IndexerResult<()> {
+        Err(IndexerError::InvalidConfig(
+            "missing field `key`".to_string(),
+        ))
+    }
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    let pool =
+        fuel_indexer_database::IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut executor = NativeIndexExecutor::create(
+        &IndexerConfig::default(),
+        &manifest,
+        pool,
+        handle_events,
+    )
+    .await
+    .unwrap();
+
+    // The handler's error is returned as is, rather than as a generic runtime error.
+    let result = executor.handle_events(vec![test_block(1)]).await;
+    assert!(matches!(result, Err(IndexerError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn test_native_executor_can_find_and_delete_objects() {
+    use fuel_indexer_plugin::native::{set_indexer_db, Entity};
+    use fuel_indexer_schema::join::JoinMetadata;
+
+    /// Database used by `PingEntity`'s `load` and `save`, which are generated
+    /// by the `indexer` macro for native modules.
+    static DB: std::sync::RwLock<Option<Arc<Mutex<Database>>>> =
+        std::sync::RwLock::new(None);
+
+    fn db() -> Arc<Mutex<Database>> {
+        DB.read().unwrap().clone().unwrap()
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct PingEntity {
+        id: UID,
+        value: u64,
+        message: String,
+    }
+
+    #[async_trait::async_trait]
+    impl<'a> Entity<'a> for PingEntity {
+        // `type_id("fuel_indexer_test_index1", "PingEntity")`
+        const TYPE_ID: i64 = -3380051669725020796;
+        const JOIN_METADATA: Option<
+            [Option<JoinMetadata<'a>>;
+                fuel_indexer_lib::graphql::MAX_FOREIGN_KEY_LIST_FIELDS],
+        > = None;
+
+        async fn save_many_to_many(&self) -> IndexerResult<()> {
+            Ok(())
+        }
+
+        fn from_row(mut vec: Vec<FtColumn>) -> Self {
+            let (
+                Some(FtColumn::Charfield(Some(message))),
+                Some(FtColumn::UInt8(Some(value))),
+                Some(FtColumn::ID(Some(id))),
+            ) = (vec.pop(), vec.pop(), vec.pop())
+            else {
+                panic!("Unexpected PingEntity row.");
+            };
+            Self { id, value, message }
+        }
+
+        fn to_row(&self) -> Vec<FtColumn> {
+            vec![
+                FtColumn::ID(Some(self.id.clone())),
+                FtColumn::UInt8(Some(self.value)),
+                FtColumn::Charfield(Some(self.message.clone())),
+            ]
+        }
+
+        async fn load(id: UID) -> IndexerResult<Option<Self>> {
+            let bytes = db()
+                .lock()
+                .await
+                .get_object(Self::TYPE_ID, id.to_string())
+                .await?;
+            Ok(bytes.map(|bytes| {
+                Self::from_row(fuel_indexer_lib::utils::deserialize(&bytes).unwrap())
+            }))
+        }
+
+        async fn save(&self) -> IndexerResult<()> {
+            db().lock()
+                .await
+                .put_object(Self::TYPE_ID, self.to_row(), serialize(&self.to_row()))
+                .await
+        }
+    }
+
+    async fn handle_events(
+        _blocks: Vec<BlockData>,
+        db: Arc<Mutex<Database>>,
+    ) -> IndexerResult<()> {
+        set_indexer_db(db.clone());
+        *DB.write().unwrap() = Some(db);
+
+        assert_eq!(
+            PingEntity::TYPE_ID,
+            fuel_indexer_types::type_id("fuel_indexer_test_index1", "PingEntity")
+        );
+
+        let id = |c: &str| UID::new(c.repeat(64)).unwrap();
+        let ping = |id: UID, value: u64| PingEntity {
+            id,
+            value,
+            message: "ping".to_string(),
+        };
+
+        for (id, value) in [(id("1"), 1), (id("2"), 1), (id("3"), 2)] {
+            ping(id, value).save().await?;
+        }
+
+        let found = PingEntity::find_many("value", FtColumn::UInt8(Some(1))).await?;
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&ping(id("1"), 1)));
+
+        // Only columns of the entity can be queried.
+        assert!(PingEntity::find_many("missing", FtColumn::UInt8(Some(1)))
+            .await
+            .is_err());
+
+        assert!(ping(id("1"), 1).delete().await?);
+        assert!(!ping(id("1"), 1).delete().await?);
+        assert!(PingEntity::load(id("1")).await?.is_none());
+
+        let found = PingEntity::find_many("value", FtColumn::UInt8(Some(1))).await?;
+        assert_eq!(found, vec![ping(id("2"), 1)]);
+
+        Ok(())
+    }
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    let pool =
+        fuel_indexer_database::IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut executor = NativeIndexExecutor::create(
+        &IndexerConfig::default(),
+        &manifest,
+        pool,
+        handle_events,
+    )
+    .await
+    .unwrap();
+
+    executor.handle_events(vec![test_block(1)]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_provenance_records_the_transaction_of_each_handler_call() {
+    use fuel_indexer::prelude::fuel::{
+        Receipt, TransactionData, TransactionStatus, TxId,
+    };
+    use fuel_types::ContractId;
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    // The `Mint` and `Burn` handlers each save an entity marked with `@provenance`,
+    // from receipts emitted by different transactions of the same block.
+    let mint_tx_id = TxId::from([1u8; 32]);
+    let burn_tx_id = TxId::from([2u8; 32]);
+    let transaction = |id: TxId, receipt: Receipt| TransactionData {
+        transaction: Default::default(),
+        status: TransactionStatus::Success {
+            block: Bytes32::zeroed(),
+            time: 0,
+            program_state: None,
+        },
+        receipts: vec![receipt],
+        id,
+    };
+
+    let blocks = vec![BlockData {
+        transactions: vec![
+            transaction(
+                mint_tx_id,
+                Receipt::mint(Bytes32::zeroed(), ContractId::zeroed(), 100, 0, 0),
+            ),
+            transaction(
+                burn_tx_id,
+                Receipt::burn(Bytes32::zeroed(), ContractId::zeroed(), 100, 0, 0),
+            ),
+        ],
+        ..test_block(1)
+    }];
+
+    executor.handle_events(blocks).await.unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    for (table, tx_id) in [("mintentity", mint_tx_id), ("burnentity", burn_tx_id)] {
+        let row = sqlx::query(&format!(
+            "SELECT _updated_tx_id FROM fuel_indexer_test_index1.{table}"
+        ))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+        assert_eq!(row.get::<String, usize>(0), format!("{tx_id:x}"));
+    }
+}
+
+#[tokio::test]
+async fn test_filtered_handlers_are_skipped_for_non_matching_receipts() {
+    use fuel_indexer::prelude::fuel::{
+        Receipt, TransactionData, TransactionStatus, TxId,
+    };
+    use fuel_tx::{PanicInstruction, PanicReason};
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    // The `Panic` handler is declared with `status = "failure"`, so only the
+    // receipt emitted by the failed transaction should be dispatched to it.
+    let contract_id = test_contract_id();
+    let panic = |reason: PanicReason| {
+        Receipt::panic(contract_id, PanicInstruction::error(reason, 0), 0, 0)
+    };
+    let success = TransactionStatus::Success {
+        block: Bytes32::zeroed(),
+        time: 0,
+        program_state: None,
+    };
+    let failure = TransactionStatus::Failure {
+        block: Bytes32::zeroed(),
+        time: 0,
+        reason: "Revert".to_string(),
+        program_state: None,
+    };
+
+    let blocks = vec![BlockData {
+        transactions: vec![
+            TransactionData {
+                transaction: Default::default(),
+                status: success,
+                receipts: vec![panic(PanicReason::OutOfGas)],
+                id: TxId::from([1u8; 32]),
+            },
+            TransactionData {
+                transaction: Default::default(),
+                status: failure,
+                receipts: vec![panic(PanicReason::Revert)],
+                id: TxId::from([2u8; 32]),
+            },
+        ],
+        ..test_block(1)
+    }];
+
+    executor.handle_events(blocks).await.unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let rows = sqlx::query("SELECT reason FROM fuel_indexer_test_index1.panicentity")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<i32, usize>(0), PanicReason::Revert as i32);
+}
+
+#[tokio::test]
+async fn test_interval_handlers_run_every_n_blocks() {
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    // The interval handler of the test indexer runs every 2 blocks.
+    let blocks = (1..=5).map(test_block).collect::<Vec<_>>();
+    executor.handle_events(blocks).await.unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let heights = sqlx::query(
+        "SELECT height FROM fuel_indexer_test_index1.intervalentity ORDER BY height",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .unwrap()
+    .iter()
+    .map(|row| row.get::<i32, usize>(0))
+    .collect::<Vec<_>>();
+
+    assert_eq!(heights, vec![2, 4]);
+}
+
+#[tokio::test]
+async fn test_handlers_receive_the_context_of_their_arguments() {
+    use fuel_indexer::prelude::fuel::{
+        Receipt, TransactionData, TransactionStatus, TxId,
+    };
+    use fuel_types::{Address, AssetId};
+    use sqlx::{types::BigDecimal, Row};
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    let block_id = Bytes32::from([3u8; 32]);
+    let tx_id = TxId::from([4u8; 32]);
+    let transfer_out = Receipt::transfer_out(
+        test_contract_id(),
+        Address::zeroed(),
+        1,
+        AssetId::zeroed(),
+        0,
+        0,
+    );
+
+    let blocks = vec![BlockData {
+        id: block_id,
+        time: 1_700_000_000,
+        transactions: vec![TransactionData {
+            transaction: Default::default(),
+            status: TransactionStatus::Success {
+                block: block_id,
+                time: 1_700_000_000,
+                program_state: None,
+            },
+            // The `TransferOut` handler is passed the second receipt.
+            receipts: vec![
+                Receipt::script_result(fuel_tx::ScriptExecutionResult::Success, 0),
+                transfer_out,
+            ],
+            id: tx_id,
+        }],
+        ..test_block(7)
+    }];
+
+    executor.handle_events(blocks).await.unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let row = sqlx::query(
+        "SELECT block_height, block_id, block_time, tx_id, tx_status, receipt_index FROM fuel_indexer_test_index1.handlercontextentity",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    assert_eq!(row.get::<i32, usize>(0), 7);
+    assert_eq!(row.get::<String, usize>(1), format!("{block_id:x}"));
+    assert_eq!(row.get::<i64, usize>(2), 1_700_000_000);
+    assert_eq!(row.get::<String, usize>(3), format!("{tx_id:x}"));
+    assert_eq!(row.get::<String, usize>(4), "success");
+    assert_eq!(row.get::<BigDecimal, usize>(5), BigDecimal::from(1));
+}
+
+#[tokio::test]
+async fn test_registered_contracts_are_dispatched_from_the_next_receipt() {
+    use fuel_indexer::prelude::fuel::{
+        Receipt, TransactionData, TransactionStatus, TxId,
+    };
+    use fuel_tx::{PanicInstruction, PanicReason};
+    use fuel_types::{AssetId, ContractId};
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    // The `Transfer` handler of the test indexer registers the receiving contract,
+    // and the `Panic` handler only runs for failed transactions.
+    let registered_id = ContractId::from([7u8; 32]);
+    let transaction =
+        |id: u8, status: TransactionStatus, receipts: Vec<Receipt>| TransactionData {
+            transaction: Default::default(),
+            status,
+            receipts,
+            id: TxId::from([id; 32]),
+        };
+    let failure = TransactionStatus::Failure {
+        block: Bytes32::zeroed(),
+        time: 0,
+        reason: "Panic".to_string(),
+        program_state: None,
+    };
+    let panic = |reason: PanicReason| {
+        Receipt::panic(registered_id, PanicInstruction::error(reason, 0), 0, 0)
+    };
+
+    let blocks = vec![
+        BlockData {
+            transactions: vec![
+                // Precedes the receipt that registers the contract, so it is skipped.
+                transaction(1, failure.clone(), vec![panic(PanicReason::MemoryOverflow)]),
+                transaction(
+                    2,
+                    failure.clone(),
+                    vec![
+                        Receipt::transfer(
+                            test_contract_id(),
+                            registered_id,
+                            1,
+                            AssetId::zeroed(),
+                            0,
+                            0,
+                        ),
+                        // Follows the registering receipt in the same transaction.
+                        panic(PanicReason::OutOfGas),
+                    ],
+                ),
+            ],
+            ..test_block(1)
+        },
+        BlockData {
+            transactions: vec![transaction(3, failure, vec![panic(PanicReason::Revert)])],
+            ..test_block(2)
+        },
+    ];
+
+    executor.handle_events(blocks).await.unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let row = sqlx::query(
+        "SELECT contract_id FROM index_contract_ids WHERE namespace = 'fuel_indexer_test' AND identifier = 'index1'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert_eq!(row.get::<String, usize>(0), format!("{registered_id:x}"));
+
+    let mut reasons =
+        sqlx::query("SELECT reason FROM fuel_indexer_test_index1.panicentity")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get::<i32, usize>(0))
+            .collect::<Vec<_>>();
+    reasons.sort();
+
+    let mut expected = vec![PanicReason::OutOfGas as i32, PanicReason::Revert as i32];
+    expected.sort();
+    assert_eq!(reasons, expected);
+}
+
+#[tokio::test]
+async fn test_wasm_executor_records_the_metrics_of_its_indexer() {
+    use fuel_indexer::prelude::fuel::{
+        Receipt, TransactionData, TransactionStatus, TxId,
+    };
+    use fuel_indexer_metrics::METRICS;
+    use fuel_types::ContractId;
+    use prometheus_client::encoding::text::encode;
+
+    // The current value of the given metric, which other tests may also be
+    // incrementing, as metrics are global.
+    fn metric(name: &str) -> f64 {
+        let mut encoded = String::new();
+        encode(&mut encoded, &METRICS.indexer.registry).unwrap();
+        encoded
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(|value| value.trim().parse().unwrap())
+            .unwrap_or(0.0)
+    }
+
+    let labels = r#"namespace="fuel_indexer_test",identifier="index1""#;
+    let entity_writes =
+        format!(r#"indexer_entity_writes_total{{{labels},entity="mintentity"}}"#);
+    let metering_points_used = format!("indexer_metering_points_used_total{{{labels}}}");
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let manifest = register_test_indexer(&test_db).await;
+    assert_eq!(manifest.metrics(), Some(true));
+
+    let mut executor = test_wasm_executor(&test_db, &manifest).await;
+
+    let writes_before = metric(&entity_writes);
+    let points_before = metric(&metering_points_used);
+
+    let blocks = vec![BlockData {
+        transactions: vec![TransactionData {
+            transaction: Default::default(),
+            status: TransactionStatus::Success {
+                block: Bytes32::zeroed(),
+                time: 0,
+                program_state: None,
+            },
+            receipts: vec![Receipt::mint(
+                Bytes32::zeroed(),
+                ContractId::zeroed(),
+                100,
+                0,
+                0,
+            )],
+            id: TxId::from([1u8; 32]),
+        }],
+        ..test_block(1)
+    }];
+
+    executor.handle_events(blocks).await.unwrap();
+
+    // The `Mint` handler saves a `MintEntity`.
+    assert!(metric(&entity_writes) >= writes_before + 1.0);
+    assert!(metric(&metering_points_used) > points_before);
+}
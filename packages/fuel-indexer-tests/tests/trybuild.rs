@@ -101,6 +101,28 @@ module:
             // Using a custom manifest here
             manifest_content.clone(),
         ),
+        (
+            "pass_if_generic_types_are_used.rs",
+            "simple_wasm.yaml",
+            TestKind::Pass,
+            manifest_content.clone(),
+        ),
+        (
+            "pass_if_generic_abi_types_are_used.rs",
+            "generics_simple_wasm.yaml",
+            TestKind::Pass,
+            // Using a custom manifest here
+            format!(
+                r#"
+        namespace: test_namespace
+        identifier: simple_wasm_executor
+        abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi-generics.json
+        graphql_schema: {tests_root_str}/indexers/simple-wasm/schema/simple_wasm.graphql
+        contract_id: ~
+        module:
+            wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+            ),
+        ),
         (
             "fail_if_abi_contains_reserved_fuel_type.rs",
             "invalid_abi_type_simple_wasm.yaml",
@@ -135,6 +157,24 @@ module:
             TestKind::Fail,
             manifest_content.clone(),
         ),
+        (
+            "fail_if_reference_type_used_in_handler_args.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
+        (
+            "fail_if_handler_attribute_status_is_invalid.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
+        (
+            "fail_if_interval_handler_interval_is_zero.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
     ];
 
     for (name, manifest_name, kind, manifest_content) in tests {
@@ -1,6 +1,10 @@
 use fuel_indexer::IndexerConfig;
-use fuel_indexer_lib::config::{
-    auth::AuthenticationStrategy, defaults as config_defaults, AuthenticationConfig,
+use fuel_indexer_lib::{
+    config::{
+        auth::AuthenticationStrategy, defaults as config_defaults, AuthenticationConfig,
+        WebApiConfig,
+    },
+    manifest::CONFIG_SCHEMA_SECTION,
 };
 use fuel_indexer_postgres as postgres;
 use fuel_indexer_tests::{
@@ -11,10 +15,14 @@ use hyper::header::CONTENT_TYPE;
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sqlx::Row;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use fuel_indexer_database::queries::last_block_height_for_indexer;
-use fuel_indexer_database::IndexerConnection;
+use fuel_indexer_database::{
+    types::{DeploymentStatus, IndexerSnapshot},
+    IndexerConnection,
+};
 use fuel_indexer_tests::fixtures::{
     mock_request, setup_indexing_test_components, IndexingTestComponents,
 };
@@ -202,6 +210,39 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
     server.abort();
 }
 
+#[actix_web::test]
+async fn test_schema_endpoint_returns_sdl_and_introspection() {
+    let WebTestComponents { server, client, .. } = setup_web_test_components(None).await;
+
+    let resp = client
+        .get("http://127.0.0.1:29987/api/schema/fuel_indexer_test/index1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body: Value = resp.json().await.unwrap();
+    let sdl = body["sdl"].as_str().unwrap();
+    assert!(sdl.contains("enum SortOrder"));
+    assert!(sdl.contains("input PingEntityFilter"));
+    assert!(sdl.contains("enum PingEntityFields"));
+
+    let types = body["introspection"]["__schema"]["types"]
+        .as_array()
+        .unwrap();
+    assert!(types.iter().any(|t| t["name"] == "PingEntity"));
+
+    let resp = client
+        .get("http://127.0.0.1:29987/api/schema/fuel_indexer_test/does_not_exist")
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(resp.status(), 404);
+}
+
 #[actix_web::test]
 async fn test_querying_sql_endpoint_when_sql_not_enabled_returns_404() {
     let WebTestComponents { server, client, .. } = setup_web_test_components(None).await;
@@ -408,3 +449,337 @@ async fn test_replacing_an_indexer_and_keeping_or_removing_data() {
 
     assert_eq!(last, 5);
 }
+
+#[actix_web::test]
+async fn test_replacing_an_indexer_without_removing_data_keeps_its_state() {
+    let config = IndexerConfig {
+        replace_indexer: true,
+        ..IndexerConfig::default()
+    };
+
+    let IndexingTestComponents {
+        ref node,
+        ref mut service,
+        ref manifest,
+        ref db,
+        ..
+    } = setup_indexing_test_components(Some(config)).await;
+
+    mock_request("/ping").await;
+
+    node.abort();
+
+    // Replace the indexer, keep the data.
+    service
+        .register_indexer_from_manifest(manifest.clone(), false)
+        .await
+        .unwrap();
+
+    // Allow the old indexer to shut down and the new indexer to start.
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    let row = sqlx::query(
+        "SELECT value FROM index_state
+        WHERE namespace = 'fuel_indexer_test'
+        AND identifier = 'index1'
+        AND key = 'ping_count'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    let value: Vec<u8> = row.get(0);
+    let pings = u64::from_le_bytes(value.try_into().unwrap());
+    assert_eq!(pings, 1);
+}
+
+async fn deploy_simple_wasm(client: &reqwest::Client, blue_green: bool) -> Value {
+    let manifest_file = multipart::Part::stream(assets::SIMPLE_WASM_MANIFEST)
+        .file_name("simple_wasm.yaml");
+    let schema_file = multipart::Part::stream(assets::SIMPLE_WASM_SCHEMA)
+        .file_name("simple_wasm.graphql");
+    let wasm_file =
+        multipart::Part::stream(assets::SIMPLE_WASM_WASM).file_name("simple_wasm.wasm");
+
+    let form = multipart::Form::new()
+        .text("blue_green", blue_green.to_string())
+        .part("manifest", manifest_file)
+        .part("schema", schema_file)
+        .part("wasm", wasm_file);
+
+    let resp = client
+        .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+        .multipart(form)
+        .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    resp.json().await.unwrap()
+}
+
+#[actix_web::test]
+async fn test_deploying_an_indexer_with_a_config_not_matching_its_schema_fails() {
+    let WebTestComponents {
+        server,
+        db,
+        rx: _rx,
+        client,
+        ..
+    } = setup_web_test_components(None).await;
+
+    let manifest = format!(
+        "{}\nconfig:\n  fee_basis_points: thirty\n",
+        assets::SIMPLE_WASM_MANIFEST.trim_end()
+    );
+
+    // Declare the config schema in the module, as the `#[indexer]` macro would
+    // for a `#[config]` struct, by appending a custom section to it.
+    let config_schema = r#"{"fee_basis_points":"UInt8!"}"#;
+    let mut section = vec![CONFIG_SCHEMA_SECTION.len() as u8];
+    section.extend_from_slice(CONFIG_SCHEMA_SECTION.as_bytes());
+    section.extend_from_slice(config_schema.as_bytes());
+    let mut wasm = assets::SIMPLE_WASM_WASM.to_vec();
+    wasm.extend_from_slice(&[0, section.len() as u8]);
+    wasm.extend_from_slice(&section);
+
+    let manifest_file = multipart::Part::text(manifest).file_name("simple_wasm.yaml");
+    let schema_file = multipart::Part::stream(assets::SIMPLE_WASM_SCHEMA)
+        .file_name("simple_wasm.graphql");
+    let wasm_file = multipart::Part::stream(wasm).file_name("simple_wasm.wasm");
+
+    let form = multipart::Form::new()
+        .part("manifest", manifest_file)
+        .part("schema", schema_file)
+        .part("wasm", wasm_file);
+
+    let resp = client
+        .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+        .multipart(form)
+        .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    assert!(postgres::get_indexer_id(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor"
+    )
+    .await
+    .is_err());
+}
+
+#[actix_web::test]
+async fn test_blue_green_deployments_are_activated_and_retired() {
+    let config = IndexerConfig {
+        replace_indexer: true,
+        ..IndexerConfig::default()
+    };
+
+    let WebTestComponents {
+        server,
+        db,
+        rx: _rx,
+        client,
+        ..
+    } = setup_web_test_components(Some(config)).await;
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    let (namespace, identifier) = ("test_namespace", "simple_wasm_executor");
+
+    deploy_simple_wasm(&client, false).await;
+    let resp = deploy_simple_wasm(&client, true).await;
+    assert_eq!(resp["deployment"], "simple_wasm_executor_v1");
+
+    // The version deployed first keeps serving queries while the new one backfills.
+    let deployments = postgres::indexer_deployments(&mut conn, namespace, identifier)
+        .await
+        .unwrap();
+    let statuses = deployments
+        .iter()
+        .map(|d| (d.deployment.as_str(), d.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            ("simple_wasm_executor", DeploymentStatus::Active),
+            ("simple_wasm_executor_v1", DeploymentStatus::Pending),
+        ]
+    );
+
+    let serving = postgres::get_serving_indexer(&mut conn, namespace, identifier)
+        .await
+        .unwrap();
+    assert_eq!(serving.identifier, "simple_wasm_executor");
+
+    // Activating the new version retires the previous one.
+    assert!(postgres::activate_deployment(
+        &mut conn,
+        namespace,
+        "simple_wasm_executor_v1"
+    )
+    .await
+    .unwrap());
+    assert!(!postgres::activate_deployment(
+        &mut conn,
+        namespace,
+        "simple_wasm_executor_v1"
+    )
+    .await
+    .unwrap());
+
+    let serving = postgres::get_serving_indexer(&mut conn, namespace, identifier)
+        .await
+        .unwrap();
+    assert_eq!(serving.identifier, "simple_wasm_executor_v1");
+
+    let retired = postgres::get_deployment(&mut conn, namespace, identifier)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(retired.status, DeploymentStatus::Retired);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let retired_at = retired.retired_at.unwrap().timestamp();
+    assert!((now - retired_at).abs() < 60);
+
+    // Queries to the indexer are served by the active version.
+    let resp = client
+        .get("http://localhost:29987/api/schema/test_namespace/simple_wasm_executor")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // A newer version retires the version that is still backfilling.
+    let resp = deploy_simple_wasm(&client, true).await;
+    assert_eq!(resp["deployment"], "simple_wasm_executor_v2");
+    let resp = deploy_simple_wasm(&client, true).await;
+    assert_eq!(resp["deployment"], "simple_wasm_executor_v3");
+
+    server.abort();
+
+    let statuses = postgres::indexer_deployments(&mut conn, namespace, identifier)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.deployment, d.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            (
+                "simple_wasm_executor".to_string(),
+                DeploymentStatus::Retired
+            ),
+            (
+                "simple_wasm_executor_v1".to_string(),
+                DeploymentStatus::Active
+            ),
+            (
+                "simple_wasm_executor_v2".to_string(),
+                DeploymentStatus::Retired
+            ),
+            (
+                "simple_wasm_executor_v3".to_string(),
+                DeploymentStatus::Pending
+            ),
+        ]
+    );
+}
+
+async fn export_snapshot(client: &reqwest::Client) -> IndexerSnapshot {
+    let resp = client
+        .get("http://localhost:29987/api/snapshot/fuel_indexer_test/index1")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    resp.json().await.unwrap()
+}
+
+// The rows of each table of a snapshot, in a deterministic order.
+fn snapshot_rows(snapshot: &IndexerSnapshot) -> Vec<(String, Vec<String>)> {
+    snapshot
+        .tables
+        .iter()
+        .map(|table| {
+            let mut rows = serde_json::from_str::<Vec<Value>>(&table.rows)
+                .unwrap()
+                .iter()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>();
+            rows.sort();
+            (table.name.clone(), rows)
+        })
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_snapshots_larger_than_max_body_size_can_be_exported_and_imported() {
+    let config = IndexerConfig {
+        web_api: WebApiConfig {
+            max_body_size: 1024,
+            ..WebApiConfig::default()
+        },
+        ..IndexerConfig::default()
+    };
+
+    let WebTestComponents {
+        node,
+        server,
+        service,
+        rx: _rx,
+        client,
+        ..
+    } = setup_web_test_components(Some(config)).await;
+
+    // Allow the indexer to start and process blocks, then stop it so that the
+    // data doesn't change between the export and the import.
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    node.abort();
+    drop(service);
+
+    let snapshot = export_snapshot(&client).await;
+    let body = serde_json::to_vec(&snapshot).unwrap();
+    assert!(body.len() > 1024);
+
+    let resp = client
+        .delete("http://localhost:29987/api/index/fuel_indexer_test/index1")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    let resp = client
+        .post("http://localhost:29987/api/snapshot/fuel_indexer_test/index1")
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    let imported = export_snapshot(&client).await;
+
+    server.abort();
+
+    assert_eq!(imported.block_height, snapshot.block_height);
+    assert_eq!(imported.schema, snapshot.schema);
+    assert_eq!(imported.wasm, snapshot.wasm);
+    assert_eq!(imported.contract_ids, snapshot.contract_ids);
+    assert_eq!(imported.state, snapshot.state);
+    assert_eq!(snapshot_rows(&imported), snapshot_rows(&snapshot));
+}
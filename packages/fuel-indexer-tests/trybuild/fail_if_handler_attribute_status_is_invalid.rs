@@ -0,0 +1,12 @@
+use fuel_indexer_utils::prelude::*;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
+mod indexer {
+    #[handler(status = "reverted")]
+    fn function_one(event: SomeEvent) {
+        let SomeEvent { id, account } = event;
+
+        let t1 = Thing1 { id, account };
+        t1.save();
+    }
+}
@@ -0,0 +1,9 @@
+use fuel_indexer_utils::prelude::*;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
+mod indexer {
+    #[interval_handler(every = 0)]
+    fn function_one(block: BlockData) {
+        let height = block.height;
+    }
+}
@@ -0,0 +1,114 @@
+use fuel_indexer_utils::prelude::*;
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_many_to_many_record(_inp: ()) {}
+
+#[no_mangle]
+fn ff_early_exit(_inp: ()) {}
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
+mod indexer {
+    fn function_one(events: Vec<SomeEvent>) {
+        assert_eq!(events.len(), 2);
+
+        let SomeEvent { id, account } = events[1].clone();
+
+        assert_eq!(id, 10);
+        assert_eq!(account, Bits256([49u8; 32]));
+    }
+}
+
+fn main() {
+    use fuels::core::codec::ABIEncoder;
+
+    let s1 = SomeEvent {
+        id: 9,
+        account: Bits256([48u8; 32]),
+    };
+
+    let s2 = SomeEvent {
+        id: 10,
+        account: Bits256([49u8; 32]),
+    };
+
+    // Vectors returned from a contract call only include the encoded elements.
+    let bytes1 = [s1, s2]
+        .into_iter()
+        .flat_map(|s| {
+            ABIEncoder::encode(&[s.into_token()])
+                .expect("Failed compile test")
+                .resolve(0)
+        })
+        .collect::<Vec<u8>>();
+
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 1,
+            message_receipt_count: 1,
+            transactions_root: [0u8; 32].into(),
+            height: 1,
+            prev_root: [0u8; 32].into(),
+            time: 1,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![fuel::TransactionData {
+            id: [0u8; 32].into(),
+            status: fuel::TransactionStatus::default(),
+            receipts: vec![
+                fuel::Receipt::Call {
+                    id: [0u8; 32].into(),
+                    to: [0u8; 32].into(),
+                    amount: 400,
+                    asset_id: [0u8; 32].into(),
+                    gas: 4,
+                    param1: 1533868047,
+                    param2: 0,
+                    pc: 0,
+                    is: 0,
+                },
+                fuel::Receipt::ReturnData {
+                    id: [0u8; 32].into(),
+                    ptr: 2342143,
+                    len: bytes1.len() as u64,
+                    digest: [0u8; 32].into(),
+                    data: Some(bytes1),
+                    pc: 0,
+                    is: 0,
+                },
+                fuel::Receipt::Call {
+                    id: [0u8; 32].into(),
+                    to: [0u8; 32].into(),
+                    amount: 400,
+                    asset_id: [0u8; 32].into(),
+                    gas: 4,
+                    param1: 2379805026,
+                    param2: 0,
+                    pc: 0,
+                    is: 0,
+                },
+            ],
+            transaction: fuel::Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+}
@@ -188,6 +188,35 @@ impl TypeId for BlockData {
     }
 }
 
+/// Where the values passed to a handler came from.
+///
+/// Handlers receive a `HandlerContext` by taking a parameter of this type. The
+/// transaction fields describe the first handler argument that was decoded from a
+/// transaction, and are `None` if all of the arguments are block-level (e.g., `BlockData`).
+#[derive(Debug, Clone)]
+pub struct HandlerContext {
+    /// Height of the block being indexed.
+    pub block_height: u32,
+
+    /// ID of the block being indexed.
+    pub block_id: Bytes32,
+
+    /// Time of the block being indexed.
+    pub block_time: i64,
+
+    /// Header of the block being indexed.
+    pub header: Header,
+
+    /// ID of the transaction the arguments were decoded from.
+    pub tx_id: Option<TxId>,
+
+    /// Status of the transaction the arguments were decoded from.
+    pub tx_status: Option<TransactionStatus>,
+
+    /// Index of the receipt the arguments were decoded from, within its transaction.
+    pub receipt_index: Option<usize>,
+}
+
 impl From<ClientTxPointer> for TxPointer {
     fn from(tx_pointer: ClientTxPointer) -> Self {
         TxPointer {
@@ -23,7 +23,7 @@ pub trait TypeId {
 
 pub mod prelude {
     pub use crate::ffi::*;
-    pub use crate::fuel;
+    pub use crate::fuel::{self, HandlerContext};
     pub use crate::graphql::*;
     pub use crate::receipt::*;
     pub use crate::scalar::*;
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Write-through cache of the objects loaded and saved by an indexer while it
+/// processes a batch of blocks, keyed by `TypeId` and object ID.
+///
+/// Objects that were looked up but not found are cached as well, so repeated
+/// lookups of a missing object don't each make a round trip to the database.
+/// Once the cache is full, the least recently inserted object is evicted.
+#[derive(Debug, Default)]
+pub(crate) struct EntityCache {
+    /// Maximum number of cached objects. A capacity of zero disables the cache.
+    capacity: usize,
+
+    /// Cached objects, or `None` for objects that don't exist.
+    objects: HashMap<(i64, String), Option<Vec<u8>>>,
+
+    /// Keys of the cached objects, in the order in which they were inserted.
+    order: VecDeque<(i64, String)>,
+}
+
+impl EntityCache {
+    /// Create a new `EntityCache` holding at most `capacity` objects.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Return the cached object, if the object has been cached. The inner `Option`
+    /// is `None` if the object is known not to exist.
+    pub fn get(&self, type_id: i64, object_id: &str) -> Option<&Option<Vec<u8>>> {
+        self.objects.get(&(type_id, object_id.to_string()))
+    }
+
+    /// Cache the given object, replacing any cached version of it.
+    pub fn insert(&mut self, type_id: i64, object_id: String, object: Option<Vec<u8>>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let key = (type_id, object_id);
+        if let Some(cached) = self.objects.get_mut(&key) {
+            *cached = object;
+            return;
+        }
+
+        if self.objects.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.objects.remove(&oldest);
+            }
+        }
+
+        self.order.push_back(key.clone());
+        self.objects.insert(key, object);
+    }
+
+    /// Remove all cached objects.
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_cache_writes_through_and_caches_missing_objects() {
+        let mut cache = EntityCache::new(10);
+        assert_eq!(cache.get(1, "a"), None);
+
+        cache.insert(1, "a".to_string(), None);
+        assert_eq!(cache.get(1, "a"), Some(&None));
+
+        cache.insert(1, "a".to_string(), Some(vec![1, 2, 3]));
+        assert_eq!(cache.get(1, "a"), Some(&Some(vec![1, 2, 3])));
+
+        // Objects of different types don't collide.
+        assert_eq!(cache.get(2, "a"), None);
+
+        cache.clear();
+        assert_eq!(cache.get(1, "a"), None);
+    }
+
+    #[test]
+    fn test_entity_cache_evicts_oldest_object_when_full() {
+        let mut cache = EntityCache::new(2);
+        cache.insert(1, "a".to_string(), Some(vec![1]));
+        cache.insert(1, "b".to_string(), Some(vec![2]));
+        cache.insert(1, "a".to_string(), Some(vec![3]));
+        cache.insert(1, "c".to_string(), Some(vec![4]));
+
+        assert_eq!(cache.get(1, "a"), None);
+        assert_eq!(cache.get(1, "b"), Some(&Some(vec![2])));
+        assert_eq!(cache.get(1, "c"), Some(&Some(vec![4])));
+    }
+
+    #[test]
+    fn test_entity_cache_with_zero_capacity_is_disabled() {
+        let mut cache = EntityCache::new(0);
+        cache.insert(1, "a".to_string(), Some(vec![1]));
+        assert_eq!(cache.get(1, "a"), None);
+    }
+}
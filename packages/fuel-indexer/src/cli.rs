@@ -1,6 +1,6 @@
-pub(crate) use crate::commands::run;
+pub(crate) use crate::commands::{dry_run, run};
 use clap::{Parser, Subcommand};
-use fuel_indexer_lib::config::IndexerArgs;
+use fuel_indexer_lib::config::{DryRunArgs, IndexerArgs};
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -19,6 +19,7 @@ pub struct Opt {
 #[derive(Debug, Subcommand)]
 pub enum Indexer {
     Run(IndexerArgs),
+    DryRun(DryRunArgs),
 }
 
 pub async fn run_cli() -> anyhow::Result<()> {
@@ -26,6 +27,7 @@ pub async fn run_cli() -> anyhow::Result<()> {
     match opt {
         Ok(opt) => match opt.command {
             Indexer::Run(args) => run::exec(args).await,
+            Indexer::DryRun(args) => dry_run::exec(args).await,
         },
         Err(e) => e.exit(),
     }
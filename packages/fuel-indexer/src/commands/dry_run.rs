@@ -0,0 +1,50 @@
+use crate::dry_run::{dry_run, write_records};
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::{
+    config::{DryRunArgs, IndexerConfig},
+    manifest::Manifest,
+    utils::init_logging,
+};
+use std::{fs::File, io::Write};
+use tracing::info;
+
+pub async fn exec(args: DryRunArgs) -> anyhow::Result<()> {
+    let DryRunArgs {
+        indexer,
+        start_block,
+        end_block,
+        output_format,
+        output,
+    } = args;
+
+    let manifest = indexer
+        .manifest
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("A manifest is required for a dry run."))?;
+
+    let config = indexer
+        .config
+        .clone()
+        .map(IndexerConfig::from_file)
+        .unwrap_or(Ok(IndexerConfig::from(indexer)))?;
+
+    config.validate()?;
+
+    init_logging(&config).await?;
+
+    info!("Using manifest file located at '{}'", manifest.display());
+    let manifest = Manifest::from_file(&manifest)?;
+
+    let pool = IndexerConnectionPool::connect(&config.database.to_string()).await?;
+
+    let records = dry_run(&config, &manifest, pool, start_block, end_block).await?;
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    write_records(&mut writer, &records, output_format)?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -1 +1,2 @@
+pub mod dry_run;
 pub mod run;
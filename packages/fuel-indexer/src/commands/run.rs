@@ -1,21 +1,24 @@
-use crate::IndexerService;
+use crate::{telemetry, IndexerService};
 use fuel_indexer_database::{queries, IndexerConnectionPool};
 use fuel_indexer_lib::{
     config::{IndexerArgs, IndexerConfig},
     defaults,
     manifest::Manifest,
-    utils::{init_logging, ServiceRequest},
+    utils::{init_logging, init_logging_with_layer, set_log_level, ServiceRequest},
+};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 use tokio::signal::unix::{signal, Signal, SignalKind};
-use tokio::sync::mpsc::channel;
-use tracing::info;
+use tokio::sync::{mpsc::channel, watch};
+use tracing::{error, info, warn};
 
 #[cfg(feature = "api-server")]
 use fuel_indexer_api_server::api::WebApi;
 
 // Returns a future which completes when a shutdown signal has been received.
 fn shutdown_signal_handler() -> std::io::Result<impl futures::Future<Output = ()>> {
-    let mut sighup: Signal = signal(SignalKind::hangup())?;
     let mut sigterm: Signal = signal(SignalKind::terminate())?;
     let mut sigint: Signal = signal(SignalKind::interrupt())?;
 
@@ -23,9 +26,6 @@ fn shutdown_signal_handler() -> std::io::Result<impl futures::Future<Output = ()
         #[cfg(unix)]
         {
             tokio::select! {
-                _ = sighup.recv() => {
-                    info!("Received SIGHUP. Stopping services.");
-                }
                 _ = sigterm.recv() => {
                     info!("Received SIGTERM. Stopping services.");
                 }
@@ -45,6 +45,91 @@ fn shutdown_signal_handler() -> std::io::Result<impl futures::Future<Output = ()
     Ok(future)
 }
 
+// Returns a future which reloads the configuration from the config file whenever the
+// file changes, or a SIGHUP is received, and publishes it to `tx`.
+//
+// Only the settings that can safely change at runtime are applied. An invalid config
+// file is rejected, and the current configuration is kept.
+fn config_reload_handler(
+    path: Option<PathBuf>,
+    tx: watch::Sender<IndexerConfig>,
+) -> std::io::Result<impl futures::Future<Output = ()>> {
+    let mut sighup: Signal = signal(SignalKind::hangup())?;
+
+    let future = async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            defaults::CONFIG_FILE_POLL_INTERVAL,
+        ));
+        let mut last_modified = path.as_ref().and_then(|p| modified_at(p));
+
+        loop {
+            tokio::select! {
+                _ = sighup.recv() => {
+                    info!("Received SIGHUP. Reloading configuration.");
+                }
+                _ = interval.tick() => {
+                    let modified = path.as_ref().and_then(|p| modified_at(p));
+                    if modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    info!("Config file changed. Reloading configuration.");
+                }
+            }
+
+            let Some(path) = path.clone() else {
+                warn!("No config file was provided, so there is no configuration to reload.");
+                continue;
+            };
+
+            // Parsing the file may panic on malformed values, which must not take down the service.
+            let config = match tokio::task::spawn_blocking(move || {
+                IndexerConfig::from_file(path).and_then(|c| c.validate().map(|_| c))
+            })
+            .await
+            {
+                Ok(Ok(config)) => config,
+                Ok(Err(e)) => {
+                    error!("Rejected reloaded configuration, keeping the current configuration: {e}");
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to read configuration, keeping the current configuration: {e}");
+                    continue;
+                }
+            };
+
+            let mut current = tx.borrow().clone();
+            let requires_restart = current.reload(config);
+
+            for setting in requires_restart {
+                warn!("Setting '{setting}' changed, but will only take effect once the indexer service is restarted.");
+            }
+
+            if current == *tx.borrow() {
+                info!("No runtime settings changed.");
+                continue;
+            }
+
+            if current.log_level != tx.borrow().log_level {
+                if let Err(e) = set_log_level(&current) {
+                    error!("Failed to change log level: {e}");
+                }
+            }
+
+            info!("Applied reloaded configuration: {current:?}");
+            tx.send_replace(current);
+        }
+    };
+
+    Ok(future)
+}
+
+// Returns the last modification time of the file at `path`, if available.
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
 pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
     let mut subsystems: tokio::task::JoinSet<()> = tokio::task::JoinSet::new();
 
@@ -97,7 +182,18 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
         .map(IndexerConfig::from_file)
         .unwrap_or(Ok(IndexerConfig::from(args)))?;
 
-    init_logging(&config).await?;
+    config.validate()?;
+
+    if config.telemetry.enabled {
+        let layer = telemetry::otlp_layer(&config.telemetry)?;
+        init_logging_with_layer(&config, layer).await?;
+        info!(
+            "Exporting tracing spans to OTLP collector at {}",
+            config.telemetry.otlp_endpoint
+        );
+    } else {
+        init_logging(&config).await?;
+    }
 
     info!("Configuration: {:?}", config);
 
@@ -111,7 +207,11 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
         queries::run_migration(&mut c).await?;
     }
 
+    let (config_tx, config_rx) = watch::channel(config.clone());
+    subsystems.spawn(config_reload_handler(args_config.clone(), config_tx)?);
+
     let mut service = IndexerService::new(config.clone(), pool.clone(), rx).await?;
+    service.subscribe_to_config_reloads(config_rx.clone());
 
     match manifest.map(|p| {
         info!("Using manifest file located at '{}'", p.display());
@@ -139,9 +239,9 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
 
     #[cfg(feature = "api-server")]
     subsystems.spawn({
-        let config = config.clone();
         async {
-            if let Err(e) = WebApi::build_and_run(config, pool, tx).await {
+            if let Err(e) = WebApi::build_and_run_with_reloads(config_rx, pool, tx).await
+            {
                 tracing::error!("Api Server failed: {e}");
             }
         }
@@ -185,5 +285,9 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
         forc_postgres::commands::stop::exec(stop_db_cmd).await?;
     };
 
+    if config.telemetry.enabled {
+        tokio::task::spawn_blocking(telemetry::shutdown).await?;
+    }
+
     Ok(())
 }
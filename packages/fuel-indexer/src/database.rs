@@ -1,13 +1,22 @@
-use crate::{IndexerConfig, IndexerError, IndexerResult, Manifest};
+use crate::{
+    cache::EntityCache, dry_run::DryRunRecord, IndexerConfig, IndexerError,
+    IndexerResult, Manifest,
+};
 use fuel_indexer_database::{
     queries, IndexerConnection, IndexerConnectionPool, IndexerDatabaseError,
 };
 use fuel_indexer_lib::{
-    fully_qualified_namespace, graphql::types::IdCol, utils::format_sql_query,
+    fully_qualified_namespace,
+    graphql::types::{IdCol, ProvenanceCol},
+    join_table_name,
+    utils::format_sql_query,
 };
-use fuel_indexer_schema::FtColumn;
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::{core::IndexerLabel, METRICS};
+use fuel_indexer_schema::{join::RawQuery, FtColumn};
+use fuel_indexer_types::type_id;
 use std::collections::HashMap;
-use tracing::{debug, error, info};
+use tracing::{debug, debug_span, error, info, Instrument, Span};
 
 /// Database for an executor instance, with schema info.
 #[derive(Debug)]
@@ -24,6 +33,11 @@ pub struct Database {
     /// Identifier of the indexer.
     identifier: String,
 
+    /// Identifier for which the indexer's module was compiled. This differs from
+    /// `identifier` for blue/green deployments, whose identifier is suffixed with
+    /// the version of the deployment.
+    module_identifier: String,
+
     /// Version of the indexer.
     version: String,
 
@@ -35,6 +49,68 @@ pub struct Database {
 
     /// Indexer configuration.
     config: IndexerConfig,
+
+    /// Block and transaction currently being processed, used to fill in provenance columns.
+    provenance: Option<Provenance>,
+
+    /// Objects that would have been written, when executing a dry run. The transaction
+    /// of each batch of a dry run is reverted rather than committed.
+    dry_run: Option<Vec<DryRunRecord>>,
+
+    /// JSON-encoded `config` section of the indexer's manifest, if any.
+    runtime_config: Option<String>,
+
+    /// Objects loaded and saved during the current transaction.
+    cache: EntityCache,
+
+    /// Whether or not to record metrics for this indexer.
+    #[cfg(feature = "metrics")]
+    metrics: bool,
+}
+
+/// Block and transaction context used to fill in the provenance columns of
+/// objects saved while a block is being processed.
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    /// Height of the block being processed.
+    pub block_height: u32,
+
+    /// Timestamp of the block being processed.
+    pub block_time: i64,
+
+    /// Hex-encoded ID of the transaction being processed, if any.
+    pub tx_id: Option<String>,
+}
+
+impl Provenance {
+    /// Return the SQL value for the given provenance column.
+    fn query_fragment(&self, column: &str) -> String {
+        match column {
+            ProvenanceCol::CREATED_AT_BLOCK | ProvenanceCol::UPDATED_AT_BLOCK => {
+                self.block_height.to_string()
+            }
+            ProvenanceCol::CREATED_AT | ProvenanceCol::UPDATED_AT => {
+                self.block_time.to_string()
+            }
+            ProvenanceCol::UPDATED_TX_ID => match &self.tx_id {
+                Some(tx_id) => format!("'{tx_id}'"),
+                None => "NULL".to_string(),
+            },
+            _ => unreachable!("'{column}' is not a provenance column."),
+        }
+    }
+}
+
+/// Table of the metadata of the blocks processed by an indexer.
+const INDEX_METADATA_TABLE: &str = "indexmetadataentity";
+
+/// Tracing span for a single SQL statement executed on behalf of an indexer.
+fn sql_span(namespace: &str, identifier: &str, statement: &str) -> Span {
+    debug_span!(
+        "sql",
+        indexer = %format!("{namespace}.{identifier}"),
+        db.statement = statement,
+    )
 }
 
 /// Check if the upsert query is for an ID column only.
@@ -54,15 +130,32 @@ impl Database {
             stashed: None,
             namespace: manifest.namespace().to_string(),
             identifier: manifest.identifier().to_string(),
+            module_identifier: manifest.identifier().to_string(),
             version: Default::default(),
             schema: Default::default(),
             tables: Default::default(),
             config: config.clone(),
+            provenance: None,
+            dry_run: None,
+            runtime_config: manifest.config().and_then(|config| {
+                serde_json::to_string(config)
+                    .map_err(|e| {
+                        error!(
+                            "Indexer({}) config could not be encoded as JSON: {e}",
+                            manifest.uid()
+                        )
+                    })
+                    .ok()
+            }),
+            cache: EntityCache::new(config.entity_cache_size),
+            #[cfg(feature = "metrics")]
+            metrics: manifest.metrics().unwrap_or(false),
         }
     }
 
     /// Open a database transaction.
     pub async fn start_transaction(&mut self) -> IndexerResult<usize> {
+        self.cache.clear();
         let conn = self.pool.acquire().await?;
         self.stashed = Some(conn);
         debug!("Connection stashed as: {:?}", self.stashed);
@@ -72,31 +165,46 @@ impl Database {
                 .ok_or(crate::IndexerError::NoTransactionError(
                     "start_transaction".to_string(),
                 ))?;
-        let result = queries::start_transaction(conn).await?;
+        let result = queries::start_transaction(conn)
+            .instrument(sql_span(&self.namespace, &self.identifier, "BEGIN"))
+            .await?;
         Ok(result)
     }
 
     /// Commit transaction to database.
     pub async fn commit_transaction(&mut self) -> IndexerResult<usize> {
+        // A dry run never commits. Reverting each batch, rather than the whole
+        // dry run at once, keeps row locks from being held across batches.
+        if self.dry_run.is_some() {
+            return self.revert_transaction().await;
+        }
+
+        self.cache.clear();
+
         let conn =
             self.stashed
                 .as_mut()
                 .ok_or(crate::IndexerError::NoTransactionError(
                     "commit_transaction".to_string(),
                 ))?;
-        let res = queries::commit_transaction(conn).await?;
+        let res = queries::commit_transaction(conn)
+            .instrument(sql_span(&self.namespace, &self.identifier, "COMMIT"))
+            .await?;
         Ok(res)
     }
 
     /// Revert open transaction.
     pub async fn revert_transaction(&mut self) -> IndexerResult<usize> {
+        self.cache.clear();
         let conn =
             self.stashed
                 .as_mut()
                 .ok_or(crate::IndexerError::NoTransactionError(
                     "revert_transaction".to_string(),
                 ))?;
-        let res = queries::revert_transaction(conn).await?;
+        let res = queries::revert_transaction(conn)
+            .instrument(sql_span(&self.namespace, &self.identifier, "ROLLBACK"))
+            .await?;
         Ok(res)
     }
 
@@ -143,7 +251,7 @@ impl Database {
         bytes: Vec<u8>,
     ) -> IndexerResult<()> {
         let table = match self.tables.get(&type_id) {
-            Some(t) => t,
+            Some(t) => t.clone(),
             None => {
                 return Err(IndexerError::Unknown(format!(
                     r#"TypeId({type_id}) not found in tables: {:?}. 
@@ -158,13 +266,49 @@ Do your WASM modules need to be rebuilt?
             }
         };
 
-        let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
-        let updates: Vec<_> = self.schema[table]
+        if self.dry_run.is_some() {
+            // The metadata of the blocks processed isn't written during a dry run, since
+            // blocks must be indexed consecutively, while a dry run may start at any block.
+            if table.rsplit('.').next() == Some(INDEX_METADATA_TABLE) {
+                return Ok(());
+            }
+            self.record_dry_run_write(&table, &columns).await?;
+        }
+
+        let table = &table;
+        let mut inserts: Vec<_> =
+            columns.iter().map(|col| col.query_fragment()).collect();
+        let mut updates: Vec<_> = self.schema[table]
             .iter()
             .zip(columns.iter())
             .map(|(colname, value)| format!("{colname} = {}", value.query_fragment()))
             .collect();
 
+        // Provenance columns follow the object's own fields, and are filled in from the
+        // block and transaction being processed, rather than by the handler.
+        for colname in self.schema[table]
+            .iter()
+            .filter(|c| ProvenanceCol::is_provenance_col(c))
+        {
+            let value = self
+                .provenance
+                .as_ref()
+                .map(|p| p.query_fragment(colname))
+                .unwrap_or_else(|| "NULL".to_string());
+
+            if colname != ProvenanceCol::CREATED_AT_BLOCK
+                && colname != ProvenanceCol::CREATED_AT
+            {
+                updates.push(format!("{colname} = {value}"));
+            }
+            inserts.push(value);
+        }
+
+        let object_id = match columns.first() {
+            Some(FtColumn::ID(Some(id))) => Some(id.to_string()),
+            _ => None,
+        };
+
         let columns = self.schema[table].clone();
 
         let query_text =
@@ -179,7 +323,25 @@ Do your WASM modules need to be rebuilt?
             info!("{query_text}");
         }
 
-        queries::put_object(conn, query_text, bytes).await?;
+        let span = sql_span(&self.namespace, &self.identifier, &query_text);
+        queries::put_object(conn, query_text, bytes.clone())
+            .instrument(span)
+            .await
+            .map_err(|e| constraint_violation(table, e))?;
+
+        if let Some(object_id) = object_id {
+            self.cache.insert(type_id, object_id, Some(bytes));
+        }
+
+        #[cfg(feature = "metrics")]
+        if self.metrics {
+            // Tables are fully qualified, but entities are labeled by table name only.
+            let entity = table.rsplit('.').next().unwrap_or(table);
+            METRICS.indexer.record_entity_write(
+                &IndexerLabel::new(&self.namespace, &self.identifier),
+                entity,
+            );
+        }
 
         Ok(())
     }
@@ -194,16 +356,39 @@ Do your WASM modules need to be rebuilt?
             .tables
             .get(&type_id)
             .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?;
+
+        let cached = self.cache.get(type_id, &object_id).cloned();
+
+        #[cfg(feature = "metrics")]
+        if self.metrics {
+            // Tables are fully qualified, but entities are labeled by table name only.
+            let entity = table.rsplit('.').next().unwrap_or(table);
+            METRICS.indexer.record_entity_cache_lookup(
+                &IndexerLabel::new(&self.namespace, &self.identifier),
+                entity,
+                cached.is_some(),
+            );
+        }
+
+        if let Some(object) = cached {
+            return Ok(object);
+        }
+
         let query = self.get_query(table, &object_id);
         let conn = self
             .stashed
             .as_mut()
             .ok_or(IndexerError::NoTransactionError("get_object".to_string()))?;
-        match queries::get_object(conn, query).await {
-            Ok(v) => Ok(Some(v)),
+        let span = sql_span(&self.namespace, &self.identifier, &query);
+        match queries::get_object(conn, query).instrument(span).await {
+            Ok(v) => {
+                self.cache.insert(type_id, object_id, Some(v.clone()));
+                Ok(Some(v))
+            }
             Err(e) => {
                 if let sqlx::Error::RowNotFound = e {
                     debug!("Row not found for object ID: {object_id}");
+                    self.cache.insert(type_id, object_id, None);
                 } else {
                     error!("Failed to get_object: {e:?}");
                 }
@@ -212,6 +397,194 @@ Do your WASM modules need to be rebuilt?
         }
     }
 
+    /// Get the objects whose `column` is equal to `value`.
+    pub async fn find_objects(
+        &mut self,
+        type_id: i64,
+        column: &str,
+        value: &FtColumn,
+    ) -> IndexerResult<Vec<Vec<u8>>> {
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?
+            .clone();
+
+        if !self.schema[&table].iter().any(|c| c == column) {
+            return Err(IndexerError::Unknown(format!(
+                "Column '{column}' not found in {table}"
+            )));
+        }
+
+        let query = format!(
+            "SELECT object FROM {table} WHERE {column} = {}",
+            value.query_fragment()
+        );
+        if self.config.verbose {
+            info!("{query}");
+        }
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("find_objects".to_string()))?;
+        let span = sql_span(&self.namespace, &self.identifier, &query);
+        let objects = queries::get_objects(conn, query).instrument(span).await?;
+
+        Ok(objects)
+    }
+
+    /// Delete an object from the database, along with its rows in any many-to-many
+    /// join tables. Returns `false` if there was no such object.
+    pub async fn delete_object(
+        &mut self,
+        type_id: i64,
+        object_id: String,
+    ) -> IndexerResult<bool> {
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?
+            .clone();
+
+        let mut join_queries = self
+            .join_table_columns(&table)
+            .into_iter()
+            .map(|(join_table, column)| {
+                format!("DELETE FROM {join_table} WHERE {column} = '{object_id}'")
+            })
+            .collect::<Vec<_>>();
+        join_queries.sort();
+
+        let query = format!("DELETE FROM {table} WHERE id = '{object_id}'");
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError(
+                "delete_object".to_string(),
+            ))?;
+
+        for join_query in join_queries {
+            if self.config.verbose {
+                info!("{join_query}");
+            }
+            let span = sql_span(&self.namespace, &self.identifier, &join_query);
+            queries::execute_query(conn, join_query)
+                .instrument(span)
+                .await?;
+        }
+
+        if self.config.verbose {
+            info!("{query}");
+        }
+        let span = sql_span(&self.namespace, &self.identifier, &query);
+        let deleted = queries::execute_query(conn, query).instrument(span).await?;
+
+        self.cache.insert(type_id, object_id, None);
+
+        Ok(deleted > 0)
+    }
+
+    /// Return the many-to-many join tables that reference the given table, along
+    /// with the column in each that holds the ID of the referenced object.
+    ///
+    /// Join tables are named after the two tables they join (see `join_table_name`),
+    /// and have a single column referencing the ID of each.
+    fn join_table_columns(&self, table: &str) -> Vec<(String, String)> {
+        let Some((namespace, name)) = table.split_once('.') else {
+            return Vec::new();
+        };
+
+        self.schema
+            .iter()
+            .filter_map(|(join_table, columns)| {
+                let (join_namespace, join_name) = join_table.split_once('.')?;
+                if join_namespace != namespace || columns.len() != 2 {
+                    return None;
+                }
+
+                let typedefs = columns
+                    .iter()
+                    .map(|c| c.rsplit_once('_').map(|(typedef, _)| typedef))
+                    .collect::<Option<Vec<_>>>()?;
+                let (a, b) = (typedefs[0], typedefs[1]);
+                if join_name != join_table_name(a, b)
+                    && join_name != join_table_name(b, a)
+                {
+                    return None;
+                }
+
+                columns
+                    .iter()
+                    .zip(typedefs)
+                    .find(|(_, typedef)| *typedef == name)
+                    .map(|(column, _)| (join_table.clone(), column.clone()))
+            })
+            .collect()
+    }
+
+    /// Record the object that is about to be written during a dry run, along with
+    /// the object it replaces, if any.
+    async fn record_dry_run_write(
+        &mut self,
+        table: &str,
+        columns: &[FtColumn],
+    ) -> IndexerResult<()> {
+        let id = match columns.first() {
+            Some(FtColumn::ID(Some(id))) => id.to_string(),
+            _ => {
+                return Err(IndexerError::Unknown(format!(
+                    "Object in {table} has no ID"
+                )))
+            }
+        };
+
+        // The object is read outside of the transaction, so that it's compared to the
+        // committed state of the table rather than to the dry run's own writes.
+        let before = {
+            let query = self.get_query(table, &id);
+            let mut conn = self.pool.acquire().await?;
+            match queries::get_object(&mut conn, query).await {
+                Ok(bytes) => Some(
+                    bincode::deserialize::<Vec<FtColumn>>(&bytes)
+                        .map_err(|e| IndexerError::Unknown(e.to_string()))?,
+                ),
+                Err(sqlx::Error::RowNotFound) => None,
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        let record = DryRunRecord::new(
+            self.provenance.as_ref().map(|p| p.block_height),
+            table,
+            id,
+            &self.schema[table],
+            before,
+            columns,
+        );
+
+        if let Some(records) = self.dry_run.as_mut() {
+            records.push(record);
+        }
+
+        Ok(())
+    }
+
+    /// Execute subsequent transactions as a dry run: every object written is recorded,
+    /// and each transaction is reverted rather than committed.
+    pub fn enable_dry_run(&mut self) {
+        self.dry_run = Some(Vec::new());
+    }
+
+    /// Take the objects recorded during a dry run.
+    pub fn finish_dry_run(&mut self) -> Vec<DryRunRecord> {
+        self.dry_run
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Load the schema for this indexer from the database, and build a mapping of `TypeId`s to tables.
     pub async fn load_schema(&mut self, version: String) -> IndexerResult<()> {
         self.version = version;
@@ -222,6 +595,32 @@ Do your WASM modules need to be rebuilt?
         );
 
         let mut conn = self.pool.acquire().await?;
+
+        if let Some(deployment) =
+            queries::get_deployment(&mut conn, &self.namespace, &self.identifier).await?
+        {
+            self.module_identifier = deployment.identifier;
+        }
+
+        // The type IDs compiled into the module are derived from the identifier for
+        // which it was compiled, rather than the identifier of the deployment.
+        let module_type_ids: HashMap<i64, i64> = if self.is_deployment() {
+            let module_namespace =
+                fully_qualified_namespace(&self.namespace, &self.module_identifier);
+            queries::type_id_list_by_name(
+                &mut conn,
+                &self.namespace,
+                &self.version,
+                &self.identifier,
+            )
+            .await?
+            .into_iter()
+            .map(|t| (t.id, type_id(&module_namespace, &t.graphql_name)))
+            .collect()
+        } else {
+            HashMap::new()
+        };
+
         let columns = queries::columns_get_schema(
             &mut conn,
             &self.namespace,
@@ -237,8 +636,13 @@ Do your WASM modules need to be rebuilt?
                 &column.table_name
             );
 
+            let type_id = module_type_ids
+                .get(&column.type_id)
+                .copied()
+                .unwrap_or(column.type_id);
+
             self.tables
-                .entry(column.type_id)
+                .entry(type_id)
                 .or_insert_with(|| table.to_string());
 
             let columns = self.schema.entry(table.to_string()).or_default();
@@ -249,6 +653,159 @@ Do your WASM modules need to be rebuilt?
         Ok(())
     }
 
+    /// Set the block and transaction being processed, which are recorded in the
+    /// provenance columns of objects saved from here on.
+    pub fn set_provenance(&mut self, provenance: Provenance) {
+        self.provenance = Some(provenance);
+    }
+
+    /// Track the given hex-encoded contract ID, so that its receipts are dispatched
+    /// to the indexer's handlers. Returns `false` if the contract ID was already
+    /// being tracked.
+    pub async fn register_contract_id(
+        &mut self,
+        contract_id: &str,
+    ) -> IndexerResult<bool> {
+        let block_height = self.provenance.as_ref().map(|p| p.block_height);
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError(
+                "register_contract_id".to_string(),
+            ))?;
+
+        let registered = queries::register_contract_id(
+            conn,
+            &self.namespace,
+            &self.identifier,
+            contract_id,
+            block_height,
+        )
+        .instrument(sql_span(
+            &self.namespace,
+            &self.identifier,
+            "INSERT INTO index_contract_ids",
+        ))
+        .await?;
+
+        if registered {
+            info!(
+                "Indexer({}.{}) is now tracking Contract({contract_id}).",
+                self.namespace, self.identifier
+            );
+        }
+
+        Ok(registered)
+    }
+
+    /// Return the hex-encoded contract IDs registered by the indexer's handlers.
+    pub async fn contract_ids(&mut self) -> IndexerResult<Vec<String>> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("contract_ids".to_string()))?;
+
+        let contract_ids = queries::contract_ids(conn, &self.namespace, &self.identifier)
+            .instrument(sql_span(
+                &self.namespace,
+                &self.identifier,
+                "SELECT contract_id FROM index_contract_ids",
+            ))
+            .await?;
+
+        Ok(contract_ids)
+    }
+
+    /// Return the value stored under the given key of the indexer's state, if any.
+    pub async fn get_state(&mut self, key: &str) -> IndexerResult<Option<Vec<u8>>> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("get_state".to_string()))?;
+
+        let value = queries::get_state(conn, &self.namespace, &self.identifier, key)
+            .instrument(sql_span(
+                &self.namespace,
+                &self.identifier,
+                "SELECT value FROM index_state",
+            ))
+            .await?;
+
+        Ok(value)
+    }
+
+    /// Store the given value under the given key of the indexer's state, as part
+    /// of the current transaction.
+    pub async fn put_state(&mut self, key: &str, value: &[u8]) -> IndexerResult<()> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("put_state".to_string()))?;
+
+        queries::put_state(conn, &self.namespace, &self.identifier, key, value)
+            .instrument(sql_span(
+                &self.namespace,
+                &self.identifier,
+                "INSERT INTO index_state",
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove the given key from the indexer's state, as part of the current
+    /// transaction.
+    pub async fn delete_state(&mut self, key: &str) -> IndexerResult<()> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("delete_state".to_string()))?;
+
+        queries::delete_state(conn, &self.namespace, &self.identifier, key)
+            .instrument(sql_span(
+                &self.namespace,
+                &self.identifier,
+                "DELETE FROM index_state",
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Whether this indexer is a blue/green deployment of another indexer.
+    fn is_deployment(&self) -> bool {
+        self.module_identifier != self.identifier
+    }
+
+    /// If this indexer is a pending blue/green deployment, make it the deployment
+    /// serving the queries of the indexer it was deployed for.
+    ///
+    /// Returns `true` if the deployment was activated.
+    pub async fn activate_deployment(&mut self) -> IndexerResult<bool> {
+        if !self.is_deployment() {
+            return Ok(false);
+        }
+
+        let mut conn = self.pool.acquire().await?;
+        let activated =
+            queries::activate_deployment(&mut conn, &self.namespace, &self.identifier)
+                .await?;
+
+        if activated {
+            info!(
+                "Indexer({}.{}) now serves the queries of Indexer({}.{}).",
+                self.namespace, self.identifier, self.namespace, self.module_identifier
+            );
+        }
+
+        Ok(activated)
+    }
+
+    /// Return the JSON-encoded `config` section of the indexer's manifest, if any.
+    pub fn runtime_config(&self) -> Option<&str> {
+        self.runtime_config.as_deref()
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -273,7 +830,7 @@ Do your WASM modules need to be rebuilt?
     /// many-to-many relationships with children `TypeDefinition`s.
     pub async fn put_many_to_many_record(
         &mut self,
-        queries: Vec<String>,
+        queries: Vec<RawQuery>,
     ) -> IndexerResult<()> {
         let conn = self
             .stashed
@@ -282,14 +839,47 @@ Do your WASM modules need to be rebuilt?
                 "put_many_to_many_record".to_string(),
             ))?;
 
-        for query in queries {
+        // The namespace compiled into the module is that of the identifier it was
+        // compiled for, so the records of a blue/green deployment are pointed at
+        // the deployment's own join tables.
+        let namespace = fully_qualified_namespace(&self.namespace, &self.identifier);
+
+        for mut query in queries {
+            query.set_namespace(&namespace);
+            let query = query.to_string();
+
             if self.config.verbose {
                 info!("{query}");
             }
 
-            queries::put_many_to_many_record(conn, query).await?;
+            let span = sql_span(&self.namespace, &self.identifier, &query);
+            queries::put_many_to_many_record(conn, query)
+                .instrument(span)
+                .await?;
         }
 
         Ok(())
     }
 }
+
+// Postgres error codes for violations of `NOT NULL`, `UNIQUE`, and `CHECK`
+// constraints, respectively.
+const CONSTRAINT_VIOLATION_CODES: [&str; 3] = ["23502", "23505", "23514"];
+
+/// Convert a database error raised by a column constraint (e.g., a `@check`
+/// expression or a `@unique` field) into a typed `IndexerError`. All other
+/// errors are passed through unchanged.
+fn constraint_violation(table: &str, e: sqlx::Error) -> IndexerError {
+    if let sqlx::Error::Database(ref inner) = e {
+        if let Some(pg) = inner.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
+            if CONSTRAINT_VIOLATION_CODES.contains(&pg.code()) {
+                return IndexerError::ConstraintViolation(
+                    table.to_string(),
+                    pg.constraint().unwrap_or_default().to_string(),
+                    pg.message().to_string(),
+                );
+            }
+        }
+    }
+    e.into()
+}
@@ -0,0 +1,280 @@
+use crate::{
+    executor::Executor, failover::FailoverClient, IndexerConfig, IndexerError,
+    IndexerResult, Manifest, Module, WasmIndexExecutor,
+};
+use fuel_indexer_database::{queries, types::IndexerAssetType, IndexerConnectionPool};
+use fuel_indexer_lib::config::DryRunOutputFormat;
+use fuel_indexer_schema::FtColumn;
+use serde::Serialize;
+use std::{collections::BTreeMap, io::Write};
+use tracing::info;
+
+/// How an object written during a dry run compares to the deployed indexer's table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DryRunOperation {
+    /// The object does not exist in the table.
+    Insert,
+
+    /// The object exists in the table, and at least one of its fields would change.
+    Update,
+
+    /// The object exists in the table, and none of its fields would change.
+    Unchanged,
+}
+
+/// An object that would have been written during a dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DryRunRecord {
+    /// Height of the block being processed when the object was written.
+    pub block_height: Option<u32>,
+
+    /// Table to which the object would have been written.
+    pub entity: String,
+
+    /// ID of the object.
+    pub id: String,
+
+    /// How the object compares to the one in the table.
+    pub operation: DryRunOperation,
+
+    /// Fields whose values would change.
+    pub changed: Vec<String>,
+
+    /// The object in the table, if any.
+    pub before: Option<BTreeMap<String, FtColumn>>,
+
+    /// The object that would have been written.
+    pub after: BTreeMap<String, FtColumn>,
+}
+
+impl DryRunRecord {
+    /// Create a new `DryRunRecord` from the column names of the table, and the
+    /// columns of the objects before and after the write.
+    pub fn new(
+        block_height: Option<u32>,
+        table: &str,
+        id: String,
+        column_names: &[String],
+        before: Option<Vec<FtColumn>>,
+        after: &[FtColumn],
+    ) -> Self {
+        let to_map = |columns: &[FtColumn]| {
+            column_names
+                .iter()
+                .cloned()
+                .zip(columns.iter().cloned())
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let after = to_map(after);
+        let before = before.map(|columns| to_map(&columns));
+
+        let changed: Vec<_> = match &before {
+            Some(before) => after
+                .iter()
+                .filter(|(name, value)| before.get(*name) != Some(*value))
+                .map(|(name, _)| name.clone())
+                .collect(),
+            None => after.keys().cloned().collect(),
+        };
+
+        let operation = match (&before, changed.is_empty()) {
+            (None, _) => DryRunOperation::Insert,
+            (Some(_), false) => DryRunOperation::Update,
+            (Some(_), true) => DryRunOperation::Unchanged,
+        };
+
+        Self {
+            block_height,
+            entity: table.to_string(),
+            id,
+            operation,
+            changed,
+            before,
+            after,
+        }
+    }
+}
+
+/// Execute a WASM indexer over the blocks in `[start_block, end_block]` without
+/// committing any of its changes, and return every object it would have written.
+///
+/// The dry run is executed against the tables of the deployed indexer, so the
+/// indexer must be registered with the same schema as the one in `manifest`.
+pub async fn dry_run(
+    config: &IndexerConfig,
+    manifest: &Manifest,
+    pool: IndexerConnectionPool,
+    start_block: u32,
+    end_block: u32,
+) -> IndexerResult<Vec<DryRunRecord>> {
+    let uid = manifest.uid();
+
+    if start_block == 0 || start_block > end_block {
+        return Err(IndexerError::Unknown(format!(
+            "Invalid block range [{start_block}, {end_block}]"
+        )));
+    }
+
+    let wasm_bytes = match manifest.module() {
+        Module::Wasm(_) => manifest.module_bytes()?,
+        Module::Native => {
+            return Err(IndexerError::Unknown(
+                "Dry runs are only supported for WASM indexers".to_string(),
+            ))
+        }
+    };
+
+    let schema_version = manifest.graphql_schema_content()?.version().to_string();
+
+    {
+        let mut conn = pool.acquire().await?;
+        let indexer_id = queries::get_indexer_id(
+            &mut conn,
+            manifest.namespace(),
+            manifest.identifier(),
+        )
+        .await
+        .map_err(|_| {
+            IndexerError::Unknown(format!(
+                "Indexer({uid}) must be deployed before it can be dry run"
+            ))
+        })?;
+
+        let schema =
+            queries::indexer_asset(&mut conn, &indexer_id, IndexerAssetType::Schema)
+                .await?;
+        if schema.digest != schema_version {
+            return Err(IndexerError::SchemaVersionMismatch(format!(
+                "Schema version {schema_version} does not match the schema version {} of the deployed Indexer({uid})",
+                schema.digest
+            )));
+        }
+    }
+
+    let mut executor =
+        WasmIndexExecutor::create(config, manifest, pool, schema_version, wasm_bytes)
+            .await?;
+    executor.db().lock().await.enable_dry_run();
+
+    // A Fuel node set in the manifest takes the place of all configured Fuel nodes.
+    let fuel_node_addrs = match manifest.fuel_client() {
+        Some(fuel_client) => vec![fuel_client.to_string()],
+        None => config.fuel_nodes().iter().map(|x| x.to_string()).collect(),
+    };
+    let client = FailoverClient::new(&fuel_node_addrs)
+        .map_err(|e| IndexerError::Unknown(e.to_string()))?;
+
+    info!("Dry running Indexer({uid}) over blocks [{start_block}, {end_block}].");
+
+    let mut cursor = Some((start_block - 1).to_string());
+    loop {
+        let (blocks, next_cursor, has_next_page) = match client
+            .retrieve_blocks(config.block_page_size, &cursor, Some(end_block), &uid)
+            .await
+        {
+            Ok(page) => page,
+            Err(IndexerError::EndBlockMet) => break,
+            Err(e) => return Err(e),
+        };
+
+        if blocks.is_empty() {
+            break;
+        }
+
+        executor.handle_events(blocks).await?;
+
+        cursor = next_cursor;
+        if !has_next_page {
+            break;
+        }
+    }
+
+    let records = executor.db().lock().await.finish_dry_run();
+
+    info!(
+        "Dry run of Indexer({uid}) would have written {} object(s).",
+        records.len()
+    );
+
+    Ok(records)
+}
+
+/// Write the records of a dry run in the given format.
+pub fn write_records(
+    writer: &mut impl Write,
+    records: &[DryRunRecord],
+    format: DryRunOutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        DryRunOutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, records)?;
+            writeln!(writer)?;
+        }
+        DryRunOutputFormat::Ndjson => {
+            for record in records {
+                serde_json::to_writer(&mut *writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_types::scalar::UID;
+
+    fn columns(id: &str, value: u64) -> Vec<FtColumn> {
+        vec![
+            FtColumn::ID(Some(UID::new(id.to_string()).unwrap())),
+            FtColumn::UInt8(Some(value)),
+        ]
+    }
+
+    #[test]
+    fn test_dry_run_record_compares_objects() {
+        let names = vec!["id".to_string(), "count".to_string()];
+        let id = "0".repeat(64);
+
+        let insert = DryRunRecord::new(
+            Some(1),
+            "test_namespace_index1.thing",
+            id.clone(),
+            &names,
+            None,
+            &columns(&id, 1),
+        );
+        assert_eq!(insert.operation, DryRunOperation::Insert);
+        assert_eq!(insert.changed, vec!["count".to_string(), "id".to_string()]);
+
+        let update = DryRunRecord::new(
+            Some(2),
+            "test_namespace_index1.thing",
+            id.clone(),
+            &names,
+            Some(columns(&id, 1)),
+            &columns(&id, 2),
+        );
+        assert_eq!(update.operation, DryRunOperation::Update);
+        assert_eq!(update.changed, vec!["count".to_string()]);
+
+        let unchanged = DryRunRecord::new(
+            Some(3),
+            "test_namespace_index1.thing",
+            id.clone(),
+            &names,
+            Some(columns(&id, 2)),
+            &columns(&id, 2),
+        );
+        assert_eq!(unchanged.operation, DryRunOperation::Unchanged);
+        assert!(unchanged.changed.is_empty());
+
+        let mut out = Vec::new();
+        write_records(&mut out, &[insert, update], DryRunOutputFormat::Ndjson).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+    }
+}
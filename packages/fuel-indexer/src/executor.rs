@@ -1,7 +1,7 @@
 /// Abstractions for indexer task execution.
 use crate::{
-    database::Database, ffi, queries::ClientExt, IndexerConfig, IndexerError,
-    IndexerResult,
+    database::Database, failover::FailoverClient, ffi, queries::ClientExt,
+    tunables::LimitingTunables, IndexerConfig, IndexerError, IndexerResult,
 };
 use async_std::sync::{Arc, Mutex};
 use async_trait::async_trait;
@@ -13,8 +13,13 @@ use fuel_core_client::client::{
 };
 use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{
-    defaults::*, manifest::Manifest, utils::serialize, WasmIndexerError,
+    defaults::*,
+    manifest::{ConfigSchema, Manifest},
+    utils::serialize,
+    WasmIndexerError,
 };
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::{core::IndexerLabel, METRICS};
 use fuel_indexer_types::{
     fuel::{field::*, *},
     scalar::{Bytes32, HexString},
@@ -22,22 +27,23 @@ use fuel_indexer_types::{
 use fuel_tx::UniqueIdentifier;
 use fuel_vm::prelude::Deserializable;
 use fuel_vm::state::ProgramState as ClientProgramState;
-use futures::Future;
+use futures::{future::BoxFuture, Future};
 use itertools::Itertools;
 use std::{
+    fmt::Display,
     marker::{Send, Sync},
     path::Path,
-    str::FromStr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
 };
 use tokio::{
+    sync::watch,
     task::spawn_blocking,
-    time::{sleep, Duration},
+    time::{sleep, timeout, Duration},
 };
-use tracing::{debug, error, info, warn};
+use tracing::{debug, debug_span, error, field, info, warn, Instrument, Span};
 use wasmer::{
-    imports, CompilerConfig, Cranelift, FunctionEnv, Instance, Memory, Module, Store,
-    TypedFunction,
+    imports, CompilerConfig, Cranelift, Engine, FunctionEnv, Instance, Memory, Module,
+    NativeEngineExt, Pages, Store, TypedFunction,
 };
 use wasmer_middlewares::metering::MeteringPoints;
 
@@ -74,22 +80,37 @@ impl From<ExecutorSource> for Vec<u8> {
 //
 // In general the logic in this function isn't very idiomatic, but that's because
 // types in `fuel_core_client` don't compile to WASM.
+///
+/// `block_page_size` and `stop_idle_indexers` are read from `config` on each
+/// iteration, so that they can be changed while the executor is running.
 pub fn run_executor<T: 'static + Executor + Send + Sync>(
-    config: &IndexerConfig,
+    config: watch::Receiver<IndexerConfig>,
     mut executor: T,
 ) -> impl Future<Output = ()> {
     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
 
     let end_block = executor.manifest().end_block();
-    let stop_idle_indexers = config.stop_idle_indexers;
     let indexer_uid = executor.manifest().uid();
-    let block_page_size = config.block_page_size;
 
-    let fuel_node_addr = executor
-        .manifest()
-        .fuel_client()
-        .map(|x| x.to_string())
-        .unwrap_or(config.fuel_node.to_string());
+    // Per-indexer metrics are only recorded if the indexer opts in via its manifest.
+    #[cfg(feature = "metrics")]
+    let metrics_label = executor.manifest().metrics().unwrap_or(false).then(|| {
+        IndexerLabel::new(
+            executor.manifest().namespace(),
+            executor.manifest().identifier(),
+        )
+    });
+
+    // A Fuel node set in the manifest takes the place of all configured Fuel nodes.
+    let fuel_node_addrs = match executor.manifest().fuel_client() {
+        Some(fuel_client) => vec![fuel_client.to_string()],
+        None => config
+            .borrow()
+            .fuel_nodes()
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+    };
 
     // Where should we initially start when fetching blocks from the client?
     let mut cursor = executor.manifest().start_block().map(|x| {
@@ -101,9 +122,12 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         }
     });
 
-    info!("Indexer({indexer_uid}) subscribing to Fuel node at {fuel_node_addr}");
+    info!(
+        "Indexer({indexer_uid}) subscribing to Fuel node(s) at {}",
+        fuel_node_addrs.join(", ")
+    );
 
-    let client = FuelClient::from_str(&fuel_node_addr).unwrap_or_else(|e| {
+    let client = FailoverClient::new(&fuel_node_addrs).unwrap_or_else(|e| {
         panic!("Indexer({indexer_uid}) client node connection failed: {e}.")
     });
 
@@ -120,18 +144,6 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         // Note that this count considers _consecutive_ failed calls.
         let mut consecutive_retries = 0;
 
-        // If we're testing or running on CI, we don't want indexers to run forever. But in production
-        // let the indexer service operator decide if they want to stop idle indexers.
-        //
-        // Maybe we can eventually make this MAX_CONSECUTIVE_EMPTY_BLOCK_RESPONSES value configurable
-        //
-        // Also note that this count considers _consecutive_ empty block requests.
-        let max_empty_block_reqs = if stop_idle_indexers {
-            MAX_CONSECUTIVE_EMPTY_BLOCK_RESPONSES
-        } else {
-            usize::MAX
-        };
-
         // Keep track of how many empty pages we've received from the client.
         let mut num_empty_block_reqs = 0;
 
@@ -142,34 +154,47 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 break;
             }
 
+            let (block_page_size, stop_idle_indexers) = {
+                let config = config.borrow();
+                (config.block_page_size, config.stop_idle_indexers)
+            };
+
+            // If we're testing or running on CI, we don't want indexers to run forever. But in production
+            // let the indexer service operator decide if they want to stop idle indexers.
+            //
+            // Maybe we can eventually make this MAX_CONSECUTIVE_EMPTY_BLOCK_RESPONSES value configurable
+            //
+            // Also note that this count considers _consecutive_ empty block requests.
+            let max_empty_block_reqs = if stop_idle_indexers {
+                MAX_CONSECUTIVE_EMPTY_BLOCK_RESPONSES
+            } else {
+                usize::MAX
+            };
+
             // Fetch the next page of blocks, and the starting cursor for the subsequent page
-            let (block_info, next_cursor, _has_next_page) =
-                match retrieve_blocks_from_node(
-                    &client,
-                    block_page_size,
-                    &cursor,
-                    end_block,
-                    &indexer_uid,
-                )
+            let (block_info, next_cursor, has_next_page) = match client
+                .retrieve_blocks(block_page_size, &cursor, end_block, &indexer_uid)
                 .await
-                {
-                    Ok((block_info, next_cursor, has_next_page)) => {
-                        (block_info, next_cursor, has_next_page)
-                    }
-                    Err(e) => {
-                        if let IndexerError::EndBlockMet = e {
-                            info!("Indexer({indexer_uid}) has met its end block; beginning indexer shutdown process.");
-                            executor.kill_switch().store(true, Ordering::SeqCst);
-                            continue;
-                        } else {
-                            error!(
-                                "Indexer({indexer_uid}) failed to fetch blocks: {e:?}",
-                            );
-                            sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
-                            continue;
+            {
+                Ok((block_info, next_cursor, has_next_page)) => {
+                    (block_info, next_cursor, has_next_page)
+                }
+                Err(e) => {
+                    if let IndexerError::EndBlockMet = e {
+                        info!("Indexer({indexer_uid}) has met its end block; beginning indexer shutdown process.");
+                        executor.kill_switch().store(true, Ordering::SeqCst);
+                        continue;
+                    } else {
+                        error!("Indexer({indexer_uid}) failed to fetch blocks: {e:?}",);
+                        #[cfg(feature = "metrics")]
+                        if let Some(label) = &metrics_label {
+                            METRICS.indexer.record_error(label, e.kind());
                         }
+                        sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
+                        continue;
                     }
-                };
+                }
+            };
 
             // If our block page request from the client returns empty, we sleep for a bit, and then continue.
             if block_info.is_empty() {
@@ -179,7 +204,11 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                     "Indexer({indexer_uid}) has no new blocks to process, sleeping zzZZ. (Empty response #{num_empty_block_reqs})"
                 );
 
-                if num_empty_block_reqs == max_empty_block_reqs {
+                if let Err(e) = executor.on_chain_head().await {
+                    error!("Indexer({indexer_uid}) failed to act on reaching the chain head: {e:?}");
+                }
+
+                if num_empty_block_reqs >= max_empty_block_reqs {
                     error!("No blocks being produced after {num_empty_block_reqs} empty responses. Indexer({indexer_uid}) giving up. <('.')>");
                     break;
                 }
@@ -189,8 +218,33 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 continue;
             }
 
+            #[cfg(feature = "metrics")]
+            let (num_blocks, last_height) = (
+                block_info.len() as u64,
+                block_info.last().map(|b| b.height).unwrap_or_default(),
+            );
+
             // The client responded with actual blocks, so attempt to index them.
-            let result = executor.handle_events(block_info).await;
+            #[cfg(feature = "metrics")]
+            let start_time = std::time::Instant::now();
+            let span = debug_span!(
+                "handle_events",
+                indexer = %indexer_uid,
+                block_height.start = block_info.first().map(|b| b.height),
+                block_height.end = block_info.last().map(|b| b.height),
+            );
+            let result = executor.handle_events(block_info).instrument(span).await;
+
+            #[cfg(feature = "metrics")]
+            if let Some(label) = &metrics_label {
+                METRICS.indexer.record_handler_duration(
+                    label,
+                    start_time.elapsed().as_millis() as f64,
+                );
+                if let Err(e) = &result {
+                    METRICS.indexer.record_error(label, e.kind());
+                }
+            }
 
             // If the kill switch has been triggered, the executor exits early.
             if executor.kill_switch().load(Ordering::SeqCst) {
@@ -208,13 +262,56 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                         );
                         break;
                     }
+
+                    if let Some(&WasmIndexerError::InvalidConfig) =
+                        e.downcast_ref::<WasmIndexerError>()
+                    {
+                        error!("Indexer({indexer_uid}) terminating due to an invalid config. Redeploy the indexer with a valid config.");
+                        break;
+                    }
+
+                    if let Some(&WasmIndexerError::ConstraintViolation) =
+                        e.downcast_ref::<WasmIndexerError>()
+                    {
+                        // Constraint violations are deterministic, so retrying won't help.
+                        warn!("Indexer({indexer_uid}) handler violated a column constraint. This is not a retry-able error. Continuing...");
+                        cursor = next_cursor;
+                        continue;
+                    }
+                }
+
+                if let IndexerError::InvalidConfig(_) = e {
+                    error!("Indexer({indexer_uid}) terminating: {e}. Redeploy the indexer with a valid config.");
+                    break;
+                }
+
+                if let IndexerError::ConstraintViolation(..) = e {
+                    warn!("{e}. This is not a retry-able error. Continuing...");
+                    cursor = next_cursor;
+                    continue;
                 }
+
                 // Run time metering is deterministic. There is no point in retrying.
                 if let IndexerError::RunTimeLimitExceededError = e {
                     error!("Indexer({indexer_uid}) executor run time limit exceeded. Giving up. <('.')>. Consider increasing metering points");
                     break;
                 }
 
+                // Neither is the memory usage of the handler.
+                if let IndexerError::MemoryLimitExceededError = e {
+                    error!("Indexer({indexer_uid}) executor memory limit exceeded. Giving up. <('.')>. Consider increasing max memory pages");
+                    break;
+                }
+
+                // A handler that timed out is retried like any other error, on a new
+                // instance of the module. Once the retries are used up, the indexer stops.
+                if let IndexerError::HandlerTimeoutError = e {
+                    if consecutive_retries >= INDEXER_FAILED_CALLS {
+                        error!("Indexer({indexer_uid}) terminating: handler timed out after {consecutive_retries} retries. Consider increasing the handler timeout.");
+                        break;
+                    }
+                }
+
                 // We don't want to retry forever as that eats resources.
                 if consecutive_retries >= INDEXER_FAILED_CALLS {
                     error!(
@@ -243,6 +340,19 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 continue;
             }
 
+            #[cfg(feature = "metrics")]
+            if let Some(label) = &metrics_label {
+                METRICS
+                    .indexer
+                    .record_blocks(label, num_blocks, last_height);
+                if let Some(head) = client.head_height() {
+                    METRICS.indexer.record_blocks_behind_head(
+                        label,
+                        head.saturating_sub(last_height),
+                    );
+                }
+            }
+
             // If we get a non-empty response, we reset the counter.
             num_empty_block_reqs = 0;
 
@@ -257,6 +367,12 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
 
             // Since we had successful call, we reset the retry count.
             consecutive_retries = 0;
+
+            if !has_next_page {
+                if let Err(e) = executor.on_chain_head().await {
+                    error!("Indexer({indexer_uid}) failed to act on reaching the chain head: {e:?}");
+                }
+            }
         }
     }
 }
@@ -265,9 +381,6 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
 ///
 /// This was abstracted out of `run_executor` in order to allow for use in the benchmarking suite
 /// to give consistent timings.
-///
-/// If there is an issue fetching blocks, we return an empty cursor and an empty list of blocks, then
-/// run_executor will determine whether or not we should sleep for a bit and try again.
 pub async fn retrieve_blocks_from_node(
     client: &FuelClient,
     block_page_size: usize,
@@ -275,6 +388,48 @@ pub async fn retrieve_blocks_from_node(
     end_block: Option<u32>,
     indexer_uid: &str,
 ) -> IndexerResult<(Vec<BlockData>, Option<String>, bool)> {
+    let page =
+        retrieve_page_from_node(client, block_page_size, cursor, end_block, indexer_uid)
+            .await?;
+    Ok((page.blocks, page.cursor, page.has_next_page))
+}
+
+/// A page of blocks retrieved from a client node.
+pub(crate) struct BlockPage {
+    /// Blocks in the page.
+    pub blocks: Vec<BlockData>,
+
+    /// Cursor from which to fetch the next page.
+    pub cursor: Option<String>,
+
+    /// Whether the node has more blocks after this page.
+    pub has_next_page: bool,
+
+    /// Height of the node's latest block.
+    pub head_height: u32,
+}
+
+/// Retrieve a page of blocks from a client node, along with the height of its latest block.
+///
+/// If there is an issue fetching blocks, the error is returned so that the `FailoverClient` can
+/// try another node, and run_executor can determine whether or not we should sleep for a bit and try again.
+#[tracing::instrument(
+    name = "fetch_blocks",
+    level = "debug",
+    skip_all,
+    fields(
+        indexer = %indexer_uid,
+        block_height.start = field::Empty,
+        block_height.end = field::Empty,
+    )
+)]
+pub(crate) async fn retrieve_page_from_node(
+    client: &FuelClient,
+    block_page_size: usize,
+    cursor: &Option<String>,
+    end_block: Option<u32>,
+    indexer_uid: &str,
+) -> IndexerResult<BlockPage> {
     // Let's check if we need less blocks than block_page_size.
     let page_size = if let (Some(start), Some(end)) = (cursor, end_block) {
         if let Ok(start) = start.parse::<u32>() {
@@ -303,19 +458,11 @@ pub async fn retrieve_blocks_from_node(
             results: page_size,
             direction: PageDirection::Forward,
         })
-        .await
-        .unwrap_or_else(|e| {
-            error!("Indexer({indexer_uid}) failed to retrieve blocks: {e:?}");
-            // Setting an empty cursor will cause the indexer to sleep for a bit and try again.
-            PaginatedResult {
-                cursor: None,
-                results: vec![],
-                has_next_page: false,
-                has_previous_page: false,
-            }
-        });
+        .await?;
 
-    let chain_id = client.chain_info().await?.consensus_parameters.chain_id;
+    let chain_info = client.chain_info().await?;
+    let chain_id = chain_info.consensus_parameters.chain_id;
+    let head_height = chain_info.latest_block.header.height;
 
     let mut block_info = Vec::new();
     for block in results.into_iter() {
@@ -515,7 +662,18 @@ pub async fn retrieve_blocks_from_node(
         block_info.push(block);
     }
 
-    Ok((block_info, cursor, has_next_page))
+    if let (Some(first), Some(last)) = (block_info.first(), block_info.last()) {
+        Span::current()
+            .record("block_height.start", first.height)
+            .record("block_height.end", last.height);
+    }
+
+    Ok(BlockPage {
+        blocks: block_info,
+        cursor,
+        has_next_page,
+        head_height,
+    })
 }
 
 /// Executors are responsible for the actual indexing of data.
@@ -531,6 +689,11 @@ where
     fn manifest(&self) -> &Manifest;
 
     fn kill_switch(&self) -> &Arc<AtomicBool>;
+
+    /// Called whenever the executor has caught up with the chain head.
+    async fn on_chain_head(&mut self) -> IndexerResult<()> {
+        Ok(())
+    }
 }
 
 /// WASM indexer runtime environment responsible for fetching/saving data to and from the database.
@@ -551,6 +714,12 @@ pub struct IndexEnv {
     /// Kill switch for this indexer. When true, the indexer service indicated
     /// that the indexer is being terminated.
     pub kill_switch: Arc<AtomicBool>,
+
+    /// UID of the indexer, recorded on the spans of FFI calls.
+    pub uid: String,
+
+    /// Height of the block currently being processed, as last set by the handler.
+    pub block_height: Arc<AtomicU32>,
 }
 
 impl IndexEnv {
@@ -568,17 +737,28 @@ impl IndexEnv {
             dealloc: None,
             db: Arc::new(Mutex::new(db)),
             kill_switch,
+            uid: manifest.uid(),
+            block_height: Arc::new(AtomicU32::new(0)),
         })
     }
+
+    /// Height of the block currently being processed.
+    pub fn block_height(&self) -> u32 {
+        self.block_height.load(Ordering::SeqCst)
+    }
 }
 
+/// Function used by a native executor to handle a page of blocks.
+pub type NativeHandler = Arc<
+    dyn Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> BoxFuture<'static, IndexerResult<()>>
+        + Send
+        + Sync,
+>;
+
 /// Native executors differ from WASM executors in that they are not sandboxed; they are merely a
 /// set of native Rust functions that (run/execute/are spawned) directly from the indexer service
 /// process.
-pub struct NativeIndexExecutor<F>
-where
-    F: Future<Output = IndexerResult<()>> + Send,
-{
+pub struct NativeIndexExecutor {
     /// Reference to the connected database.
     db: Arc<Mutex<Database>>,
 
@@ -586,23 +766,33 @@ where
     manifest: Manifest,
 
     /// Function that handles events.
-    handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+    handle_events_fn: NativeHandler,
+
+    /// Maximum duration of a single call to `handle_events`.
+    ///
+    /// The timeout can only fire while the handler is awaiting, e.g., a database
+    /// operation. A handler that runs synchronously for longer is not preempted,
+    /// and times out at its next `.await`.
+    handler_timeout: Option<Duration>,
 
     /// Kill switch. When set to true, the indexer must stop execution.
     kill_switch: Arc<AtomicBool>,
 }
 
-impl<F> NativeIndexExecutor<F>
-where
-    F: Future<Output = IndexerResult<()>> + Send,
-{
+impl NativeIndexExecutor {
     /// Create a new `NativeIndexExecutor`.
     pub async fn new(
         manifest: &Manifest,
         pool: IndexerConnectionPool,
         config: &IndexerConfig,
-        handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+        handle_events_fn: NativeHandler,
     ) -> IndexerResult<Self> {
+        let handler_timeout = resolve_limit(
+            &manifest.uid(),
+            "handler_timeout",
+            manifest.limits().and_then(|limits| limits.handler_timeout),
+            config.handler_timeout,
+        );
         let mut db = Database::new(pool.clone(), manifest, config).await;
         let mut conn = pool.acquire().await?;
         let version = fuel_indexer_database::queries::type_id_latest(
@@ -617,35 +807,69 @@ where
             db: Arc::new(Mutex::new(db)),
             manifest: manifest.to_owned(),
             handle_events_fn,
+            handler_timeout: handler_timeout.map(Duration::from_secs),
             kill_switch,
         })
     }
 
     /// Create a new `NativeIndexExecutor`.
-    pub async fn create(
+    pub async fn create<F>(
         config: &IndexerConfig,
         manifest: &Manifest,
         pool: IndexerConnectionPool,
         handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
-    ) -> IndexerResult<Self> {
-        NativeIndexExecutor::new(manifest, pool.clone(), config, handle_events).await
+    ) -> IndexerResult<Self>
+    where
+        F: Future<Output = IndexerResult<()>> + Send + 'static,
+    {
+        NativeIndexExecutor::new(manifest, pool, config, native_handler(handle_events))
+            .await
     }
 }
 
-#[async_trait]
-impl<F> Executor for NativeIndexExecutor<F>
+/// Wrap the `handle_events` function of a native indexer into a `NativeHandler`.
+pub fn native_handler<F>(
+    handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+) -> NativeHandler
 where
-    F: Future<Output = IndexerResult<()>> + Send,
+    F: Future<Output = IndexerResult<()>> + Send + 'static,
 {
+    Arc::new(move |blocks, db| Box::pin(handle_events(blocks, db)))
+}
+
+#[async_trait]
+impl Executor for NativeIndexExecutor {
     /// Handle events for  native executor.
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
-        self.db.lock().await.start_transaction().await?;
-        let res = (self.handle_events_fn)(blocks, self.db.clone()).await;
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
         let uid = self.manifest.uid();
+
+        self.db.lock().await.start_transaction().await?;
+
+        let handle = (self.handle_events_fn)(blocks, self.db.clone());
+
+        let res = match self.handler_timeout {
+            Some(handler_timeout) => match timeout(handler_timeout, handle).await {
+                Ok(res) => res,
+                Err(_) => {
+                    error!(
+                        "Indexer({uid}) handle_events exceeded the timeout of {}s.",
+                        handler_timeout.as_secs()
+                    );
+                    self.db.lock().await.revert_transaction().await?;
+                    return Err(IndexerError::HandlerTimeoutError);
+                }
+            },
+            None => handle.await,
+        };
+
         if let Err(e) = res {
-            error!("NativeIndexExecutor({uid}) handle_events failed: {e:?}.");
+            error!("NativeIndexExecutor({uid}) handle_events failed: {e}.");
             self.db.lock().await.revert_transaction().await?;
-            return Err(IndexerError::NativeExecutionRuntimeError);
+            return Err(e);
         } else {
             // Do not commit if kill switch has been triggered.
             if self.kill_switch.load(Ordering::SeqCst) {
@@ -666,6 +890,82 @@ where
     }
 }
 
+/// Resolve a resource limit requested in an indexer's manifest against the limit set by the
+/// indexer service, which acts as a ceiling. If the manifest doesn't request a limit, the
+/// service's limit is used.
+fn resolve_limit<T: Ord + Copy + Display>(
+    indexer_uid: &str,
+    name: &str,
+    requested: Option<T>,
+    ceiling: Option<T>,
+) -> Option<T> {
+    match (requested, ceiling) {
+        (Some(requested), Some(ceiling)) if requested > ceiling => {
+            warn!("Indexer({indexer_uid}) requested {name} of {requested}, which exceeds the service limit of {ceiling}. Using {ceiling}.");
+            Some(ceiling)
+        }
+        (Some(requested), _) => Some(requested),
+        (None, ceiling) => ceiling,
+    }
+}
+
+/// Instantiate a compiled WASM module in a new store, with `idx_env` as its environment.
+#[allow(clippy::result_large_err)]
+fn instantiate(
+    engine: &Engine,
+    module: &Module,
+    idx_env: IndexEnv,
+    schema_version: &str,
+) -> IndexerResult<(Store, Instance)> {
+    let mut store = Store::new(engine.clone());
+
+    let env = FunctionEnv::new(&mut store, idx_env);
+
+    let mut imports = imports! {};
+    for (export_name, export) in ffi::get_exports(&mut store, &env) {
+        imports.define("env", &export_name, export.clone());
+    }
+
+    let instance = Instance::new(&mut store, module, &imports)?;
+
+    if !instance
+        .exports
+        .contains(ffi::MODULE_ENTRYPOINT.to_string())
+    {
+        return Err(IndexerError::MissingHandler);
+    }
+
+    // FunctionEnvMut and StoreMut must be scoped because they can't
+    // be used across await
+    {
+        let mut env_mut = env.into_mut(&mut store);
+        let (data_mut, mut store_mut) = env_mut.data_and_store_mut();
+
+        let schema_version_from_wasm = ffi::get_version(&mut store_mut, &instance)?;
+
+        if schema_version_from_wasm != schema_version {
+            return Err(IndexerError::SchemaVersionMismatch(format!(
+                "Schema version from WASM {} does not match schema version from database {}",
+                schema_version_from_wasm, schema_version
+            )));
+        }
+
+        data_mut.memory = Some(instance.exports.get_memory("memory")?.clone());
+        data_mut.alloc = Some(
+            instance
+                .exports
+                .get_typed_function(&store_mut, "alloc_fn")?,
+        );
+        data_mut.dealloc = Some(
+            instance
+                .exports
+                .get_typed_function(&store_mut, "dealloc_fn")?,
+        );
+    }
+
+    Ok((store, instance))
+}
+
 /// WASM executors are the primary means of execution.
 ///
 /// WASM executors contain a WASM module that is instantiated and executed by the indexer service on a
@@ -677,7 +977,10 @@ pub struct WasmIndexExecutor {
     instance: Instance,
 
     /// Associated wasmer module.
-    _module: Module,
+    module: Module,
+
+    /// Engine with which the module was compiled.
+    engine: Engine,
 
     /// Associated wasmer store.
     store: Arc<Mutex<Store>>,
@@ -685,14 +988,35 @@ pub struct WasmIndexExecutor {
     /// Reference to the connected database.
     db: Arc<Mutex<Database>>,
 
+    /// Connection pool from which the database connection is acquired.
+    pool: IndexerConnectionPool,
+
+    /// Indexer service configuration.
+    config: IndexerConfig,
+
+    /// Version of the indexer schema.
+    schema_version: String,
+
     /// Number of metering points to use for this executor.
     metering_points: Option<u64>,
 
+    /// Maximum number of pages of linear memory the WASM module can use.
+    max_memory_pages: Option<u32>,
+
+    /// Set when the WASM module attempts to use more memory than `max_memory_pages`.
+    memory_limit_exceeded: Arc<AtomicBool>,
+
+    /// Maximum duration of a single call to `handle_events`.
+    handler_timeout: Option<Duration>,
+
     /// Manifest of the indexer.
     manifest: Manifest,
 
     /// Kill switch. When set to true, the indexer must stop execution.
     kill_switch: Arc<AtomicBool>,
+
+    /// Whether the executor has caught up with the chain head since it started.
+    reached_chain_head: bool,
 }
 
 impl WasmIndexExecutor {
@@ -704,9 +1028,39 @@ impl WasmIndexExecutor {
         pool: IndexerConnectionPool,
         schema_version: String,
     ) -> IndexerResult<Self> {
+        let uid = manifest.uid();
+
+        if let Some(config_schema) = ConfigSchema::from_wasm(wasm_bytes.as_ref())? {
+            manifest.validate_config(&config_schema)?;
+        }
+
+        let limits = manifest.limits().cloned().unwrap_or_default();
+        let metering_points = resolve_limit(
+            &uid,
+            "metering_points",
+            limits.metering_points,
+            config.metering_points,
+        );
+        let max_memory_pages = resolve_limit(
+            &uid,
+            "max_memory_pages",
+            limits.max_memory_pages,
+            config.max_memory_pages,
+        );
+        let handler_timeout = resolve_limit(
+            &uid,
+            "handler_timeout",
+            limits.handler_timeout,
+            config.handler_timeout,
+        );
+
+        if handler_timeout.is_some() && metering_points.is_none() {
+            warn!("Indexer({uid}) has a handler timeout but no metering points. A timed out handler that never calls into the database will keep running in the background.");
+        }
+
         let mut compiler_config = Cranelift::new();
 
-        if let Some(metering_points) = config.metering_points {
+        if let Some(metering_points) = metering_points {
             // `Metering` needs to be configured with a limit and a cost
             // function. For each `Operator`, the metering middleware will call
             // the cost function and subtract the cost from the remaining points.
@@ -717,71 +1071,84 @@ impl WasmIndexExecutor {
 
         let kill_switch = Arc::new(AtomicBool::new(false));
 
-        let idx_env = IndexEnv::new(pool, manifest, config, kill_switch.clone()).await?;
+        let memory_limit_exceeded = Arc::new(AtomicBool::new(false));
 
-        let db: Arc<Mutex<Database>> = idx_env.db.clone();
-
-        let mut store = Store::new(compiler_config);
-
-        let module = Module::new(&store, &wasm_bytes)?;
-
-        let env = FunctionEnv::new(&mut store, idx_env);
-
-        let mut imports = imports! {};
-        for (export_name, export) in ffi::get_exports(&mut store, &env) {
-            imports.define("env", &export_name, export.clone());
+        let mut engine: Engine = compiler_config.into();
+        if let Some(max_memory_pages) = max_memory_pages {
+            engine.set_tunables(LimitingTunables::new(
+                Pages(max_memory_pages),
+                memory_limit_exceeded.clone(),
+            ));
         }
 
-        let instance = Instance::new(&mut store, &module, &imports)?;
+        let module = Module::new(&engine, &wasm_bytes)?;
 
-        if !instance
-            .exports
-            .contains(ffi::MODULE_ENTRYPOINT.to_string())
-        {
-            return Err(IndexerError::MissingHandler);
-        }
+        let idx_env =
+            IndexEnv::new(pool.clone(), manifest, config, kill_switch.clone()).await?;
 
-        // FunctionEnvMut and StoreMut must be scoped because they can't
-        // be used across await
-        {
-            let mut env_mut = env.clone().into_mut(&mut store);
-            let (data_mut, mut store_mut) = env_mut.data_and_store_mut();
-
-            let schema_version_from_wasm = ffi::get_version(&mut store_mut, &instance)?;
-
-            if schema_version_from_wasm != schema_version {
-                return Err(IndexerError::SchemaVersionMismatch(format!(
-                    "Schema version from WASM {} does not match schema version from database {}",
-                    schema_version_from_wasm, schema_version
-                )));
-            }
+        let db: Arc<Mutex<Database>> = idx_env.db.clone();
 
-            data_mut.memory = Some(instance.exports.get_memory("memory")?.clone());
-            data_mut.alloc = Some(
-                instance
-                    .exports
-                    .get_typed_function(&store_mut, "alloc_fn")?,
-            );
-            data_mut.dealloc = Some(
-                instance
-                    .exports
-                    .get_typed_function(&store_mut, "dealloc_fn")?,
-            );
-        }
+        let (store, instance) = instantiate(&engine, &module, idx_env, &schema_version)
+            .map_err(|e| {
+                if memory_limit_exceeded.load(Ordering::SeqCst) {
+                    error!("Indexer({uid}) WASM module requires more than {max_memory_pages:?} pages of memory.");
+                    IndexerError::MemoryLimitExceededError
+                } else {
+                    e
+                }
+            })?;
 
-        db.lock().await.load_schema(schema_version).await?;
+        db.lock().await.load_schema(schema_version.clone()).await?;
 
         Ok(WasmIndexExecutor {
             instance,
-            _module: module,
+            module,
+            engine,
             store: Arc::new(Mutex::new(store)),
-            db: db.clone(),
-            metering_points: config.metering_points,
+            db,
+            pool,
+            config: config.clone(),
+            schema_version,
+            metering_points,
+            max_memory_pages,
+            memory_limit_exceeded,
+            handler_timeout: handler_timeout.map(Duration::from_secs),
             manifest: manifest.clone(),
             kill_switch,
+            reached_chain_head: false,
         })
     }
 
+    /// Replace the module instance, and its database connection, with new ones.
+    ///
+    /// Used when a call to `handle_events` times out. The handler keeps running on its
+    /// own thread, holding the lock on the old store, until it traps. This happens at the
+    /// latest when it runs out of metering points, or at its next database call, since the
+    /// transaction of the old database connection has been reverted.
+    async fn reinstantiate(&mut self) -> IndexerResult<()> {
+        let idx_env = IndexEnv::new(
+            self.pool.clone(),
+            &self.manifest,
+            &self.config,
+            self.kill_switch.clone(),
+        )
+        .await?;
+        let db = idx_env.db.clone();
+
+        let (store, instance) =
+            instantiate(&self.engine, &self.module, idx_env, &self.schema_version)?;
+        db.lock()
+            .await
+            .load_schema(self.schema_version.clone())
+            .await?;
+
+        self.store = Arc::new(Mutex::new(store));
+        self.instance = instance;
+        self.db = db;
+
+        Ok(())
+    }
+
     /// Restore index from wasm file
     pub async fn from_file(
         p: impl AsRef<Path>,
@@ -816,6 +1183,11 @@ impl WasmIndexExecutor {
         }
     }
 
+    /// Returns the database used by this executor.
+    pub(crate) fn db(&self) -> &Arc<Mutex<Database>> {
+        &self.db
+    }
+
     /// Returns true if metering is enabled.
     pub fn metering_enabled(&self) -> bool {
         self.metering_points.is_some()
@@ -876,7 +1248,10 @@ impl Executor for WasmIndexExecutor {
         if let Some(metering_points) = self.metering_points {
             self.set_metering_points(metering_points).await?
         }
-        let bytes = serialize(&blocks);
+        self.memory_limit_exceeded.store(false, Ordering::SeqCst);
+
+        let bytes = debug_span!("serialize", blocks = blocks.len())
+            .in_scope(|| serialize(&blocks));
         let uid = self.manifest.uid();
 
         let fun = {
@@ -889,11 +1264,15 @@ impl Executor for WasmIndexExecutor {
 
         let _ = self.db.lock().await.start_transaction().await?;
 
-        let res = spawn_blocking({
+        let mut handle = spawn_blocking({
             let store = self.store.clone();
             let instance = self.instance.clone();
             let metering_enabled = self.metering_enabled();
+            // The blocking task runs on a different thread, so the current span has
+            // to be passed along explicitly in order for FFI calls to be nested under it.
+            let span = debug_span!("wasm_handle_events");
             move || {
+                let _span = span.entered();
                 let store_guard =
                     tokio::runtime::Handle::current().block_on(store.lock());
                 let mut arg =
@@ -905,13 +1284,51 @@ impl Executor for WasmIndexExecutor {
 
                 fun.call(&mut arg.store(), ptr, len)
             }
-        })
-        .await?;
+        });
+
+        let res = match self.handler_timeout {
+            Some(handler_timeout) => match timeout(handler_timeout, &mut handle).await {
+                Ok(res) => res?,
+                Err(_) => {
+                    error!(
+                        "Indexer({uid}) handle_events exceeded the timeout of {}s.",
+                        handler_timeout.as_secs()
+                    );
+                    // The handler can't be stopped from here. Its changes are
+                    // rolled back, and it's left to trap on its own thread, while a
+                    // new instance takes its place.
+                    self.db.lock().await.revert_transaction().await?;
+                    self.reinstantiate().await?;
+                    return Err(IndexerError::HandlerTimeoutError);
+                }
+            },
+            None => handle.await?,
+        };
+
+        #[cfg(feature = "metrics")]
+        if let (Some(true), Some(metering_points)) =
+            (self.manifest.metrics(), self.metering_points)
+        {
+            let used = match self.get_remaining_metering_points().await {
+                Some(MeteringPoints::Remaining(remaining)) => metering_points - remaining,
+                _ => metering_points,
+            };
+            let label =
+                IndexerLabel::new(self.manifest.namespace(), self.manifest.identifier());
+            METRICS.indexer.record_metering_points_used(&label, used);
+        }
 
         if let Err(e) = res {
             if self.metering_points_exhausted().await {
                 self.db.lock().await.revert_transaction().await?;
                 return Err(IndexerError::RunTimeLimitExceededError);
+            } else if self.memory_limit_exceeded.load(Ordering::SeqCst) {
+                error!(
+                    "Indexer({uid}) WASM execution exceeded the memory limit of {:?} pages.",
+                    self.max_memory_pages
+                );
+                self.db.lock().await.revert_transaction().await?;
+                return Err(IndexerError::MemoryLimitExceededError);
             } else {
                 if let Some(e) = e.downcast_ref::<WasmIndexerError>() {
                     match e {
@@ -948,4 +1365,17 @@ impl Executor for WasmIndexExecutor {
     fn manifest(&self) -> &Manifest {
         &self.manifest
     }
+
+    /// If this indexer is a pending blue/green deployment, cut queries over to it the
+    /// first time it catches up with the chain head.
+    async fn on_chain_head(&mut self) -> IndexerResult<()> {
+        if self.reached_chain_head {
+            return Ok(());
+        }
+        self.db.lock().await.activate_deployment().await?;
+        // Set only once the activation succeeded, so that a failed activation is
+        // retried the next time the chain head is reached.
+        self.reached_chain_head = true;
+        Ok(())
+    }
 }
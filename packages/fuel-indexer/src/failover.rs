@@ -0,0 +1,258 @@
+//! Failover between the Fuel nodes from which an indexer fetches blocks.
+
+use crate::{executor::retrieve_page_from_node, IndexerError, IndexerResult};
+use fuel_core_client::client::FuelClient;
+use fuel_indexer_lib::{
+    defaults,
+    health::{EndpointHealth, FuelNodeHealth, FUEL_NODE_HEALTH},
+};
+use fuel_indexer_types::fuel::BlockData;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// A Fuel node, along with its observed health.
+struct Endpoint {
+    /// Address of the node.
+    addr: String,
+
+    /// Client connected to the node.
+    client: FuelClient,
+
+    /// Observed health of the node, shared with every other client of the node.
+    health: Arc<Mutex<EndpointHealth>>,
+}
+
+impl Endpoint {
+    fn health(&self) -> MutexGuard<'_, EndpointHealth> {
+        self.health.lock().expect("Poisoned endpoint health lock.")
+    }
+
+    // Nodes that failed recently rank below nodes that did not, and are otherwise
+    // ranked by their latency. Lower is better.
+    //
+    // Once a node's cooldown has passed, it's ranked as if it were healthy again, so
+    // that the next request probes it, and fails back to it if it has recovered.
+    fn score(&self) -> (u32, Duration) {
+        let health = self.health();
+        let cooldown = Duration::from_secs(defaults::FUEL_NODE_FAILBACK_COOLDOWN_SECS);
+        let consecutive_errors = if health.is_failing(cooldown) {
+            health.consecutive_errors
+        } else {
+            0
+        };
+        (consecutive_errors, health.latency.unwrap_or_default())
+    }
+}
+
+/// Client that fetches blocks from the healthiest of a set of Fuel nodes, failing
+/// over to the next healthiest node whenever a request fails.
+///
+/// A node whose latest block is behind the indexer's cursor is never read from.
+pub struct FailoverClient {
+    endpoints: Vec<Endpoint>,
+}
+
+impl FailoverClient {
+    /// Create a new `FailoverClient` from node addresses, in order of preference.
+    ///
+    /// The health of each node is shared by every client in this process.
+    pub fn new(addrs: &[String]) -> anyhow::Result<Self> {
+        Self::with_health(addrs, &FUEL_NODE_HEALTH)
+    }
+
+    /// Create a new `FailoverClient` from node addresses, in order of preference,
+    /// that tracks the health of each node in `health`.
+    pub fn with_health(
+        addrs: &[String],
+        health: &FuelNodeHealth,
+    ) -> anyhow::Result<Self> {
+        let endpoints = addrs
+            .iter()
+            .map(|addr| {
+                Ok(Endpoint {
+                    addr: addr.clone(),
+                    client: FuelClient::from_str(addr)?,
+                    health: health.endpoint(addr),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self { endpoints })
+    }
+
+    /// Health of each node, in order of preference.
+    pub fn health(&self) -> Vec<(String, EndpointHealth)> {
+        self.endpoints
+            .iter()
+            .map(|e| (e.addr.clone(), e.health().clone()))
+            .collect()
+    }
+
+    /// Highest block height reported by any of the nodes.
+    pub fn head_height(&self) -> Option<u32> {
+        self.endpoints
+            .iter()
+            .filter_map(|e| e.health().head_height)
+            .max()
+    }
+
+    // Nodes ranked by their health, with ties broken by order of preference.
+    fn ranked(&self) -> Vec<&Endpoint> {
+        let mut endpoints: Vec<&Endpoint> = self.endpoints.iter().collect();
+        endpoints.sort_by_key(|e| e.score());
+        endpoints
+    }
+
+    /// Retrieve the next page of blocks after `cursor`, trying each node in turn
+    /// until one succeeds.
+    pub async fn retrieve_blocks(
+        &self,
+        block_page_size: usize,
+        cursor: &Option<String>,
+        end_block: Option<u32>,
+        indexer_uid: &str,
+    ) -> IndexerResult<(Vec<BlockData>, Option<String>, bool)> {
+        let cursor_height = cursor
+            .as_ref()
+            .and_then(|c| c.parse::<u32>().ok())
+            .unwrap_or_default();
+
+        let mut error = IndexerError::NoAvailableFuelNodeError;
+
+        for endpoint in self.ranked() {
+            let addr = &endpoint.addr;
+
+            let start = Instant::now();
+            match retrieve_page_from_node(
+                &endpoint.client,
+                block_page_size,
+                cursor,
+                end_block,
+                indexer_uid,
+            )
+            .await
+            {
+                Err(IndexerError::EndBlockMet) => return Err(IndexerError::EndBlockMet),
+                Err(e) => {
+                    warn!("Indexer({indexer_uid}) failed to retrieve blocks from Fuel node at {addr}: {e}");
+                    endpoint.health().record_error();
+                    error = e;
+                }
+                Ok(page) => {
+                    let head_height = page.head_height;
+                    endpoint
+                        .health()
+                        .record_success(start.elapsed(), head_height);
+
+                    // A node that is behind returns no blocks, which would be
+                    // mistaken for having reached the chain head.
+                    if head_height < cursor_height {
+                        warn!("Fuel node at {addr} is behind Indexer({indexer_uid}) (#{head_height} < #{cursor_height}), skipping.");
+                        continue;
+                    }
+
+                    return Ok((page.blocks, page.cursor, page.has_next_page));
+                }
+            }
+        }
+
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nothing listens on port 1, so requests to these nodes fail immediately.
+    fn unreachable_nodes() -> Vec<String> {
+        vec!["127.0.0.1:1".to_string(), "localhost:1".to_string()]
+    }
+
+    // Each test tracks health separately, so that tests don't affect each other.
+    fn client() -> FailoverClient {
+        FailoverClient::with_health(&unreachable_nodes(), &FuelNodeHealth::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_failing_nodes_are_ranked_below_healthy_nodes() {
+        let client = client();
+
+        let ranked = client.ranked();
+        assert_eq!(ranked[0].addr, "127.0.0.1:1");
+
+        client.endpoints[0].health().record_error();
+        client.endpoints[1]
+            .health()
+            .record_success(Duration::from_millis(500), 10);
+
+        let ranked = client.ranked();
+        assert_eq!(ranked[0].addr, "localhost:1");
+        assert_eq!(client.head_height(), Some(10));
+    }
+
+    #[test]
+    fn test_faster_nodes_are_ranked_first() {
+        let client = client();
+
+        client.endpoints[0]
+            .health()
+            .record_success(Duration::from_millis(500), 10);
+        client.endpoints[1]
+            .health()
+            .record_success(Duration::from_millis(50), 9);
+
+        let ranked = client.ranked();
+        assert_eq!(ranked[0].addr, "localhost:1");
+        assert_eq!(client.head_height(), Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_blocks_fails_over_to_every_node() {
+        let client = client();
+
+        let result = client
+            .retrieve_blocks(10, &Some("5".to_string()), None, "test.index")
+            .await;
+
+        assert!(result.is_err());
+        for (_, health) in client.health() {
+            assert_eq!(health.consecutive_errors, 1);
+            assert_eq!(health.errors, 1);
+        }
+    }
+
+    #[test]
+    fn test_failing_nodes_are_ranked_by_preference_after_cooldown() {
+        let client = client();
+
+        client.endpoints[0].health().record_error();
+        let ranked = client.ranked();
+        assert_eq!(ranked[0].addr, "localhost:1");
+
+        // Backdate the error past the cooldown, so that the node is tried again.
+        let cooldown = Duration::from_secs(defaults::FUEL_NODE_FAILBACK_COOLDOWN_SECS);
+        client.endpoints[0].health().last_error = Instant::now().checked_sub(cooldown);
+
+        let ranked = client.ranked();
+        assert_eq!(ranked[0].addr, "127.0.0.1:1");
+        assert_eq!(client.health()[0].1.consecutive_errors, 1);
+    }
+
+    #[test]
+    fn test_clients_share_node_health() {
+        let health = FuelNodeHealth::default();
+        let a = FailoverClient::with_health(&unreachable_nodes(), &health).unwrap();
+        let b = FailoverClient::with_health(&unreachable_nodes(), &health).unwrap();
+
+        a.endpoints[0].health().record_error();
+
+        assert_eq!(b.health()[0].1.consecutive_errors, 1);
+        assert_eq!(health.get("127.0.0.1:1").unwrap().consecutive_errors, 1);
+    }
+}
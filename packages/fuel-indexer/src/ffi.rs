@@ -14,7 +14,7 @@ use wasmer_middlewares::metering::{
     get_remaining_points, set_remaining_points, MeteringPoints,
 };
 
-use crate::{IndexEnv, IndexerResult};
+use crate::{IndexEnv, IndexerResult, Provenance};
 
 /// Symbol name for the module entrypoint.
 pub const MODULE_ENTRYPOINT: &str = "handle_events";
@@ -111,6 +111,12 @@ fn log_data(
 /// Fetch the given type at the given pointer from memory.
 ///
 /// This function is fallible, and will panic if the type cannot be fetched.
+#[tracing::instrument(
+    name = "ff_get_object",
+    level = "debug",
+    skip_all,
+    fields(type_id, indexer = %env.data().uid, block_height = env.data().block_height())
+)]
 fn get_object(
     mut env: FunctionEnvMut<IndexEnv>,
     type_id: i64,
@@ -177,6 +183,12 @@ fn get_object(
 /// Put the given type at the given pointer into memory.
 ///
 /// This function is fallible, and will panic if the type cannot be saved.
+#[tracing::instrument(
+    name = "ff_put_object",
+    level = "debug",
+    skip_all,
+    fields(type_id, indexer = %env.data().uid, block_height = env.data().block_height())
+)]
 fn put_object(
     mut env: FunctionEnvMut<IndexEnv>,
     type_id: i64,
@@ -233,9 +245,155 @@ fn put_object(
     Ok(())
 }
 
+/// Fetch the objects of the given type whose column is equal to the value at the
+/// given pointer, write them into memory, and return a pointer to them.
+///
+/// The column name and value are read as a bincode-encoded `(String, FtColumn)`,
+/// and the objects are written as a bincode-encoded `Vec<Vec<u8>>`.
+#[tracing::instrument(
+    name = "ff_find_many",
+    level = "debug",
+    skip_all,
+    fields(type_id, indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn find_many(
+    mut env: FunctionEnvMut<IndexEnv>,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let (column, value): (String, FtColumn) = match bincode::deserialize(&bytes) {
+        Ok(query) => query,
+        Err(e) => {
+            error!("Failed to deserialize (String, FtColumn) for find_many: {e:?}");
+            return Err(WasmIndexerError::DeserializationError);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let objects = rt
+        .block_on(async {
+            idx_env
+                .db
+                .lock()
+                .await
+                .find_objects(type_id, &column, &value)
+                .await
+        })
+        .map_err(|e| {
+            error!("Failed to find_many: {e}");
+            WasmIndexerError::DatabaseError
+        })?;
+
+    let bytes = bincode::serialize(&objects).map_err(|e| {
+        error!("Failed to serialize objects for find_many: {e:?}");
+        WasmIndexerError::SerializationError
+    })?;
+
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
+}
+
+/// Delete the object of the given type with the ID at the given pointer. Returns
+/// `1` if the object was deleted, and `0` if there was no such object.
+#[tracing::instrument(
+    name = "ff_delete_object",
+    level = "debug",
+    skip_all,
+    fields(type_id, indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn delete_object(
+    mut env: FunctionEnvMut<IndexEnv>,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let id =
+        get_string(&mem, ptr, len).map_err(|_| WasmIndexerError::DeserializationError)?;
+
+    let rt = tokio::runtime::Handle::current();
+    let result =
+        rt.block_on(async { idx_env.db.lock().await.delete_object(type_id, id).await });
+
+    match result {
+        Ok(deleted) => Ok(deleted as u32),
+        Err(e) => {
+            error!("Failed to delete_object: {e}");
+            Err(database_operation_failure(e))
+        }
+    }
+}
+
 /// Execute the arbitrary query at the given pointer.
 ///
 /// This function is fallible, and will panic if the query cannot be executed.
+#[tracing::instrument(
+    name = "ff_put_many_to_many_record",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
 fn put_many_to_many_record(
     mut env: FunctionEnvMut<IndexEnv>,
     ptr: u32,
@@ -265,8 +423,8 @@ fn put_many_to_many_record(
         bytes.extend_from_slice(&mem.data_unchecked()[range]);
     }
 
-    let queries: Vec<String> = match bincode::deserialize::<Vec<RawQuery>>(&bytes) {
-        Ok(queries) => queries.iter().map(|q| q.to_string()).collect(),
+    let queries = match bincode::deserialize::<Vec<RawQuery>>(&bytes) {
+        Ok(queries) => queries,
         Err(e) => {
             error!("Failed to deserialize queries: {e:?}");
             return Err(WasmIndexerError::DeserializationError);
@@ -291,6 +449,401 @@ fn put_many_to_many_record(
     Ok(())
 }
 
+/// Set the block and transaction being processed, which are recorded in the
+/// provenance columns of objects saved from here on.
+///
+/// The transaction ID is read as a hex string at the given pointer, where a
+/// zero length indicates that there is no transaction.
+#[tracing::instrument(
+    name = "ff_set_provenance",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn set_provenance(
+    mut env: FunctionEnvMut<IndexEnv>,
+    block_height: u32,
+    block_time: i64,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let tx_id = match len {
+        0 => None,
+        _ => Some(
+            get_string(&mem, ptr, len)
+                .map_err(|_| WasmIndexerError::DeserializationError)?,
+        ),
+    };
+
+    idx_env
+        .block_height
+        .store(block_height, std::sync::atomic::Ordering::SeqCst);
+    tracing::Span::current().record("block_height", block_height);
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async {
+        idx_env.db.lock().await.set_provenance(Provenance {
+            block_height,
+            block_time,
+            tx_id,
+        })
+    });
+
+    Ok(())
+}
+
+/// Track the hex-encoded contract ID at the given pointer, so that its receipts
+/// are dispatched to the indexer's handlers.
+#[tracing::instrument(
+    name = "ff_register_contract",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn register_contract(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let contract_id =
+        get_string(&mem, ptr, len).map_err(|_| WasmIndexerError::DeserializationError)?;
+
+    let rt = tokio::runtime::Handle::current();
+    let result = rt.block_on(async {
+        idx_env
+            .db
+            .lock()
+            .await
+            .register_contract_id(&contract_id)
+            .await
+    });
+
+    if let Err(e) = result {
+        error!("Failed to register_contract: {e}");
+        return Err(database_operation_failure(e));
+    }
+
+    Ok(())
+}
+
+/// Write the hex-encoded contract IDs registered by the indexer's handlers into
+/// memory, and return a pointer to them.
+#[tracing::instrument(
+    name = "ff_get_contract_ids",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn get_contract_ids(
+    mut env: FunctionEnvMut<IndexEnv>,
+    len_ptr: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let rt = tokio::runtime::Handle::current();
+    let contract_ids = rt
+        .block_on(async { idx_env.db.lock().await.contract_ids().await })
+        .map_err(|e| {
+            error!("Failed to get_contract_ids: {e}");
+            WasmIndexerError::DatabaseError
+        })?;
+
+    let bytes = bincode::serialize(&contract_ids)
+        .map_err(|_| WasmIndexerError::SerializationError)?;
+
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
+}
+
+/// Fetch the value stored under the key at the given pointer of the indexer's
+/// state, write it into memory, and return a pointer to it. Returns a null
+/// pointer if no value is stored under the key.
+#[tracing::instrument(
+    name = "ff_get_state",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn get_state(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let key =
+        get_string(&mem, ptr, len).map_err(|_| WasmIndexerError::DeserializationError)?;
+
+    let rt = tokio::runtime::Handle::current();
+    let bytes = rt
+        .block_on(async { idx_env.db.lock().await.get_state(&key).await })
+        .map_err(|e| {
+            error!("Failed to get_state: {e}");
+            WasmIndexerError::DatabaseError
+        })?;
+
+    if let Some(bytes) = bytes {
+        let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+        let size = bytes.len() as u32;
+        let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+        let range = result as usize..result as usize + size as usize;
+
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized.")
+            .view(&store);
+        WasmPtr::<u32>::new(len_ptr)
+            .deref(&mem)
+            .write(size)
+            .expect("Failed to write length to memory.");
+
+        unsafe {
+            mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+        }
+
+        Ok(result)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Store the value at the given pointer under the key at the given pointer of
+/// the indexer's state.
+#[tracing::instrument(
+    name = "ff_put_state",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn put_state(
+    mut env: FunctionEnvMut<IndexEnv>,
+    key_ptr: u32,
+    key_len: u32,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let key = get_string(&mem, key_ptr, key_len)
+        .map_err(|_| WasmIndexerError::DeserializationError)?;
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let rt = tokio::runtime::Handle::current();
+    let result =
+        rt.block_on(async { idx_env.db.lock().await.put_state(&key, &bytes).await });
+
+    if let Err(e) = result {
+        error!("Failed to put_state: {e}");
+        return Err(database_operation_failure(e));
+    }
+
+    Ok(())
+}
+
+/// Remove the key at the given pointer from the indexer's state.
+#[tracing::instrument(
+    name = "ff_delete_state",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn delete_state(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = if let Some(memory) = idx_env.memory.as_mut() {
+        memory.view(&store)
+    } else {
+        return Err(WasmIndexerError::UninitializedMemory);
+    };
+
+    let key =
+        get_string(&mem, ptr, len).map_err(|_| WasmIndexerError::DeserializationError)?;
+
+    let rt = tokio::runtime::Handle::current();
+    let result = rt.block_on(async { idx_env.db.lock().await.delete_state(&key).await });
+
+    if let Err(e) = result {
+        error!("Failed to delete_state: {e}");
+        return Err(database_operation_failure(e));
+    }
+
+    Ok(())
+}
+
+/// Write the JSON-encoded `config` section of the indexer's manifest into memory,
+/// and return a pointer to it. Returns a null pointer if the manifest has no config.
+#[tracing::instrument(
+    name = "ff_get_config",
+    level = "debug",
+    skip_all,
+    fields(indexer = %env.data().uid, block_height = env.data().block_height())
+)]
+fn get_config(
+    mut env: FunctionEnvMut<IndexEnv>,
+    len_ptr: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let rt = tokio::runtime::Handle::current();
+    let config = rt.block_on(async {
+        idx_env
+            .db
+            .lock()
+            .await
+            .runtime_config()
+            .map(|config| config.as_bytes().to_vec())
+    });
+
+    if let Some(bytes) = config {
+        let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+        let size = bytes.len() as u32;
+        let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+        let range = result as usize..result as usize + size as usize;
+
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized.")
+            .view(&store);
+        WasmPtr::<u32>::new(len_ptr)
+            .deref(&mem)
+            .write(size)
+            .expect("Failed to write length to memory.");
+
+        unsafe {
+            mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+        }
+
+        Ok(result)
+    } else {
+        Ok(0)
+    }
+}
+
 // Returns a specialized error code when the database trigger, which ensures
 // indexers can't miss blocks, raises an exception. Otherwise, returns an error
 // code indicating a generic database operation failure.
@@ -308,6 +861,9 @@ fn database_operation_failure(e: crate::IndexerError) -> WasmIndexerError {
             }
             WasmIndexerError::DatabaseError
         }
+        crate::IndexerError::ConstraintViolation(..) => {
+            WasmIndexerError::ConstraintViolation
+        }
         _ => WasmIndexerError::DatabaseError,
     }
 }
@@ -324,19 +880,37 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
 
     let f_get_obj = Function::new_typed_with_env(store, env, get_object);
     let f_put_obj = Function::new_typed_with_env(store, env, put_object);
+    let f_find_many = Function::new_typed_with_env(store, env, find_many);
+    let f_delete_obj = Function::new_typed_with_env(store, env, delete_object);
     let f_log_data = Function::new_typed_with_env(store, env, log_data);
     let f_put_many_to_many_record =
         Function::new_typed_with_env(store, env, put_many_to_many_record);
+    let f_set_provenance = Function::new_typed_with_env(store, env, set_provenance);
+    let f_register_contract = Function::new_typed_with_env(store, env, register_contract);
+    let f_get_contract_ids = Function::new_typed_with_env(store, env, get_contract_ids);
+    let f_get_state = Function::new_typed_with_env(store, env, get_state);
+    let f_put_state = Function::new_typed_with_env(store, env, put_state);
+    let f_delete_state = Function::new_typed_with_env(store, env, delete_state);
+    let f_get_config = Function::new_typed_with_env(store, env, get_config);
     let f_early_exit = Function::new_typed(store, early_exit);
 
     exports.insert("ff_early_exit".to_string(), f_early_exit);
     exports.insert("ff_get_object".to_string(), f_get_obj);
     exports.insert("ff_put_object".to_string(), f_put_obj);
+    exports.insert("ff_find_many".to_string(), f_find_many);
+    exports.insert("ff_delete_object".to_string(), f_delete_obj);
     exports.insert(
         "ff_put_many_to_many_record".to_string(),
         f_put_many_to_many_record,
     );
     exports.insert("ff_log_data".to_string(), f_log_data);
+    exports.insert("ff_set_provenance".to_string(), f_set_provenance);
+    exports.insert("ff_register_contract".to_string(), f_register_contract);
+    exports.insert("ff_get_contract_ids".to_string(), f_get_contract_ids);
+    exports.insert("ff_get_state".to_string(), f_get_state);
+    exports.insert("ff_put_state".to_string(), f_put_state);
+    exports.insert("ff_delete_state".to_string(), f_delete_state);
+    exports.insert("ff_get_config".to_string(), f_get_config);
 
     exports
 }
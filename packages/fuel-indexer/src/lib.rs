@@ -1,14 +1,22 @@
 #![deny(unused_crate_dependencies)]
+mod cache;
 pub mod cli;
 pub(crate) mod commands;
 mod database;
+pub mod dry_run;
 pub mod executor;
+pub mod failover;
 pub mod ffi;
 pub(crate) mod queries;
 mod service;
+pub mod telemetry;
+mod tunables;
 
-pub use database::Database;
-pub use executor::{Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor};
+pub use database::{Database, Provenance};
+pub use executor::{
+    native_handler, Executor, IndexEnv, NativeHandler, NativeIndexExecutor,
+    WasmIndexExecutor,
+};
 pub use fuel_indexer_database::IndexerDatabaseError;
 pub use fuel_indexer_lib::{
     config::IndexerConfig,
@@ -26,7 +34,7 @@ use openssl as _;
 pub mod prelude {
     pub use super::{
         Database, Executor, FtColumn, IndexEnv, IndexerConfig, IndexerError,
-        IndexerResult, IndexerService, Manifest, Module, NativeIndexExecutor,
+        IndexerResult, IndexerService, Manifest, Module, NativeIndexExecutor, Provenance,
         WasmIndexExecutor,
     };
     pub use async_std::sync::{Arc, Mutex};
@@ -50,6 +58,10 @@ pub enum IndexerError {
     RuntimeError(#[from] RuntimeError),
     #[error("Run time limit exceeded error")]
     RunTimeLimitExceededError,
+    #[error("Memory limit exceeded error")]
+    MemoryLimitExceededError,
+    #[error("Handler timeout exceeded error")]
+    HandlerTimeoutError,
     #[error("IO Error: {0:#?}")]
     IoError(#[from] std::io::Error),
     #[error("FFI Error {0:?}")]
@@ -80,12 +92,58 @@ pub enum IndexerError {
     WasmExecutionInstantiationError,
     #[error("Error creating native executor.")]
     NativeExecutionInstantiationError,
-    #[error("Native execution runtime error.")]
-    NativeExecutionRuntimeError,
+    #[error("Native execution runtime error: {0}")]
+    NativeExecutionRuntimeError(String),
     #[error("Tokio time error: {0:?}")]
     Elapsed(#[from] tokio::time::error::Elapsed),
     #[error("Indexer end block has been stopping execution.")]
     EndBlockMet,
     #[error("Invalid schema: {0:?}")]
     SchemaVersionMismatch(String),
+    #[error("No Fuel node is available that is not behind the indexer.")]
+    NoAvailableFuelNodeError,
+    #[error("Entity in {0} violates constraint {1:?}: {2}")]
+    ConstraintViolation(String, String, String),
+    #[error("Indexer config does not match the expected type: {0}")]
+    InvalidConfig(String),
+}
+
+impl IndexerError {
+    /// Short, stable name of the error variant, used to label error metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CompileError(_) => "compile_error",
+            Self::SqlxError(_) => "sqlx_error",
+            Self::InstantiationError(_) => "instantiation_error",
+            Self::ExportError(_) => "export_error",
+            Self::RuntimeError(_) => "runtime_error",
+            Self::RunTimeLimitExceededError => "run_time_limit_exceeded",
+            Self::MemoryLimitExceededError => "memory_limit_exceeded",
+            Self::HandlerTimeoutError => "handler_timeout",
+            Self::IoError(_) => "io_error",
+            Self::FFIError(_) => "ffi_error",
+            Self::MissingHandler => "missing_handler",
+            Self::DatabaseError(_) => "database_error",
+            Self::InvalidAddress(_) => "invalid_address",
+            Self::JoinError(_) => "join_error",
+            Self::ExecutorInitError => "executor_init_error",
+            Self::HandlerError => "handler_error",
+            Self::InvalidPortNumber(_) => "invalid_port_number",
+            Self::NoTransactionError(_) => "no_transaction_error",
+            Self::Unknown(_) => "unknown",
+            Self::SchemaError(_) => "schema_error",
+            Self::ManifestError(_) => "manifest_error",
+            Self::WasmExecutionInstantiationError => "wasm_execution_instantiation_error",
+            Self::NativeExecutionInstantiationError => {
+                "native_execution_instantiation_error"
+            }
+            Self::NativeExecutionRuntimeError(_) => "native_execution_runtime_error",
+            Self::Elapsed(_) => "elapsed",
+            Self::EndBlockMet => "end_block_met",
+            Self::SchemaVersionMismatch(_) => "schema_version_mismatch",
+            Self::NoAvailableFuelNodeError => "no_available_fuel_node_error",
+            Self::ConstraintViolation(..) => "constraint_violation",
+            Self::InvalidConfig(_) => "invalid_config",
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    executor::{NativeIndexExecutor, WasmIndexExecutor},
+    executor::{native_handler, NativeHandler, NativeIndexExecutor, WasmIndexExecutor},
     Database, Executor, IndexerConfig, IndexerError, IndexerResult, Manifest,
 };
 use async_std::sync::{Arc, Mutex};
@@ -7,20 +7,21 @@ use async_std::{fs::File, io::ReadExt};
 use fuel_indexer_database::{
     queries, types::IndexerAssetType, IndexerConnection, IndexerConnectionPool,
 };
-use fuel_indexer_lib::utils::ServiceRequest;
+use fuel_indexer_lib::{defaults, utils::ServiceRequest};
 use fuel_indexer_schema::db::manager::SchemaManager;
 use fuel_indexer_types::fuel::BlockData;
 use futures::Future;
 use std::collections::HashMap;
 use std::marker::Send;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::sync::mpsc::Receiver;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc::Receiver, watch};
 use tracing::{error, info, warn};
 
 /// Primary service used to run one or many indexers.
 pub struct IndexerService {
-    /// Indexer service configuration.
-    config: IndexerConfig,
+    /// Indexer service configuration, which changes whenever the configuration is reloaded.
+    config: watch::Receiver<IndexerConfig>,
 
     /// Connection pool used to connect to the database.
     pool: IndexerConnectionPool,
@@ -36,6 +37,10 @@ pub struct IndexerService {
 
     /// Killers used to stop the spawned indexers.
     killers: HashMap<String, Arc<AtomicBool>>,
+
+    /// Handlers of the native indexers registered to this service, which are kept so
+    /// that native indexers can be reloaded.
+    native_handlers: HashMap<String, NativeHandler>,
 }
 
 impl IndexerService {
@@ -46,17 +51,28 @@ impl IndexerService {
         rx: Receiver<ServiceRequest>,
     ) -> IndexerResult<IndexerService> {
         let manager = SchemaManager::new(pool.clone());
+        let (_, config) = watch::channel(config);
 
         Ok(IndexerService {
             config,
             pool,
             manager,
             killers: HashMap::default(),
+            native_handlers: HashMap::default(),
             tasks: tokio::task::JoinSet::new(),
             rx,
         })
     }
 
+    /// Apply configuration reloads received on `config` to this service, and to the
+    /// indexers it runs.
+    pub fn subscribe_to_config_reloads(
+        &mut self,
+        config: watch::Receiver<IndexerConfig>,
+    ) {
+        self.config = config;
+    }
+
     /// Register new indexers to the `IndexerService`, from a `Manifest`.
     pub async fn register_indexer_from_manifest(
         &mut self,
@@ -77,7 +93,7 @@ impl IndexerService {
         .await)
             .is_ok();
         if indexer_exists {
-            if !self.config.replace_indexer {
+            if !self.config.borrow().replace_indexer {
                 return Err(IndexerError::Unknown(format!(
                     "Indexer({}.{}) already exists.",
                     manifest.namespace(),
@@ -138,8 +154,9 @@ impl IndexerService {
             }
         };
 
+        let config = self.config.borrow().clone();
         let executor = WasmIndexExecutor::create(
-            &self.config,
+            &config,
             &manifest,
             self.pool.clone(),
             schema_version,
@@ -187,14 +204,30 @@ impl IndexerService {
         let mut conn = self.pool.acquire().await?;
         let indices = queries::all_registered_indexers(&mut conn).await?;
         for index in indices {
+            let manifest_asset =
+                queries::indexer_asset(&mut conn, &index.id, IndexerAssetType::Manifest)
+                    .await?;
+            let manifest = Manifest::try_from(&manifest_asset.bytes)?;
+
+            // Native indexers are compiled into their own binary, and are registered
+            // by that binary when it starts.
+            if let crate::Module::Native = manifest.module() {
+                info!(
+                    "Skipping NativeIndex({}), which is registered by its own binary.",
+                    manifest.uid()
+                );
+                continue;
+            }
+
             let assets = queries::indexer_assets(&mut conn, &index.id).await?;
             let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
 
             let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
             manifest.set_start_block(start_block);
 
+            let config = self.config.borrow().clone();
             if let Ok(executor) = WasmIndexExecutor::create(
-                &self.config,
+                &config,
                 &manifest,
                 self.pool.clone(),
                 assets.schema.digest,
@@ -233,30 +266,86 @@ impl IndexerService {
         )
         .await?;
 
+        let schema = manifest.graphql_schema_content()?;
+        let schema_bytes = Vec::<u8>::from(&schema);
+
         self.manager
             .new_schema(
                 manifest.namespace(),
                 manifest.identifier(),
-                manifest.graphql_schema_content()?,
+                schema,
                 manifest.execution_source(),
                 &mut conn,
             )
             .await?;
 
+        // Native indexers have no module to store, but their manifest and schema are
+        // registered so that they're listed in the registry, and can be reloaded.
+        for (asset_type, bytes) in [
+            (IndexerAssetType::Manifest, manifest.clone().into()),
+            (IndexerAssetType::Schema, schema_bytes),
+        ] {
+            queries::register_indexer_asset(
+                &mut conn,
+                manifest.namespace(),
+                manifest.identifier(),
+                bytes,
+                asset_type,
+                None,
+            )
+            .await?;
+        }
+
         let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
         manifest.set_start_block(start_block);
 
         let uid = manifest.uid();
-        let executor = NativeIndexExecutor::<T>::create(
-            &self.config,
+        let handler = native_handler(handle_events);
+        let config = self.config.borrow().clone();
+        let executor = NativeIndexExecutor::new(
             &manifest,
             self.pool.clone(),
-            handle_events,
+            &config,
+            handler.clone(),
         )
         .await?;
 
         info!("Registered NativeIndex({})", uid);
 
+        self.native_handlers.insert(uid, handler);
+        self.start_executor(executor);
+
+        Ok(())
+    }
+
+    /// Reload a native indexer from the manifest stored in the registry, using the
+    /// handler with which it was registered.
+    async fn reload_native_indexer(
+        &mut self,
+        conn: &mut IndexerConnection,
+        indexer_id: i64,
+        handler: NativeHandler,
+    ) -> IndexerResult<()> {
+        let manifest_asset =
+            queries::indexer_asset(conn, &indexer_id, IndexerAssetType::Manifest).await?;
+        let mut manifest = Manifest::try_from(&manifest_asset.bytes)?;
+
+        let start_block = get_start_block(conn, &manifest).await?;
+        manifest.set_start_block(start_block);
+
+        let uid = manifest.uid();
+        if let Some(killer_for_prev_executor) = self.killers.remove(&uid) {
+            info!("NativeIndex({uid}) is being replaced. Stopping previous version of NativeIndex({uid}).");
+            killer_for_prev_executor.store(true, Ordering::SeqCst);
+        }
+
+        let config = self.config.borrow().clone();
+        let executor =
+            NativeIndexExecutor::new(&manifest, self.pool.clone(), &config, handler)
+                .await?;
+
+        info!("Reloaded NativeIndex({uid})");
+
         self.start_executor(executor);
 
         Ok(())
@@ -264,12 +353,21 @@ impl IndexerService {
 
     /// Kick it off! Run the indexer service loop, listening to service messages primarily coming from the web server.
     pub async fn run(mut self) -> IndexerResult<()> {
+        let mut retirement_interval = tokio::time::interval(Duration::from_secs(
+            defaults::RETIRED_DEPLOYMENT_POLL_INTERVAL,
+        ));
+
         loop {
             tokio::select! {
                 // Calling join_next will remove finished tasks from the set.
                 Some(Err(e)) = self.tasks.join_next() => {
                     error!("Error retiring indexer task {e}");
                 }
+                _ = retirement_interval.tick() => {
+                    if let Err(e) = self.retire_deployments().await {
+                        error!("Failed to retire indexer deployments: {e:?}");
+                    }
+                }
                 Some(service_request) = self.rx.recv() => {
                     match service_request {
                         ServiceRequest::Reload(request) => {
@@ -283,6 +381,14 @@ impl IndexerService {
                             .await
                             {
                                 Ok(id) => {
+                                    let uid = format!("{}.{}", request.namespace, request.identifier);
+                                    if let Some(handler) = self.native_handlers.get(&uid).cloned() {
+                                        if let Err(e) = self.reload_native_indexer(&mut conn, id, handler).await {
+                                            error!("Failed to reload NativeIndex({uid}): {e:?}");
+                                        }
+                                        continue;
+                                    }
+
                                     let assets =
                                         queries::indexer_assets(&mut conn, &id)
                                             .await?;
@@ -302,8 +408,9 @@ impl IndexerService {
                                             .store(true, Ordering::SeqCst);
                                     }
 
+                                    let config = self.config.borrow().clone();
                                     match WasmIndexExecutor::create(
-                                        &self.config,
+                                        &config,
                                         &manifest,
                                         self.pool.clone(),
                                         assets.schema.digest,
@@ -348,6 +455,55 @@ impl IndexerService {
         }
     }
 
+    // Stop the executors of retired blue/green deployments, and remove their
+    // data once the configured grace period has elapsed.
+    async fn retire_deployments(&mut self) -> IndexerResult<()> {
+        let mut conn = self.pool.acquire().await?;
+        let grace_period = self.config.borrow().deployment_grace_period as i64;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+
+        for deployment in queries::retired_deployments(&mut conn).await? {
+            let uid = deployment.uid();
+            if let Some(killer) = self.killers.remove(&uid) {
+                info!("Indexer({uid}) has been retired. Stopping Indexer({uid}).");
+                killer.store(true, Ordering::SeqCst);
+            }
+
+            let retired_at = match deployment.retired_at {
+                Some(t) => t.timestamp(),
+                None => continue,
+            };
+
+            if now - retired_at < grace_period {
+                continue;
+            }
+
+            queries::start_transaction(&mut conn).await?;
+            match queries::remove_indexer(
+                &mut conn,
+                &deployment.namespace,
+                &deployment.deployment,
+                true,
+            )
+            .await
+            {
+                Ok(_) => {
+                    queries::commit_transaction(&mut conn).await?;
+                    info!("Removed retired Indexer({uid}).");
+                }
+                Err(e) => {
+                    queries::revert_transaction(&mut conn).await?;
+                    error!("Failed to remove retired Indexer({uid}): {e:?}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     // Spawn and register a tokio::task running the Executor loop, as well as
     // the kill switch and the abort handle.
     fn start_executor<T: 'static + Executor + Send + Sync>(&mut self, executor: T) {
@@ -357,7 +513,7 @@ impl IndexerService {
             .insert(uid.clone(), executor.kill_switch().clone());
 
         self.tasks
-            .spawn(crate::executor::run_executor(&self.config, executor));
+            .spawn(crate::executor::run_executor(self.config.clone(), executor));
     }
 }
 
@@ -0,0 +1,127 @@
+//! Export of the indexer service's tracing spans to an OTLP collector.
+
+use fuel_indexer_lib::config::TelemetryConfig;
+use opentelemetry::{
+    global,
+    sdk::{
+        trace::{self, Sampler, Tracer},
+        Resource,
+    },
+    trace::TraceError,
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing::Level;
+use tracing_subscriber::{filter::Targets, Layer, Registry};
+
+/// Only spans created by the indexer service itself are exported.
+const EXPORTED_TARGET: &str = "fuel_indexer";
+
+/// Path of the OTLP/HTTP traces endpoint, relative to the collector's base URL.
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// Create a tracer that exports spans in batches to the configured OTLP/HTTP endpoint.
+///
+/// `otlp_endpoint` is the collector's base URL, so spans are sent to `<otlp_endpoint>/v1/traces`.
+///
+/// This also installs the tracer's provider as the global tracer provider, so
+/// that `shutdown` can flush any pending spans on exit.
+pub fn init_tracer(config: &TelemetryConfig) -> Result<Tracer, TraceError> {
+    let exporter = opentelemetry_otlp::new_exporter()
+        .http()
+        .with_endpoint(format!(
+            "{}{OTLP_TRACES_PATH}",
+            config.otlp_endpoint.trim_end_matches('/')
+        ));
+
+    let trace_config = trace::config()
+        .with_sampler(Sampler::TraceIdRatioBased(config.sample_ratio))
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            config.service_name.clone(),
+        )]));
+
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(exporter)
+        .with_trace_config(trace_config)
+        .install_batch(opentelemetry::runtime::Tokio)
+}
+
+/// Build a subscriber layer that exports the indexer service's spans.
+pub fn otlp_layer(
+    config: &TelemetryConfig,
+) -> Result<impl Layer<Registry> + Send + Sync, TraceError> {
+    let tracer = init_tracer(config)?;
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(Targets::new().with_target(EXPORTED_TARGET, Level::DEBUG)))
+}
+
+/// Flush any pending spans and shut down the global tracer provider.
+///
+/// This blocks until the export is complete, so it should not be called from
+/// within an async context.
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::oneshot,
+        time::{timeout, Duration},
+    };
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // Accepts a single request on the listener, and returns its request line.
+    async fn collector(listener: TcpListener, tx: oneshot::Sender<String>) {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 64 * 1024];
+        let n = socket.read(&mut buf).await.unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        let request_line = request.lines().next().unwrap_or_default().to_string();
+        tx.send(request_line).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_spans_are_exported_to_otlp_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(collector(listener, tx));
+
+        let config = TelemetryConfig {
+            enabled: true,
+            otlp_endpoint: format!("http://{addr}"),
+            ..TelemetryConfig::default()
+        };
+
+        let subscriber = Registry::default().with(otlp_layer(&config).unwrap());
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::debug_span!(
+                "handle_events",
+                indexer = "fuel_indexer_test.index1",
+                block_height.start = 1,
+                block_height.end = 10,
+            )
+            .entered();
+        });
+
+        tokio::task::spawn_blocking(shutdown).await.unwrap();
+
+        let request_line = timeout(Duration::from_secs(10), rx)
+            .await
+            .expect("Collector did not receive any spans.")
+            .unwrap();
+
+        assert_eq!(request_line, "POST /v1/traces HTTP/1.1");
+    }
+}
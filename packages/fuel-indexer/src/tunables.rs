@@ -0,0 +1,225 @@
+/// Limits on the linear memory available to an indexer's WASM module.
+use std::{
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use wasmer::{
+    vm::{
+        LinearMemory, MemoryError, MemoryStyle, TableStyle, VMMemory, VMMemoryDefinition,
+        VMTable, VMTableDefinition,
+    },
+    BaseTunables, MemoryType, Pages, TableType, Target, Tunables,
+};
+
+/// Tunables that cap the linear memory of a WASM module at a maximum number of pages.
+///
+/// Any attempt by the module to use more memory than the limit fails, and is recorded
+/// so that the executor can tell a memory limit violation apart from other traps.
+pub struct LimitingTunables {
+    /// Tunables to which everything but the memory limit is delegated.
+    base: BaseTunables,
+
+    /// Maximum number of pages of linear memory.
+    limit: Pages,
+
+    /// Set when the module attempts to use more memory than the limit.
+    limit_exceeded: Arc<AtomicBool>,
+}
+
+impl LimitingTunables {
+    /// Create a new `LimitingTunables`.
+    pub fn new(limit: Pages, limit_exceeded: Arc<AtomicBool>) -> Self {
+        Self {
+            base: BaseTunables::for_target(&Target::default()),
+            limit,
+            limit_exceeded,
+        }
+    }
+
+    /// Cap the maximum of the requested memory at the limit.
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = *requested;
+        adjusted.maximum = Some(
+            requested
+                .maximum
+                .map_or(self.limit, |maximum| maximum.min(self.limit)),
+        );
+        adjusted
+    }
+
+    /// Ensure that the initial size of the memory is within the limit.
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            self.limit_exceeded.store(true, Ordering::SeqCst);
+            return Err(MemoryError::MinimumMemoryTooLarge {
+                min_requested: ty.minimum,
+                max_allowed: self.limit,
+            });
+        }
+        Ok(())
+    }
+
+    /// Wrap a memory so that failures to grow past the limit are recorded.
+    fn limit(&self, memory: VMMemory) -> VMMemory {
+        VMMemory(Box::new(LimitedMemory {
+            inner: memory,
+            limit_exceeded: self.limit_exceeded.clone(),
+        }))
+    }
+}
+
+impl Tunables for LimitingTunables {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(&self.adjust_memory(memory))
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        let memory = self.base.create_host_memory(&adjusted, style)?;
+        Ok(self.limit(memory))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        let memory =
+            self.base
+                .create_vm_memory(&adjusted, style, vm_definition_location)?;
+        Ok(self.limit(memory))
+    }
+
+    fn create_host_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+    ) -> Result<VMTable, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<VMTable, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+/// Linear memory that records failures to grow beyond its maximum size.
+#[derive(Debug)]
+struct LimitedMemory {
+    /// The underlying memory.
+    inner: VMMemory,
+
+    /// Set when the memory fails to grow beyond its maximum size.
+    limit_exceeded: Arc<AtomicBool>,
+}
+
+impl LinearMemory for LimitedMemory {
+    fn ty(&self) -> MemoryType {
+        self.inner.ty()
+    }
+
+    fn size(&self) -> Pages {
+        self.inner.size()
+    }
+
+    fn style(&self) -> MemoryStyle {
+        self.inner.style()
+    }
+
+    fn grow(&mut self, delta: Pages) -> Result<Pages, MemoryError> {
+        self.inner.grow(delta).map_err(|e| {
+            if let MemoryError::CouldNotGrow { .. } = e {
+                self.limit_exceeded.store(true, Ordering::SeqCst);
+            }
+            e
+        })
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        Ok(Box::new(LimitedMemory {
+            inner: VMMemory(self.inner.try_clone()?),
+            limit_exceeded: self.limit_exceeded.clone(),
+        }))
+    }
+
+    fn copy(&mut self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        Ok(Box::new(LimitedMemory {
+            inner: VMMemory(self.inner.copy()?),
+            limit_exceeded: self.limit_exceeded.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasmer::{imports, Cranelift, Engine, Instance, Module, NativeEngineExt, Store};
+
+    // Grows the exported memory by the given number of pages, returning the previous
+    // size in pages, or -1 if the memory could not be grown.
+    const GROW_MEMORY_WAT: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "grow") (param i32) (result i32)
+                local.get 0
+                memory.grow))
+    "#;
+
+    fn store_with_limit(limit: u32, limit_exceeded: Arc<AtomicBool>) -> Store {
+        let mut engine: Engine = Cranelift::default().into();
+        engine.set_tunables(LimitingTunables::new(Pages(limit), limit_exceeded));
+        Store::new(engine)
+    }
+
+    #[test]
+    fn test_memory_cannot_grow_beyond_limit() {
+        let limit_exceeded = Arc::new(AtomicBool::new(false));
+        let mut store = store_with_limit(4, limit_exceeded.clone());
+        let module = Module::new(&store, GROW_MEMORY_WAT).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let grow = instance
+            .exports
+            .get_typed_function::<i32, i32>(&store, "grow")
+            .unwrap();
+
+        assert_eq!(grow.call(&mut store, 3).unwrap(), 1);
+        assert!(!limit_exceeded.load(Ordering::SeqCst));
+
+        assert_eq!(grow.call(&mut store, 1).unwrap(), -1);
+        assert!(limit_exceeded.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_module_requiring_more_memory_than_limit_cannot_be_instantiated() {
+        let limit_exceeded = Arc::new(AtomicBool::new(false));
+        let mut store = store_with_limit(0, limit_exceeded.clone());
+        let module = Module::new(&store, GROW_MEMORY_WAT).unwrap();
+
+        assert!(Instance::new(&mut store, &module, &imports! {}).is_err());
+        assert!(limit_exceeded.load(Ordering::SeqCst));
+    }
+}
@@ -1,9 +1,11 @@
 #[allow(unused)]
 pub(crate) use crate::commands::{
     auth::Command as AuthCommand, build::Command as BuildCommand,
-    check::Command as CheckCommand, deploy::Command as DeployCommand,
+    check::Command as CheckCommand, codegen::Command as CodegenCommand,
+    deploy::Command as DeployCommand, dry_run::Command as DryRunCommand,
     kill::Command as KillCommand, new::Command as NewCommand,
-    remove::Command as RemoveCommand, start::Command as StartCommand,
+    remove::Command as RemoveCommand, schema::Command as SchemaCommand,
+    snapshot::Command as SnapshotCommand, start::Command as StartCommand,
     status::Command as StatusCommand,
 };
 use clap::{Parser, Subcommand};
@@ -33,6 +35,10 @@ pub enum ForcIndex {
     Postgres(ForcPostgresOpt),
     Kill(KillCommand),
     Status(StatusCommand),
+    DryRun(Box<DryRunCommand>),
+    Snapshot(SnapshotCommand),
+    Codegen(CodegenCommand),
+    Schema(SchemaCommand),
 }
 
 pub async fn run_cli() -> Result<(), anyhow::Error> {
@@ -58,5 +64,9 @@ pub async fn run_cli() -> Result<(), anyhow::Error> {
         },
         ForcIndex::Kill(command) => crate::commands::kill::exec(command),
         ForcIndex::Status(command) => crate::commands::status::exec(command).await,
+        ForcIndex::DryRun(command) => crate::commands::dry_run::exec(command).await,
+        ForcIndex::Snapshot(command) => crate::commands::snapshot::exec(command).await,
+        ForcIndex::Codegen(command) => crate::commands::codegen::exec(command).await,
+        ForcIndex::Schema(command) => crate::commands::schema::exec(command).await,
     }
 }
@@ -0,0 +1,39 @@
+use crate::ops::forc_index_codegen;
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Generate a typed Rust client crate from an indexer's GraphQL schema.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// URL of an indexer service at which the indexer is deployed.
+    #[clap(
+        long,
+        help = "URL of an indexer service at which the indexer is deployed. If set, the schema is read from the service instead of the manifest."
+    )]
+    pub url: Option<String>,
+
+    /// Path of the directory in which the client crate is generated.
+    #[clap(
+        short,
+        long,
+        help = "Path of the directory in which the client crate is generated. Defaults to '<namespace>-<identifier>-client'."
+    )]
+    pub output: Option<PathBuf>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_codegen::init(command).await
+}
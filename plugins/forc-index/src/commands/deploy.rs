@@ -60,6 +60,14 @@ pub struct Command {
         help = "Remove all indexed data when replacing an existing indexer."
     )]
     pub remove_data: bool,
+
+    /// Deploy alongside the existing indexer, and switch queries over once it is in sync.
+    #[clap(
+        long,
+        conflicts_with_all = &["replace_indexer", "remove_data"],
+        help = "Deploy alongside the existing indexer, and switch queries over once it is in sync."
+    )]
+    pub blue_green: bool,
 }
 
 impl Default for Command {
@@ -76,6 +84,7 @@ impl Default for Command {
             skip_build: false,
             replace_indexer: false,
             remove_data: false,
+            blue_green: false,
         }
     }
 }
@@ -0,0 +1,9 @@
+use crate::ops::forc_index_dry_run;
+use anyhow::Result;
+use fuel_indexer_lib::config::DryRunArgs;
+
+pub type Command = DryRunArgs;
+
+pub async fn exec(command: Box<Command>) -> Result<()> {
+    forc_index_dry_run::init(*command)
+}
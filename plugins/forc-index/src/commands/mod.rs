@@ -1,9 +1,13 @@
 pub mod auth;
 pub mod build;
 pub mod check;
+pub mod codegen;
 pub mod deploy;
+pub mod dry_run;
 pub mod kill;
 pub mod new;
 pub mod remove;
+pub mod schema;
+pub mod snapshot;
 pub mod start;
 pub mod status;
@@ -0,0 +1,43 @@
+use crate::{defaults, ops::forc_index_schema};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Download the GraphQL schema of a deployed indexer.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Path of the file to which the schema is written.
+    #[clap(
+        short,
+        long,
+        help = "Path of the file to which the schema is written. If not set, the schema is printed."
+    )]
+    pub output: Option<PathBuf>,
+
+    /// Download the result of an introspection query instead of the SDL.
+    #[clap(
+        long,
+        help = "Download the result of an introspection query instead of the SDL."
+    )]
+    pub json: bool,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_schema::init(command).await
+}
@@ -0,0 +1,80 @@
+use crate::{defaults, ops::forc_index_snapshot};
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Export or import a snapshot of an indexer's data.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(subcommand)]
+    pub command: SnapshotCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCommand {
+    Export(ExportCommand),
+    Import(ImportCommand),
+}
+
+/// Export a snapshot of an indexer's data, at the last block height it has indexed.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project being exported.
+    #[clap(
+        short,
+        long,
+        help = "Path to the manifest of the indexer project being exported."
+    )]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Path of the file to which the snapshot is written.
+    #[clap(
+        short,
+        long,
+        help = "Path of the file to which the snapshot is written. Defaults to '<namespace>.<identifier>.snapshot.json'."
+    )]
+    pub output: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+/// Import a snapshot of an indexer's data into an indexer service.
+#[derive(Debug, Parser)]
+pub struct ImportCommand {
+    /// Path of the snapshot to import.
+    #[clap(help = "Path of the snapshot to import.")]
+    pub snapshot: PathBuf,
+
+    /// URL at which to import the snapshot.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which to import the snapshot.")]
+    pub url: String,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    match command.command {
+        SnapshotCommand::Export(command) => forc_index_snapshot::export(command).await,
+        SnapshotCommand::Import(command) => forc_index_snapshot::import(command).await,
+    }
+}
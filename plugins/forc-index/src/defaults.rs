@@ -190,18 +190,20 @@ pub fn default_indexer_binary(
 #[indexer(manifest = "{manifest_path}")]
 pub mod {indexer_name}_index_mod {{
 
-    async fn {indexer_name}_handler(block_data: BlockData) {{
+    async fn {indexer_name}_handler(block_data: BlockData) -> IndexerResult<()> {{
         if block_data.header.height % 1000 == 0 {{
             info!("Processing Block#{{}}. (>'.')>", block_data.header.height);
         }}
         
         let block = Block::new(block_data.header.height.into(), block_data.id);
-        block.save().await;
+        block.save().await?;
 
         for transaction in block_data.transactions.iter() {{
             let tx = Transaction::new(block_data.id, Bytes32::from(<[u8; 32]>::from(transaction.id)));
-            tx.save().await;
+            tx.save().await?;
         }}
+
+        Ok(())
     }}
 }}
 "#
@@ -237,3 +239,21 @@ target = "wasm32-unknown-unknown"
 pub fn manifest_name(indexer_name: &str) -> String {
     format!("{indexer_name}.manifest.yaml")
 }
+
+/// Generate default cargo manifest for a client crate generated by `forc index codegen`.
+pub fn default_client_cargo_toml(crate_name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{crate_name}"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+reqwest = {{ version = "0.11", default-features = false, features = ["json", "rustls-tls"] }}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+thiserror = "1.0"
+"#
+    )
+}
@@ -0,0 +1,905 @@
+use crate::{
+    commands::codegen::Command,
+    defaults,
+    defaults::CARGO_MANIFEST_FILE_NAME,
+    utils::{cargo_workspace_root_dir, project_dir_info},
+};
+use fuel_indexer_database_types::DbType;
+use fuel_indexer_graphql::dynamic::{build_dynamic_schema, introspect};
+use fuel_indexer_lib::{graphql::GraphQLSchema, manifest::Manifest};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+use tracing::info;
+
+/// Rust keywords that can't be used as raw identifiers.
+const RESERVED_IDENTIFIERS: [&str; 4] = ["self", "Self", "super", "crate"];
+
+/// Rust keywords that have to be written as raw identifiers.
+const RUST_KEYWORDS: [&str; 47] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+    "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Items of the generated client that don't depend on the indexer's schema.
+const CLIENT_RUNTIME: &str = r#"/// Error returned by the [`Client`].
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Indexer service returned {0}: {1}")]
+    Service(reqwest::StatusCode, serde_json::Value),
+    #[error("Could not deserialize response: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+/// Result returned by the [`Client`].
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// A value that can be written as a GraphQL input value.
+pub trait GraphqlInput {
+    /// Write this value to `out` as a GraphQL input value.
+    fn write_graphql(&self, out: &mut String);
+}
+
+macro_rules! impl_graphql_input_for_literals {
+    ($($ty:ty),*) => {
+        $(
+            impl GraphqlInput for $ty {
+                fn write_graphql(&self, out: &mut String) {
+                    out.push_str(&self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+impl_graphql_input_for_literals!(i32, i64, i128, u32, u64, u128, f64, bool);
+
+impl GraphqlInput for String {
+    fn write_graphql(&self, out: &mut String) {
+        // A JSON string literal is also a valid GraphQL string literal.
+        out.push_str(&serde_json::Value::String(self.clone()).to_string());
+    }
+}
+
+impl<T: GraphqlInput> GraphqlInput for Box<T> {
+    fn write_graphql(&self, out: &mut String) {
+        (**self).write_graphql(out);
+    }
+}
+
+impl<T: GraphqlInput> GraphqlInput for Vec<T> {
+    fn write_graphql(&self, out: &mut String) {
+        out.push('[');
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            value.write_graphql(out);
+        }
+        out.push(']');
+    }
+}
+
+fn to_graphql(value: &impl GraphqlInput) -> String {
+    let mut out = String::new();
+    value.write_graphql(&mut out);
+    out
+}
+
+/// An entity that can be queried from the indexer.
+pub trait Entity: serde::de::DeserializeOwned {
+    /// Name of the query field of the entity.
+    const QUERY_FIELD: &'static str;
+
+    /// Selection set containing all of the scalar fields of the entity.
+    const SCALAR_FIELDS: &'static str;
+}
+
+/// An entity that can be filtered with the `filter` argument.
+pub trait Filterable: Entity {
+    type Filter: GraphqlInput;
+}
+
+/// An entity that can be ordered with the `order` argument.
+pub trait Sortable: Entity {
+    type Order: GraphqlInput;
+}
+
+/// An entity that can be searched with the `search` argument.
+pub trait Searchable: Entity {}
+
+/// A query for a list of entities of type `E`.
+#[derive(Debug, Clone)]
+pub struct Query<E> {
+    arguments: Vec<(&'static str, String)>,
+    selection: String,
+    entity: std::marker::PhantomData<E>,
+}
+
+impl<E: Entity> Default for Query<E> {
+    fn default() -> Self {
+        Self {
+            arguments: Vec::new(),
+            selection: E::SCALAR_FIELDS.to_string(),
+            entity: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<E: Entity> Query<E> {
+    /// Create a query for all entities of type `E`, selecting their scalar fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn argument(mut self, name: &'static str, value: String) -> Self {
+        self.arguments.retain(|(n, _)| *n != name);
+        self.arguments.push((name, value));
+        self
+    }
+
+    /// Only return the entity with the given ID.
+    pub fn id(self, id: impl Into<String>) -> Self {
+        self.argument("id", to_graphql(&id.into()))
+    }
+
+    /// Return at most `first` entities.
+    pub fn first(self, first: i64) -> Self {
+        self.argument("first", to_graphql(&first))
+    }
+
+    /// Skip the first `offset` entities.
+    pub fn offset(self, offset: i64) -> Self {
+        self.argument("offset", to_graphql(&offset))
+    }
+
+    /// Replace the selection set of the query, e.g. to also select related entities.
+    pub fn select(mut self, selection: impl Into<String>) -> Self {
+        self.selection = selection.into();
+        self
+    }
+
+    /// Return the GraphQL query for this query.
+    pub fn to_graphql(&self) -> String {
+        let mut query = format!("query {{ {}", E::QUERY_FIELD);
+        if !self.arguments.is_empty() {
+            let arguments = self
+                .arguments
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            query.push_str(&format!("({arguments})"));
+        }
+        query.push_str(&format!(" {{ {} }} }}", self.selection));
+        query
+    }
+}
+
+impl<E: Filterable> Query<E> {
+    /// Only return the entities matching `filter`.
+    pub fn filter(self, filter: E::Filter) -> Self {
+        self.argument("filter", to_graphql(&filter))
+    }
+}
+
+impl<E: Sortable> Query<E> {
+    /// Return the entities sorted by `order`.
+    pub fn order(self, order: E::Order) -> Self {
+        self.argument("order", to_graphql(&order))
+    }
+}
+
+impl<E: Searchable> Query<E> {
+    /// Only return the entities whose searchable fields match `text`.
+    pub fn search(self, text: impl Into<String>) -> Self {
+        self.argument("search", format!("{{ text: {} }}", to_graphql(&text.into())))
+    }
+}
+
+/// Client for the GraphQL API of the indexer.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    endpoint: String,
+}
+
+impl Client {
+    /// Create a client for the indexer deployed to the indexer service at `url`,
+    /// e.g. `http://127.0.0.1:29987`.
+    pub fn new(url: &str) -> Self {
+        Self::with_http_client(reqwest::Client::new(), url)
+    }
+
+    /// Create a client that sends its requests with the given HTTP client.
+    pub fn with_http_client(http: reqwest::Client, url: &str) -> Self {
+        let endpoint = format!(
+            "{}/api/graph/{NAMESPACE}/{IDENTIFIER}",
+            url.trim_end_matches('/')
+        );
+        Self { http, endpoint }
+    }
+
+    /// Run `query` and return the matching entities.
+    pub async fn query<E: Entity>(&self, query: &Query<E>) -> ClientResult<Vec<E>> {
+        let data = self.execute(&query.to_graphql()).await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Run a GraphQL query and return the `data` of its response.
+    pub async fn execute(&self, query: &str) -> ClientResult<serde_json::Value> {
+        let res = self
+            .http
+            .post(&self.endpoint)
+            .json(&serde_json::json!({ "query": query }))
+            .send()
+            .await?;
+
+        let status = res.status();
+        let mut body: serde_json::Value = res.json().await?;
+        if !status.is_success() {
+            return Err(ClientError::Service(status, body));
+        }
+
+        Ok(body["data"].take())
+    }
+}
+"#;
+
+#[derive(Debug, Deserialize)]
+struct IntrospectionResponse {
+    #[serde(rename = "__schema")]
+    schema: IntrospectionSchema,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    query_type: NamedType,
+    types: Vec<IntrospectionType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedType {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionType {
+    kind: String,
+    name: String,
+    fields: Option<Vec<IntrospectionField>>,
+    input_fields: Option<Vec<IntrospectionInputValue>>,
+    enum_values: Option<Vec<NamedType>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectionField {
+    name: String,
+    args: Vec<IntrospectionInputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectionInputValue {
+    name: String,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: String,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    /// Return the named type wrapped by this type.
+    fn named(&self) -> &TypeRef {
+        match &self.of_type {
+            Some(of_type) => of_type.named(),
+            None => self,
+        }
+    }
+
+    /// Return the name of the named type wrapped by this type.
+    fn name(&self) -> &str {
+        self.named().name.as_deref().unwrap_or_default()
+    }
+}
+
+pub async fn init(command: Command) -> anyhow::Result<()> {
+    let Command {
+        manifest,
+        path,
+        url,
+        output,
+        verbose,
+    } = command;
+
+    let (root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let schema = match url {
+        Some(url) => {
+            let target = format!(
+                "{url}/api/schema/{}/{}",
+                manifest.namespace(),
+                manifest.identifier()
+            );
+            if verbose {
+                info!(
+                    "\n🔎 Reading the schema of '{}' from {target}",
+                    manifest.uid()
+                );
+            }
+            remote_introspection(&target).await?
+        }
+        None => {
+            let workspace_root = cargo_workspace_root_dir(root_dir.as_path())?;
+            let schema_path = workspace_root.join(manifest.graphql_schema());
+            if verbose {
+                info!(
+                    "\n🔎 Reading the schema of '{}' from {}",
+                    manifest.uid(),
+                    schema_path.display()
+                );
+            }
+            local_introspection(&manifest, &schema_path).await?
+        }
+    };
+
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "{}-{}-client",
+            manifest.namespace(),
+            manifest.identifier()
+        ))
+    });
+    let crate_name = output
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow::anyhow!("❌ Invalid output path: {}", output.display()))?
+        .to_string();
+
+    fs::create_dir_all(output.join("src"))?;
+
+    // The manifest is only written once, so that users can add to it.
+    let cargo_manifest_path = output.join(CARGO_MANIFEST_FILE_NAME);
+    if !cargo_manifest_path.exists() {
+        fs::write(
+            &cargo_manifest_path,
+            defaults::default_client_cargo_toml(&crate_name),
+        )?;
+    }
+
+    let lib_path = output.join("src").join("lib.rs");
+    fs::write(&lib_path, generate_client(&manifest, &schema)?)?;
+
+    // The generated code is valid either way, so formatting it is best effort.
+    let _ = process::Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(&lib_path)
+        .status();
+
+    info!(
+        "\n✅ Successfully generated a client for indexer '{}' in {}\n",
+        manifest.uid(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Build the dynamic schema of the indexer from its GraphQL schema file and introspect it.
+async fn local_introspection(
+    manifest: &Manifest,
+    schema_path: &Path,
+) -> anyhow::Result<IntrospectionSchema> {
+    let schema = fs::read_to_string(schema_path).map_err(|e| {
+        anyhow::anyhow!(
+            "❌ Failed to read GraphQL schema '{}': {e}",
+            schema_path.display()
+        )
+    })?;
+
+    let schema = IndexerSchema::new(
+        manifest.namespace(),
+        manifest.identifier(),
+        &GraphQLSchema::new(schema),
+        DbType::Postgres,
+        manifest.execution_source(),
+    )?;
+    let dynamic_schema = build_dynamic_schema(&schema)?;
+
+    let response: IntrospectionResponse =
+        serde_json::from_value(introspect(&dynamic_schema).await?)?;
+    Ok(response.schema)
+}
+
+/// Download the introspection of the dynamic schema of the indexer deployed at
+/// `target`, an `/api/schema` endpoint.
+async fn remote_introspection(target: &str) -> anyhow::Result<IntrospectionSchema> {
+    let res = Client::new().get(target).send().await?;
+
+    let status = res.status();
+    let mut body = res.json::<Value>().await?;
+    if status != StatusCode::OK {
+        anyhow::bail!("❌ {target} returned a non-200 response code: {status:?}\n{body}");
+    }
+
+    let response: IntrospectionResponse =
+        serde_json::from_value(body["introspection"].take())?;
+    Ok(response.schema)
+}
+
+/// Generate the `lib.rs` of the client crate from the introspected schema.
+fn generate_client(
+    manifest: &Manifest,
+    schema: &IntrospectionSchema,
+) -> anyhow::Result<String> {
+    let mut lib = String::new();
+    writeln!(
+        lib,
+        "//! Typed client for the `{}` indexer.\n//!\n//! This file is generated by `forc index codegen`. Do not edit it by hand.\n",
+        manifest.uid()
+    )?;
+    writeln!(lib, "#![allow(clippy::all)]\n")?;
+    writeln!(
+        lib,
+        "/// Namespace of the indexer.\npub const NAMESPACE: &str = {:?};\n",
+        manifest.namespace()
+    )?;
+    writeln!(
+        lib,
+        "/// Identifier of the indexer.\npub const IDENTIFIER: &str = {:?};\n",
+        manifest.identifier()
+    )?;
+    lib.push_str(CLIENT_RUNTIME);
+    lib.push_str(&generate_schema_items(schema)?);
+
+    Ok(lib)
+}
+
+/// Generate the items of the client that depend on the indexer's schema: the
+/// `entities` module, and the `inputs` module of their filter and order arguments.
+fn generate_schema_items(schema: &IntrospectionSchema) -> anyhow::Result<String> {
+    let types = schema
+        .types
+        .iter()
+        .filter(|t| !t.name.starts_with("__"))
+        .map(|t| (t.name.as_str(), t))
+        .collect::<BTreeMap<_, _>>();
+
+    // Each entity is queried using the field of the query root that returns it.
+    let query_fields = types
+        .get(schema.query_type.name.as_str())
+        .and_then(|t| t.fields.as_ref())
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|f| (f.ty.name(), f))
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let mut lib = String::new();
+    let mut entities = Vec::new();
+    for t in types
+        .values()
+        .filter(|t| t.kind == "OBJECT" && t.name != schema.query_type.name)
+    {
+        let mut item = String::new();
+        generate_entity(&mut item, t, &types, query_fields.get(t.name.as_str()))?;
+        entities.push(item);
+    }
+    writeln!(
+        lib,
+        "\n/// Entities of the indexer.\npub mod entities {{\n{}}}",
+        entities.join("\n")
+    )?;
+
+    let mut inputs = Vec::new();
+    for t in types.values() {
+        let mut item = String::new();
+        match t.kind.as_str() {
+            "INPUT_OBJECT" => generate_input_object(&mut item, t, &types)?,
+            "ENUM" => generate_enum(&mut item, t)?,
+            _ => continue,
+        }
+        inputs.push(item);
+    }
+    writeln!(
+        lib,
+        "\n/// Filter and order arguments of the entities of the indexer.\npub mod inputs {{\n{}}}",
+        inputs.join("\n")
+    )?;
+
+    Ok(lib)
+}
+
+/// Generate the struct of an entity, along with its `Entity` implementation.
+fn generate_entity(
+    lib: &mut String,
+    t: &IntrospectionType,
+    types: &BTreeMap<&str, &IntrospectionType>,
+    query_field: Option<&&IntrospectionField>,
+) -> anyhow::Result<()> {
+    let name = type_ident(&t.name);
+    let fields = t.fields.as_deref().unwrap_or_default();
+
+    // Every field is optional, since only the selected fields are returned.
+    writeln!(lib, "    /// The `{}` entity.", t.name)?;
+    writeln!(
+        lib,
+        "    #[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]\n    #[serde(default)]\n    pub struct {name} {{"
+    )?;
+    for field in fields {
+        let ident = field_ident(&field.name);
+        if ident.trim_start_matches("r#") != field.name {
+            writeln!(lib, "        #[serde(rename = {:?})]", field.name)?;
+        }
+        writeln!(
+            lib,
+            "        pub {ident}: Option<{}>,",
+            output_type(&field.ty, types)
+        )?;
+    }
+    writeln!(lib, "    }}")?;
+
+    let Some(query_field) = query_field else {
+        return Ok(());
+    };
+
+    let scalar_fields = fields
+        .iter()
+        .filter(|f| !matches!(types.get(f.ty.name()), Some(t) if t.kind == "OBJECT"))
+        .map(|f| f.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    writeln!(
+        lib,
+        "\n    impl crate::Entity for {name} {{\n        const QUERY_FIELD: &'static str = {:?};\n        const SCALAR_FIELDS: &'static str = {scalar_fields:?};\n    }}",
+        query_field.name
+    )?;
+
+    for arg in &query_field.args {
+        match arg.name.as_str() {
+            "filter" => writeln!(
+                lib,
+                "\n    impl crate::Filterable for {name} {{\n        type Filter = crate::inputs::{};\n    }}",
+                type_ident(arg.ty.name())
+            )?,
+            "order" => writeln!(
+                lib,
+                "\n    impl crate::Sortable for {name} {{\n        type Order = crate::inputs::{};\n    }}",
+                type_ident(arg.ty.name())
+            )?,
+            "search" => {
+                writeln!(lib, "\n    impl crate::Searchable for {name} {{}}")?
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate the builder of a filter or order input object.
+fn generate_input_object(
+    lib: &mut String,
+    t: &IntrospectionType,
+    types: &BTreeMap<&str, &IntrospectionType>,
+) -> anyhow::Result<()> {
+    let name = type_ident(&t.name);
+    let fields = t.input_fields.as_deref().unwrap_or_default();
+
+    writeln!(lib, "    /// The `{}` input object.", t.name)?;
+    writeln!(
+        lib,
+        "    #[derive(Debug, Clone, Default, PartialEq)]\n    pub struct {name} {{"
+    )?;
+    for field in fields {
+        writeln!(
+            lib,
+            "        pub {}: Option<{}>,",
+            field_ident(&field.name),
+            input_type(&field.ty, types)
+        )?;
+    }
+    writeln!(lib, "    }}")?;
+
+    writeln!(
+        lib,
+        "\n    impl {name} {{\n        pub fn new() -> Self {{\n            Self::default()\n        }}"
+    )?;
+    for field in fields {
+        let ident = field_ident(&field.name);
+        let ty = input_type(&field.ty, types);
+        match ty.strip_prefix("Vec<").and_then(|ty| ty.strip_suffix('>')) {
+            Some(item) => writeln!(
+                lib,
+                "\n        pub fn {ident}(mut self, values: impl IntoIterator<Item = impl Into<{item}>>) -> Self {{\n            self.{ident} = Some(values.into_iter().map(Into::into).collect());\n            self\n        }}"
+            )?,
+            None => writeln!(
+                lib,
+                "\n        pub fn {ident}(mut self, value: impl Into<{ty}>) -> Self {{\n            self.{ident} = Some(value.into());\n            self\n        }}"
+            )?,
+        }
+    }
+    writeln!(lib, "    }}")?;
+
+    writeln!(
+        lib,
+        "\n    impl crate::GraphqlInput for {name} {{\n        fn write_graphql(&self, out: &mut String) {{\n            out.push('{{');"
+    )?;
+    for field in fields {
+        writeln!(
+            lib,
+            "            if let Some(value) = &self.{} {{\n                out.push_str(\" {}: \");\n                value.write_graphql(out);\n            }}",
+            field_ident(&field.name),
+            field.name
+        )?;
+    }
+    writeln!(
+        lib,
+        "            out.push_str(\" }}\");\n        }}\n    }}"
+    )?;
+
+    Ok(())
+}
+
+/// Generate an enum, e.g. `SortOrder` or the fields of an entity.
+fn generate_enum(lib: &mut String, t: &IntrospectionType) -> anyhow::Result<()> {
+    let name = type_ident(&t.name);
+    let values = t.enum_values.as_deref().unwrap_or_default();
+
+    writeln!(lib, "    /// The `{}` enum.", t.name)?;
+    writeln!(
+        lib,
+        "    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n    pub enum {name} {{"
+    )?;
+    for value in values {
+        writeln!(lib, "        {},", type_ident(&value.name))?;
+    }
+    writeln!(lib, "    }}")?;
+
+    writeln!(
+        lib,
+        "\n    impl crate::GraphqlInput for {name} {{\n        fn write_graphql(&self, out: &mut String) {{\n            out.push_str(match self {{"
+    )?;
+    for value in values {
+        writeln!(
+            lib,
+            "                Self::{} => {:?},",
+            type_ident(&value.name),
+            value.name
+        )?;
+    }
+    writeln!(lib, "            }});\n        }}\n    }}")?;
+
+    Ok(())
+}
+
+/// Return the Rust type of an entity field.
+fn output_type(ty: &TypeRef, types: &BTreeMap<&str, &IntrospectionType>) -> String {
+    match ty.kind.as_str() {
+        "NON_NULL" => output_type(ty.of_type.as_deref().unwrap_or(ty), types),
+        "LIST" => format!(
+            "Vec<{}>",
+            output_type(ty.of_type.as_deref().unwrap_or(ty), types)
+        ),
+        _ => {
+            let name = ty.name();
+            match types.get(name).map(|t| t.kind.as_str()) {
+                Some("OBJECT") => format!("Box<{}>", type_ident(name)),
+                _ => output_scalar_type(name).to_string(),
+            }
+        }
+    }
+}
+
+/// Return the Rust type of a scalar entity field, based on how the indexer
+/// service returns values of that scalar.
+fn output_scalar_type(name: &str) -> &'static str {
+    match name {
+        "Json" | "Virtual" => "serde_json::Value",
+        _ => primitive_scalar_type(name).unwrap_or("String"),
+    }
+}
+
+/// Return the Rust type of a boolean or numeric scalar, or `None` if the scalar
+/// is represented by a string.
+fn primitive_scalar_type(name: &str) -> Option<&'static str> {
+    match name {
+        "Boolean" => Some("bool"),
+        "Float" => Some("f64"),
+        "Int1" | "Int4" => Some("i32"),
+        "Int" | "Int8" | "Timestamp" => Some("i64"),
+        "Int16" => Some("i128"),
+        "UInt1" | "UInt4" | "BlockHeight" => Some("u32"),
+        "UInt8" => Some("u64"),
+        "UInt16" => Some("u128"),
+        _ => None,
+    }
+}
+
+/// Return the Rust type of an input object field.
+fn input_type(ty: &TypeRef, types: &BTreeMap<&str, &IntrospectionType>) -> String {
+    match ty.kind.as_str() {
+        "NON_NULL" => input_type(ty.of_type.as_deref().unwrap_or(ty), types),
+        "LIST" => format!(
+            "Vec<{}>",
+            input_type(ty.of_type.as_deref().unwrap_or(ty), types)
+        ),
+        _ => {
+            let name = ty.name();
+            match types.get(name).map(|t| t.kind.as_str()) {
+                // Input objects can refer to each other, so they are boxed.
+                Some("INPUT_OBJECT") => format!("Box<{}>", type_ident(name)),
+                Some("ENUM") => type_ident(name),
+                _ => primitive_scalar_type(name).unwrap_or("String").to_string(),
+            }
+        }
+    }
+}
+
+/// Convert a GraphQL type or enum value name into a Rust type or variant name,
+/// e.g. `Foo_bar_ComplexComparisonObject` into `FooBarComplexComparisonObject`.
+fn type_ident(name: &str) -> String {
+    let ident = name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+    escape_ident(ident)
+}
+
+/// Convert a GraphQL field name into a Rust field name, e.g. `startsWith` into
+/// `starts_with`.
+fn field_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut prev_is_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_is_lower {
+            ident.push('_');
+        }
+        prev_is_lower = c.is_lowercase() || c.is_ascii_digit();
+        ident.extend(c.to_lowercase());
+    }
+    escape_ident(ident)
+}
+
+fn escape_ident(ident: String) -> String {
+    if RESERVED_IDENTIFIERS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    /// Generate the client of the indexer with the given manifest and schema.
+    async fn client(manifest: &Manifest, schema_path: &Path) -> String {
+        let schema = local_introspection(manifest, schema_path).await.unwrap();
+        generate_client(manifest, &schema).unwrap()
+    }
+
+    /// Generate the items of the client of the indexer with the given manifest and
+    /// schema that depend on its schema.
+    async fn schema_items(manifest: &Manifest, schema_path: &Path) -> String {
+        let schema = local_introspection(manifest, schema_path).await.unwrap();
+        generate_schema_items(&schema).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generated_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("schema.graphql");
+        fs::write(
+            &schema_path,
+            r#"
+type Account @entity {
+  id: ID!
+  address: Address!
+  balance: UInt8!
+  nonce: UInt4
+  label: Charfield
+}
+
+type Payment @entity {
+  id: ID!
+  from: Account!
+  amount: UInt8!
+  fee: Int8
+  block_height: BlockHeight!
+  memo: Json
+}
+"#,
+        )
+        .unwrap();
+
+        let manifest = Manifest::try_from(
+            format!(
+                "namespace: test_namespace\nidentifier: index1\ngraphql_schema: {}\nmodule:\n  wasm: indexer.wasm\ncontract_id: ~\nstart_block: ~\nend_block: ~\n",
+                schema_path.display()
+            )
+            .as_str(),
+        )
+        .unwrap();
+
+        insta::assert_snapshot!(schema_items(&manifest, &schema_path).await);
+    }
+
+    #[tokio::test]
+    #[ignore = "builds the generated client and its dependencies, which takes minutes"]
+    async fn test_generated_client_of_test_indexer_compiles() {
+        let root = workspace_root();
+        let manifest = Manifest::from_file(root.join(
+            "packages/fuel-indexer-tests/indexers/fuel-indexer-test/fuel_indexer_test.yaml",
+        ))
+        .unwrap();
+        let lib = client(&manifest, &root.join(manifest.graphql_schema())).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join(CARGO_MANIFEST_FILE_NAME),
+            defaults::default_client_cargo_toml("fuel-indexer-test-client"),
+        )
+        .unwrap();
+        fs::write(dir.path().join("src").join("lib.rs"), lib).unwrap();
+
+        // Pin the dependencies of the client to the versions used by the workspace,
+        // so that it can be built offline.
+        fs::copy(root.join("Cargo.lock"), dir.path().join("Cargo.lock")).unwrap();
+
+        let status =
+            process::Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()))
+                .args(["check", "--offline", "--quiet"])
+                .current_dir(dir.path())
+                .env(
+                    "CARGO_TARGET_DIR",
+                    root.join("target").join("codegen-client"),
+                )
+                .status()
+                .unwrap();
+
+        assert!(status.success());
+    }
+}
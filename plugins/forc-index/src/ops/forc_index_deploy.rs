@@ -3,7 +3,7 @@ use crate::{
     commands::{build, remove},
     utils::{file_part, project_dir_info},
 };
-use fuel_indexer_lib::manifest::Manifest;
+use fuel_indexer_lib::manifest::{ConfigSchema, Manifest};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::{
     header::{HeaderMap, AUTHORIZATION, CONNECTION},
@@ -30,6 +30,7 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
         replace_indexer,
         remove_data,
         skip_build,
+        blue_green,
     } = command;
 
     if !skip_build {
@@ -75,8 +76,15 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
 
     let manifest_module_file = workspace_root.join(manifest.module());
 
+    if let Some(config_schema) =
+        ConfigSchema::from_wasm(&std::fs::read(&manifest_module_file)?)?
+    {
+        manifest.validate_config(&config_schema)?;
+    }
+
     let form = Form::new()
         .text("replace_indexer", replace_indexer.to_string())
+        .text("blue_green", blue_green.to_string())
         .part("manifest", file_part(&manifest_path).await?)
         .part("schema", file_part(manifest_schema_file).await?)
         .part("wasm", file_part(manifest_module_file).await?);
@@ -0,0 +1,43 @@
+use crate::{cli::DryRunCommand, ops::forc_index_start::indexer_command};
+use clap::ValueEnum;
+use tracing::info;
+
+pub fn init(command: DryRunCommand) -> anyhow::Result<()> {
+    let DryRunCommand {
+        indexer,
+        start_block,
+        end_block,
+        output_format,
+        output,
+    } = command;
+
+    let verbose = indexer.verbose;
+    let mut cmd = indexer_command("dry-run", indexer);
+
+    cmd.arg("--start-block").arg(start_block.to_string());
+    cmd.arg("--end-block").arg(end_block.to_string());
+
+    if let Some(format) = output_format.to_possible_value() {
+        cmd.arg("--output-format").arg(format.get_name());
+    }
+
+    if let Some(output) = &output {
+        cmd.arg("--output").arg(output);
+    }
+
+    if verbose {
+        info!("{cmd:?}");
+    }
+
+    // Unlike `forc index start`, wait for the dry run to finish, so that its output
+    // can be piped elsewhere.
+    let status = cmd
+        .status()
+        .map_err(|e| anyhow::anyhow!("❌ Failed to run fuel-indexer: {e:?}."))?;
+
+    if !status.success() {
+        anyhow::bail!("❌ Dry run failed.");
+    }
+
+    Ok(())
+}
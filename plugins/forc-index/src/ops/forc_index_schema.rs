@@ -0,0 +1,69 @@
+use crate::{commands::schema::Command, utils::project_dir_info};
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{Client, StatusCode};
+use serde_json::{to_string_pretty, value::Value, Map};
+use tracing::info;
+
+pub async fn init(command: Command) -> anyhow::Result<()> {
+    let Command {
+        url,
+        manifest,
+        path,
+        output,
+        json,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/schema/{}/{}",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    if verbose {
+        info!(
+            "\n🔎 Downloading the schema of '{}' from {target}",
+            manifest.uid()
+        );
+    }
+
+    let res = Client::new().get(&target).send().await?;
+
+    let status = res.status();
+    let mut res_json = res.json::<Map<String, Value>>().await?;
+
+    if status != StatusCode::OK {
+        anyhow::bail!(
+            "❌ {target} returned a non-200 response code: {status:?}\n{}",
+            to_string_pretty(&res_json)?
+        );
+    }
+
+    let schema = if json {
+        to_string_pretty(&res_json.remove("introspection").unwrap_or_default())?
+    } else {
+        res_json
+            .remove("sdl")
+            .and_then(|sdl| sdl.as_str().map(str::to_string))
+            .unwrap_or_default()
+    };
+
+    match output {
+        Some(output) => {
+            std::fs::write(&output, schema)?;
+            info!(
+                "\n✅ Successfully downloaded the schema of '{}' to {}\n",
+                manifest.uid(),
+                output.display()
+            );
+        }
+        None => println!("{schema}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use crate::{
+    commands::snapshot::{ExportCommand, ImportCommand},
+    utils::project_dir_info,
+};
+use fuel_indexer_database_types::IndexerSnapshot;
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION, CONTENT_TYPE},
+    Client, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+pub async fn export(command: ExportCommand) -> anyhow::Result<()> {
+    let ExportCommand {
+        url,
+        manifest,
+        path,
+        output,
+        auth,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/snapshot/{}/{}",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    if verbose {
+        info!("\n📸 Exporting indexer '{}' from {target}", manifest.uid());
+    } else {
+        info!("\n📸 Exporting indexer.")
+    }
+
+    let res = Client::new()
+        .get(&target)
+        .headers(headers)
+        .send()
+        .await
+        .expect("Failed to export indexer.");
+
+    let status = res.status();
+    if status != StatusCode::OK {
+        let res_json = res
+            .json::<Map<String, Value>>()
+            .await
+            .expect("Failed to read JSON response.");
+
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        }
+        info!("\n{}", to_string_pretty(&res_json)?);
+
+        return Ok(());
+    }
+
+    let output = output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.snapshot.json", manifest.uid())));
+    let bytes = res.bytes().await?;
+    std::fs::write(&output, &bytes)?;
+
+    info!(
+        "\n✅ Successfully exported indexer '{}' to {}\n",
+        manifest.uid(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+pub async fn import(command: ImportCommand) -> anyhow::Result<()> {
+    let ImportCommand {
+        snapshot,
+        url,
+        auth,
+        verbose,
+    } = command;
+
+    let bytes = std::fs::read(&snapshot)?;
+    let IndexerSnapshot {
+        namespace,
+        identifier,
+        block_height,
+        ..
+    } = serde_json::from_slice(&bytes)?;
+
+    let target = format!("{url}/api/snapshot/{namespace}/{identifier}");
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "application/json".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    if verbose {
+        info!(
+            "\n📦 Importing indexer '{namespace}.{identifier}' at block {block_height} to {target}"
+        );
+    } else {
+        info!("\n📦 Importing indexer.")
+    }
+
+    let res = Client::new()
+        .post(&target)
+        .headers(headers)
+        .body(bytes)
+        .send()
+        .await
+        .expect("Failed to import indexer.");
+
+    let status = res.status();
+    let res_json = res
+        .json::<Map<String, Value>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    if status != StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        }
+        info!("\n{}", to_string_pretty(&res_json)?);
+
+        return Ok(());
+    }
+
+    if verbose {
+        info!(
+            "\n{}\n✅ Successfully imported indexer '{namespace}.{identifier}' at {target}\n",
+            to_string_pretty(&res_json)?,
+        );
+    } else {
+        info!("\n✅ Successfully imported indexer\n");
+    }
+
+    Ok(())
+}
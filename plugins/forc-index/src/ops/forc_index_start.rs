@@ -4,11 +4,33 @@ use std::{ffi::OsStr, process::Command};
 use tracing::info;
 
 pub async fn init(command: StartCommand) -> anyhow::Result<()> {
+    let verbose = command.verbose;
+    let mut cmd = indexer_command("run", command);
+
+    if verbose {
+        info!("{cmd:?}");
+    }
+
+    match cmd.spawn() {
+        Ok(child) => {
+            let pid = child.id();
+            info!("✅ Successfully started the indexer service at PID {pid}");
+        }
+        Err(e) => panic!("❌ Failed to spawn fuel-indexer child process: {e:?}."),
+    }
+
+    Ok(())
+}
+
+/// Build a `fuel-indexer` command for the given subcommand, passing along the
+/// indexer service options.
+pub(crate) fn indexer_command(subcommand: &str, command: StartCommand) -> Command {
     let StartCommand {
         manifest,
         config,
         fuel_node_host,
         fuel_node_port,
+        fuel_node_fallbacks,
         web_api_host,
         web_api_port,
         log_level,
@@ -29,20 +51,29 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         verbose,
         local_fuel_node,
         max_body_size,
+        max_snapshot_size,
         stop_idle_indexers,
         indexer_net_config,
         rate_limit,
         rate_limit_request_count,
         rate_limit_window_size,
         metering_points,
+        max_memory_pages,
+        handler_timeout,
         replace_indexer,
         remove_data,
         accept_sql_queries,
         block_page_size,
+        deployment_grace_period,
+        entity_cache_size,
+        telemetry,
+        otlp_endpoint,
+        telemetry_service_name,
+        telemetry_sample_ratio,
     } = command;
 
     let mut cmd = Command::new("fuel-indexer");
-    cmd.arg("run");
+    cmd.arg(subcommand);
 
     if let Some(m) = &manifest {
         cmd.arg("--manifest").arg(m);
@@ -63,19 +94,37 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         // Options that have default values
         cmd.arg("--fuel-node-host").arg(&fuel_node_host);
         cmd.arg("--fuel-node-port").arg(&fuel_node_port);
+        if !fuel_node_fallbacks.is_empty() {
+            let fuel_node_fallbacks = fuel_node_fallbacks
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            cmd.arg("--fuel-node-fallbacks").arg(&fuel_node_fallbacks);
+        }
         cmd.arg("--web-api-host").arg(&web_api_host);
         cmd.arg("--web-api-port").arg(&web_api_port);
         cmd.arg("--log-level").arg(&log_level);
         cmd.arg("--max-body-size")
             .arg(OsStr::new(&max_body_size.to_string()));
+        cmd.arg("--max-snapshot-size")
+            .arg(OsStr::new(&max_snapshot_size.to_string()));
         cmd.arg("--rate-limit-request-count")
             .arg(rate_limit_request_count);
         cmd.arg("--rate-limit-window-size")
             .arg(rate_limit_window_size);
         cmd.arg("--metering-points")
             .arg(OsStr::new(&metering_points.to_string()));
+        cmd.arg("--max-memory-pages")
+            .arg(OsStr::new(&max_memory_pages.to_string()));
+        cmd.arg("--handler-timeout")
+            .arg(OsStr::new(&handler_timeout.to_string()));
         cmd.arg("--block-page-size")
             .arg(OsStr::new(&block_page_size.to_string()));
+        cmd.arg("--deployment-grace-period")
+            .arg(OsStr::new(&deployment_grace_period.to_string()));
+        cmd.arg("--entity-cache-size")
+            .arg(OsStr::new(&entity_cache_size.to_string()));
 
         // Bool options
         let options = [
@@ -91,6 +140,7 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--auth-enabled", auth_enabled),
             ("--verbose", verbose),
             ("--local-fuel-node", local_fuel_node),
+            ("--telemetry", telemetry),
         ];
         for (opt, value) in options.iter() {
             if *value {
@@ -104,6 +154,12 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--jwt-secret", jwt_secret),
             ("--jwt-issuer", jwt_issuer),
             ("--jwt-expiry", jwt_expiry.map(|x| x.to_string())),
+            ("--otlp-endpoint", otlp_endpoint),
+            ("--telemetry-service-name", telemetry_service_name),
+            (
+                "--telemetry-sample-ratio",
+                telemetry_sample_ratio.map(|x| x.to_string()),
+            ),
         ];
         for (opt, value) in options.iter() {
             if let Some(value) = value {
@@ -134,17 +190,5 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         }
     }
 
-    if verbose {
-        info!("{cmd:?}");
-    }
-
-    match cmd.spawn() {
-        Ok(child) => {
-            let pid = child.id();
-            info!("✅ Successfully started the indexer service at PID {pid}");
-        }
-        Err(e) => panic!("❌ Failed to spawn fuel-indexer child process: {e:?}."),
-    }
-
-    Ok(())
+    cmd
 }
@@ -1,9 +1,13 @@
 pub mod forc_index_auth;
 pub mod forc_index_build;
 pub mod forc_index_check;
+pub mod forc_index_codegen;
 pub mod forc_index_deploy;
+pub mod forc_index_dry_run;
 pub mod forc_index_kill;
 pub mod forc_index_new;
 pub mod forc_index_remove;
+pub mod forc_index_schema;
+pub mod forc_index_snapshot;
 pub mod forc_index_start;
 pub mod forc_index_status;